@@ -0,0 +1,14 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Handles received datagrams.
+pub trait DatagramHandler<SA: DatagramSocketAddress>
+{
+	/// Handle a datagram received from `remote_peer_address`.
+	///
+	/// `payload` is only valid for the duration of the call; replies should be queued using `reply_queue` and are sent in batches.
+	///
+	/// Returning an error stops the reactor; errors relating to just one remote peer (eg a malformed request) should be handled by discarding the datagram or queuing an error reply instead.
+	fn handle_datagram(&mut self, remote_peer_address: SA, payload: &[u8], reply_queue: &mut ReplyQueue<SA>) -> Result<(), Box<dyn error::Error>>;
+}
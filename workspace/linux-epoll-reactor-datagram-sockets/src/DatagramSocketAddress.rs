@@ -0,0 +1,87 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A socket address for a datagram socket.
+pub trait DatagramSocketAddress: Sized + Copy + Debug
+{
+	#[doc(hidden)]
+	type SD: Copy;
+
+	#[doc(hidden)]
+	const AddressFamily: c_int;
+
+	#[doc(hidden)]
+	#[inline(always)]
+	fn configure_socket(_raw_file_descriptor: RawFd) -> io::Result<()>
+	{
+		Ok(())
+	}
+
+	#[doc(hidden)]
+	fn to_socket_data(&self) -> Self::SD;
+
+	#[doc(hidden)]
+	fn from_socket_data(socket_data: &Self::SD) -> Self;
+}
+
+impl DatagramSocketAddress for SocketAddrV4
+{
+	type SD = sockaddr_in;
+
+	const AddressFamily: c_int = AF_INET;
+
+	#[inline(always)]
+	fn to_socket_data(&self) -> Self::SD
+	{
+		let mut socket_data: sockaddr_in = unsafe { zeroed() };
+		socket_data.sin_family = AF_INET as sa_family_t;
+		socket_data.sin_port = self.port().to_be();
+		socket_data.sin_addr = in_addr
+		{
+			s_addr: u32::from(*self.ip()).to_be(),
+		};
+		socket_data
+	}
+
+	#[inline(always)]
+	fn from_socket_data(socket_data: &Self::SD) -> Self
+	{
+		SocketAddrV4::new(Ipv4Addr::from(u32::from_be(socket_data.sin_addr.s_addr)), u16::from_be(socket_data.sin_port))
+	}
+}
+
+impl DatagramSocketAddress for SocketAddrV6
+{
+	type SD = sockaddr_in6;
+
+	const AddressFamily: c_int = AF_INET6;
+
+	/// Internet Protocol version 4 datagrams are not received on Internet Protocol version 6 sockets; use a separate reactor.
+	#[inline(always)]
+	fn configure_socket(raw_file_descriptor: RawFd) -> io::Result<()>
+	{
+		DatagramSocketFileDescriptor::set_socket_option(raw_file_descriptor, IPPROTO_IPV6, IPV6_V6ONLY, 1)
+	}
+
+	#[inline(always)]
+	fn to_socket_data(&self) -> Self::SD
+	{
+		let mut socket_data: sockaddr_in6 = unsafe { zeroed() };
+		socket_data.sin6_family = AF_INET6 as sa_family_t;
+		socket_data.sin6_port = self.port().to_be();
+		socket_data.sin6_flowinfo = self.flowinfo();
+		socket_data.sin6_addr = in6_addr
+		{
+			s6_addr: self.ip().octets(),
+		};
+		socket_data.sin6_scope_id = self.scope_id();
+		socket_data
+	}
+
+	#[inline(always)]
+	fn from_socket_data(socket_data: &Self::SD) -> Self
+	{
+		SocketAddrV6::new(Ipv6Addr::from(socket_data.sin6_addr.s6_addr), u16::from_be(socket_data.sin6_port), socket_data.sin6_flowinfo, socket_data.sin6_scope_id)
+	}
+}
@@ -0,0 +1,182 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Number of datagrams received by one `recvmmsg()` or sent by one `sendmmsg()`.
+const BatchSize: usize = 32;
+
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct DatagramSocketCommon<SA: DatagramSocketAddress, DH: DatagramHandler<SA>>
+{
+	datagram_socket_file_descriptor: DatagramSocketFileDescriptor,
+	datagram_handler: DH,
+	reply_queue: ReplyQueue<SA>,
+	receive_buffers: Box<[u8]>,
+	maximum_datagram_size: usize,
+}
+
+impl<SA: DatagramSocketAddress, DH: DatagramHandler<SA>> DatagramSocketCommon<SA, DH>
+{
+	#[inline(always)]
+	fn do_initial_input_and_output_and_register_with_epoll_if_necesssary<A: Arena<DSR>, DSR: DatagramSocketReactor<SA, DH>, EPR: EventPollRegister>(event_poll_register: &EPR, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, (settings, socket_address, datagram_handler): (Arc<DatagramSocketSettings>, SA, DH)) -> Result<(), EventPollRegistrationError>
+	{
+		let datagram_socket_file_descriptor = DatagramSocketFileDescriptor::new(&settings, socket_address).map_err(|error| EventPollRegistrationError::InitialInputOrOutputFailed(Box::new(error)))?;
+
+		event_poll_register.register::<A, DSR, _>(arena, reactor_compressed_type_identifier, datagram_socket_file_descriptor, EPollAddFlags::EdgeTriggeredInput | EPollAddFlags::Output, None, |uninitialized_reactor, datagram_socket_file_descriptor|
+		{
+			uninitialized_reactor.initialize
+			(
+				Self
+				{
+					datagram_socket_file_descriptor,
+					datagram_handler,
+					reply_queue: ReplyQueue::new(settings.maximum_queued_replies),
+					receive_buffers: vec![0; BatchSize * settings.maximum_datagram_size].into_boxed_slice(),
+					maximum_datagram_size: settings.maximum_datagram_size,
+				}
+			);
+			Ok(())
+		})
+	}
+
+	#[inline(always)]
+	fn react(&mut self, event_flags: EPollEventFlags, terminate: &impl Terminate) -> Result<bool, String>
+	{
+		if event_flags.contains(EPollEventFlags::Output)
+		{
+			self.send_queued_replies()
+		}
+
+		if event_flags.contains(EPollEventFlags::Input)
+		{
+			while terminate.should_continue()
+			{
+				let more_may_be_available = self.receive_batch()?;
+
+				self.send_queued_replies();
+
+				if !more_may_be_available
+				{
+					break
+				}
+			}
+		}
+
+		Ok(false)
+	}
+
+	/// Returns `Ok(false)` if no more datagrams are available to receive.
+	#[inline(always)]
+	fn receive_batch(&mut self) -> Result<bool, String>
+	{
+		let mut remote_peer_addresses: [SA::SD; BatchSize] = unsafe { zeroed() };
+		let mut buffers: [iovec; BatchSize] = unsafe { zeroed() };
+		let mut message_headers: [mmsghdr; BatchSize] = unsafe { zeroed() };
+
+		for index in 0 .. BatchSize
+		{
+			buffers[index] = iovec
+			{
+				iov_base: self.receive_buffers[index * self.maximum_datagram_size .. ].as_mut_ptr() as *mut c_void,
+				iov_len: self.maximum_datagram_size,
+			};
+
+			let message_header = &mut message_headers[index].msg_hdr;
+			message_header.msg_name = &mut remote_peer_addresses[index] as *mut SA::SD as *mut c_void;
+			message_header.msg_namelen = size_of::<SA::SD>() as socklen_t;
+			message_header.msg_iov = &mut buffers[index];
+			message_header.msg_iovlen = 1;
+		}
+
+		let result = unsafe { recvmmsg(self.datagram_socket_file_descriptor.as_raw_fd(), message_headers.as_mut_ptr(), BatchSize as c_uint, MSG_DONTWAIT, null_mut()) };
+
+		if unlikely!(result == -1)
+		{
+			let error = io::Error::last_os_error();
+			return match error.kind()
+			{
+				ErrorKind::WouldBlock => Ok(false),
+
+				ErrorKind::Interrupted => Ok(true),
+
+				_ => Err(format!("Could not receive datagrams: {}", error)),
+			}
+		}
+
+		let number_of_datagrams_received = result as usize;
+		for index in 0 .. number_of_datagrams_received
+		{
+			let message_header = &message_headers[index];
+
+			// Datagrams larger than `maximum_datagram_size` are discarded rather than passed to the handler truncated.
+			if unlikely!(message_header.msg_hdr.msg_flags & MSG_TRUNC != 0)
+			{
+				continue
+			}
+
+			let remote_peer_address = SA::from_socket_data(&remote_peer_addresses[index]);
+			let start = index * self.maximum_datagram_size;
+			let payload = &self.receive_buffers[start .. start + (message_header.msg_len as usize)];
+
+			if let Err(error) = self.datagram_handler.handle_datagram(remote_peer_address, payload, &mut self.reply_queue)
+			{
+				return Err(format!("Could not handle datagram: {}", error))
+			}
+		}
+
+		Ok(number_of_datagrams_received == BatchSize)
+	}
+
+	/// Sends until the queue is empty or the socket would block; the remainder are sent when the socket becomes writable again.
+	#[inline(always)]
+	fn send_queued_replies(&mut self)
+	{
+		while !self.reply_queue.is_empty()
+		{
+			let mut remote_peer_addresses: [SA::SD; BatchSize] = unsafe { zeroed() };
+			let mut buffers: [iovec; BatchSize] = unsafe { zeroed() };
+			let mut message_headers: [mmsghdr; BatchSize] = unsafe { zeroed() };
+
+			let mut number_of_replies = 0;
+			for &(ref remote_peer_address, ref payload) in self.reply_queue.front(BatchSize)
+			{
+				let index = number_of_replies;
+
+				remote_peer_addresses[index] = remote_peer_address.to_socket_data();
+				buffers[index] = iovec
+				{
+					iov_base: payload.as_ptr() as *mut c_void,
+					iov_len: payload.len(),
+				};
+
+				let message_header = &mut message_headers[index].msg_hdr;
+				message_header.msg_name = &mut remote_peer_addresses[index] as *mut SA::SD as *mut c_void;
+				message_header.msg_namelen = size_of::<SA::SD>() as socklen_t;
+				message_header.msg_iov = &mut buffers[index];
+				message_header.msg_iovlen = 1;
+
+				number_of_replies += 1;
+			}
+
+			let result = unsafe { sendmmsg(self.datagram_socket_file_descriptor.as_raw_fd(), message_headers.as_mut_ptr(), number_of_replies as c_uint, MSG_DONTWAIT | MSG_NOSIGNAL) };
+
+			if unlikely!(result == -1)
+			{
+				match io::Error::last_os_error().kind()
+				{
+					ErrorKind::WouldBlock => return,
+
+					ErrorKind::Interrupted => continue,
+
+					// An error (eg `EMSGSIZE`) relates to the first datagram in the batch; discard it and carry on.
+					_ => self.reply_queue.discard(1),
+				}
+			}
+			else
+			{
+				self.reply_queue.discard(result as usize)
+			}
+		}
+	}
+}
@@ -0,0 +1,71 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A non-blocking, close-on-exec file descriptor for a datagram socket.
+#[derive(Debug)]
+pub struct DatagramSocketFileDescriptor(RawFd);
+
+impl Drop for DatagramSocketFileDescriptor
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		unsafe { close(self.0) };
+	}
+}
+
+impl AsRawFd for DatagramSocketFileDescriptor
+{
+	#[inline(always)]
+	fn as_raw_fd(&self) -> RawFd
+	{
+		self.0
+	}
+}
+
+impl DatagramSocketFileDescriptor
+{
+	/// Creates a new socket bound to `socket_address`.
+	///
+	/// `SO_REUSEPORT` is set so that each thread can have its own reactor bound to the same address; the kernel distributes datagrams between them.
+	#[inline(always)]
+	pub fn new<SA: DatagramSocketAddress>(settings: &DatagramSocketSettings, socket_address: SA) -> io::Result<Self>
+	{
+		let raw_file_descriptor = unsafe { socket(SA::AddressFamily, SOCK_DGRAM | SOCK_NONBLOCK | SOCK_CLOEXEC, 0) };
+		if unlikely!(raw_file_descriptor == -1)
+		{
+			return Err(io::Error::last_os_error())
+		}
+		let this = DatagramSocketFileDescriptor(raw_file_descriptor);
+
+		Self::set_socket_option(raw_file_descriptor, SOL_SOCKET, SO_REUSEADDR, 1)?;
+		Self::set_socket_option(raw_file_descriptor, SOL_SOCKET, SO_REUSEPORT, 1)?;
+		Self::set_socket_option(raw_file_descriptor, SOL_SOCKET, SO_SNDBUF, settings.send_buffer_size_in_bytes as c_int)?;
+		Self::set_socket_option(raw_file_descriptor, SOL_SOCKET, SO_RCVBUF, settings.receive_buffer_size_in_bytes as c_int)?;
+		SA::configure_socket(raw_file_descriptor)?;
+
+		let socket_data = socket_address.to_socket_data();
+		let result = unsafe { bind(raw_file_descriptor, &socket_data as *const SA::SD as *const sockaddr, size_of::<SA::SD>() as socklen_t) };
+		if unlikely!(result != 0)
+		{
+			return Err(io::Error::last_os_error())
+		}
+
+		Ok(this)
+	}
+
+	#[inline(always)]
+	pub(crate) fn set_socket_option(raw_file_descriptor: RawFd, level: c_int, option_name: c_int, value: c_int) -> io::Result<()>
+	{
+		let result = unsafe { setsockopt(raw_file_descriptor, level, option_name, &value as *const c_int as *const c_void, size_of::<c_int>() as socklen_t) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(io::Error::last_os_error())
+		}
+	}
+}
@@ -0,0 +1,38 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Settings to apply to a datagram socket.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DatagramSocketSettings
+{
+	/// Send buffer size (in bytes).
+	pub send_buffer_size_in_bytes: usize,
+
+	/// Receive buffer size (in bytes).
+	pub receive_buffer_size_in_bytes: usize,
+
+	/// Maximum size of a datagram to receive (in bytes); larger datagrams are discarded.
+	///
+	/// A buffer of this size is allocated for each datagram in a `recvmmsg()` batch.
+	/// For DNS, 4096 is sufficient for EDNS(0) and 512 for classic DNS; the largest possible UDP over IPv4 payload is 65,507 bytes.
+	pub maximum_datagram_size: usize,
+
+	/// Maximum number of replies to queue whilst waiting for the socket to become writable; further replies are discarded.
+	pub maximum_queued_replies: usize,
+}
+
+impl Default for DatagramSocketSettings
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		DatagramSocketSettings
+		{
+			send_buffer_size_in_bytes: 256 * 1024,
+			receive_buffer_size_in_bytes: 256 * 1024,
+			maximum_datagram_size: 4096,
+			maximum_queued_replies: 1024,
+		}
+	}
+}
@@ -0,0 +1,72 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A queue of replies to send.
+#[derive(Debug)]
+pub struct ReplyQueue<SA: DatagramSocketAddress>
+{
+	queue: VecDeque<(SA, Vec<u8>)>,
+	maximum_queued_replies: usize,
+}
+
+impl<SA: DatagramSocketAddress> ReplyQueue<SA>
+{
+	#[inline(always)]
+	pub(crate) fn new(maximum_queued_replies: usize) -> Self
+	{
+		Self
+		{
+			queue: VecDeque::with_capacity(maximum_queued_replies),
+			maximum_queued_replies,
+		}
+	}
+
+	/// Queue a reply to `remote_peer_address`.
+	///
+	/// Returns `false` if the queue is full and the reply was discarded.
+	#[inline(always)]
+	pub fn queue_reply(&mut self, remote_peer_address: SA, payload: Vec<u8>) -> bool
+	{
+		if unlikely!(self.is_full())
+		{
+			return false
+		}
+
+		self.queue.push_back((remote_peer_address, payload));
+		true
+	}
+
+	/// Is the queue full?
+	#[inline(always)]
+	pub fn is_full(&self) -> bool
+	{
+		self.queue.len() >= self.maximum_queued_replies
+	}
+
+	/// Number of replies queued.
+	#[inline(always)]
+	pub fn len(&self) -> usize
+	{
+		self.queue.len()
+	}
+
+	/// Is the queue empty?
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool
+	{
+		self.queue.is_empty()
+	}
+
+	#[inline(always)]
+	pub(crate) fn front(&self, count: usize) -> impl Iterator<Item=&(SA, Vec<u8>)>
+	{
+		self.queue.iter().take(count)
+	}
+
+	#[inline(always)]
+	pub(crate) fn discard(&mut self, count: usize)
+	{
+		self.queue.drain(.. min(count, self.queue.len()));
+	}
+}
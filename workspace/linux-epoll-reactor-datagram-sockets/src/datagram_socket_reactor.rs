@@ -0,0 +1,46 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+macro_rules! datagram_socket_reactor
+{
+	($reactor_name: ident, $rust_socket_type: ty, $title_case: ident) =>
+	{
+		/// This object wraps datagram sockets of variety `$title_case`.
+		///
+		/// Construction is done via `do_initial_input_and_output_and_register_with_epoll_if_necesssary()`; each thread can register a reactor for the same socket address.
+		#[derive(Debug)]
+		pub struct $reactor_name<DH: DatagramHandler<$rust_socket_type>>
+		{
+			common: DatagramSocketCommon<$rust_socket_type, DH>,
+		}
+
+		impl<DH: DatagramHandler<$rust_socket_type>> Reactor for $reactor_name<DH>
+		{
+			type FileDescriptor = DatagramSocketFileDescriptor;
+
+			type RegistrationData = (Arc<DatagramSocketSettings>, $rust_socket_type, DH);
+
+			#[inline(always)]
+			fn do_initial_input_and_output_and_register_with_epoll_if_necesssary<A: Arena<Self>, EPR: EventPollRegister>(event_poll_register: &EPR, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, registration_data: Self::RegistrationData) -> Result<(), EventPollRegistrationError>
+			{
+				DatagramSocketCommon::<$rust_socket_type, DH>::do_initial_input_and_output_and_register_with_epoll_if_necesssary::<A, Self, EPR>(event_poll_register, arena, reactor_compressed_type_identifier, registration_data)
+			}
+
+			#[inline(always)]
+			fn react(&mut self, event_flags: EPollEventFlags, terminate: &impl Terminate) -> Result<bool, String>
+			{
+				self.common.react(event_flags, terminate)
+			}
+		}
+
+		impl<DH: DatagramHandler<$rust_socket_type>> DatagramSocketReactor<$rust_socket_type, DH> for $reactor_name<DH>
+		{
+			#[inline(always)]
+			fn initialize(&mut self, common: DatagramSocketCommon<$rust_socket_type, DH>)
+			{
+				unsafe { write(&mut self.common, common) }
+			}
+		}
+	}
+}
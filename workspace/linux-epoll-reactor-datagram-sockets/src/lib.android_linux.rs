@@ -0,0 +1,76 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+#[macro_use] extern crate likely;
+extern crate linux_epoll;
+
+
+use ::linux_epoll::*;
+use ::linux_epoll::arena::*;
+use ::linux_epoll::file_descriptors::epoll::*;
+use ::linux_epoll::libc::AF_INET;
+use ::linux_epoll::libc::AF_INET6;
+use ::linux_epoll::libc::bind;
+use ::linux_epoll::libc::c_int;
+use ::linux_epoll::libc::c_uint;
+use ::linux_epoll::libc::c_void;
+use ::linux_epoll::libc::close;
+use ::linux_epoll::libc::in_addr;
+use ::linux_epoll::libc::in6_addr;
+use ::linux_epoll::libc::iovec;
+use ::linux_epoll::libc::IPPROTO_IPV6;
+use ::linux_epoll::libc::IPV6_V6ONLY;
+use ::linux_epoll::libc::mmsghdr;
+use ::linux_epoll::libc::MSG_DONTWAIT;
+use ::linux_epoll::libc::MSG_NOSIGNAL;
+use ::linux_epoll::libc::MSG_TRUNC;
+use ::linux_epoll::libc::recvmmsg;
+use ::linux_epoll::libc::sa_family_t;
+use ::linux_epoll::libc::sendmmsg;
+use ::linux_epoll::libc::setsockopt;
+use ::linux_epoll::libc::SO_RCVBUF;
+use ::linux_epoll::libc::SO_REUSEADDR;
+use ::linux_epoll::libc::SO_REUSEPORT;
+use ::linux_epoll::libc::SO_SNDBUF;
+use ::linux_epoll::libc::SOCK_CLOEXEC;
+use ::linux_epoll::libc::SOCK_DGRAM;
+use ::linux_epoll::libc::SOCK_NONBLOCK;
+use ::linux_epoll::libc::sockaddr;
+use ::linux_epoll::libc::sockaddr_in;
+use ::linux_epoll::libc::sockaddr_in6;
+use ::linux_epoll::libc::socket;
+use ::linux_epoll::libc::socklen_t;
+use ::linux_epoll::libc::SOL_SOCKET;
+use ::linux_epoll::reactor::*;
+use ::std::cmp::min;
+use ::std::collections::VecDeque;
+use ::std::error;
+use ::std::fmt::Debug;
+use ::std::io;
+use ::std::io::ErrorKind;
+use ::std::mem::size_of;
+use ::std::mem::zeroed;
+use ::std::net::Ipv4Addr;
+use ::std::net::Ipv6Addr;
+use ::std::net::SocketAddrV4;
+use ::std::net::SocketAddrV6;
+use ::std::os::unix::io::AsRawFd;
+use ::std::os::unix::io::RawFd;
+use ::std::ptr::null_mut;
+use ::std::ptr::write;
+use ::std::sync::Arc;
+
+
+include!("datagram_socket_reactor.rs");
+
+
+include!("DatagramHandler.rs");
+include!("DatagramSocketAddress.rs");
+include!("DatagramSocketCommon.rs");
+include!("DatagramSocketFileDescriptor.rs");
+include!("DatagramSocketInternetProtocolVersion4Reactor.rs");
+include!("DatagramSocketInternetProtocolVersion6Reactor.rs");
+include!("DatagramSocketReactor.rs");
+include!("DatagramSocketSettings.rs");
+include!("ReplyQueue.rs");
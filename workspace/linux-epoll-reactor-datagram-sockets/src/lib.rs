@@ -0,0 +1,18 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![deny(missing_docs)]
+#![deny(unreachable_patterns)]
+#![cfg_attr(any(target_os = "android", target_os = "linux"), feature(core_intrinsics))]
+
+
+//! #linux-epoll-reactor-datagram-sockets
+//!
+//! A reactor for UDP datagram sockets using `recvmmsg()` and `sendmmsg()`.
+
+
+#[cfg(any(target_os = "android", target_os = "linux"))] include!("lib.android_linux.rs");
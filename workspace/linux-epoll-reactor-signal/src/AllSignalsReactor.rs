@@ -23,7 +23,7 @@ impl<SH: SignalHandler> Reactor for AllSignalsReactor<SH>
 		let signal_handler = registration_data;
 		let (signal_file_descriptor, _signal_mask) = SignalFileDescriptor::new_with_filled_signal_mask()?;
 
-		event_poll_register.register::<A, Self, _>(arena, reactor_compressed_type_identifier, signal_file_descriptor, EPollAddFlags::EdgeTriggeredInput, |uninitialized_this, signal_file_descriptor|
+		event_poll_register.register::<A, Self, _>(arena, reactor_compressed_type_identifier, signal_file_descriptor, EPollAddFlags::EdgeTriggeredInput, None, |uninitialized_this, signal_file_descriptor|
 		{
 			unsafe
 			{
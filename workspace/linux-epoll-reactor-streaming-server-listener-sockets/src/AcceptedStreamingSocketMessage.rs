@@ -2,17 +2,20 @@
 // Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
 
 
+/// A message published to the thread which should handle an accepted connection.
 #[derive(Debug)]
-struct AcceptedStreamingSocketMessage<SD: SocketData>
+pub struct AcceptedStreamingSocketMessage<SD: RemotePeerConnectionKey>
 {
 	streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>,
+	connection_permit: ConnectionPermit<SD>,
 	streaming_socket_service_identifier: u8,
 }
 
-impl<SD: SocketData> AcceptedStreamingSocketMessage<SD>
+impl<SD: RemotePeerConnectionKey> AcceptedStreamingSocketMessage<SD>
 {
+	/// Initializes the message in place.
 	#[inline(always)]
-	pub fn initialize(mut receiver: NonNull<Self>, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, streaming_socket_service_identifier: u8)
+	pub fn initialize(mut receiver: NonNull<Self>, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, connection_permit: ConnectionPermit<SD>, streaming_socket_service_identifier: u8)
 	{
 		unsafe
 		{
@@ -22,9 +25,19 @@ impl<SD: SocketData> AcceptedStreamingSocketMessage<SD>
 				Self
 				{
 					streaming_socket_file_descriptor,
+					connection_permit,
 					streaming_socket_service_identifier,
 				}
 			)
 		}
 	}
+
+	/// Returns the accepted connection, its permit and the streaming socket service identifier.
+	///
+	/// The permit should be passed to the streaming socket reactor (as the last element of its registration data) so that it is held for as long as the connection is open.
+	#[inline(always)]
+	pub fn into_parts(self) -> (StreamingSocketFileDescriptor<SD>, ConnectionPermit<SD>, u8)
+	{
+		(self.streaming_socket_file_descriptor, self.connection_permit, self.streaming_socket_service_identifier)
+	}
 }
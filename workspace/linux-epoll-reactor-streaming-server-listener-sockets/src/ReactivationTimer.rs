@@ -0,0 +1,115 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A timer, registered with the same event poll token as a listener, which makes the listener react again once it can resume accepting or a tarpitted connection expires.
+///
+/// Listeners are edge-triggered, so connections left queued in the back log whilst not accepting would otherwise only be accepted when another connection arrives.
+#[derive(Debug)]
+pub(crate) struct ReactivationTimer
+{
+	raw_file_descriptor: RawFd,
+}
+
+impl Drop for ReactivationTimer
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		unsafe { close(self.raw_file_descriptor) };
+	}
+}
+
+impl AsRawFd for ReactivationTimer
+{
+	#[inline(always)]
+	fn as_raw_fd(&self) -> RawFd
+	{
+		self.raw_file_descriptor
+	}
+}
+
+impl ReactivationTimer
+{
+	#[inline(always)]
+	pub(crate) fn new() -> io::Result<Self>
+	{
+		let raw_file_descriptor = unsafe { timerfd_create(CLOCK_MONOTONIC, TFD_NONBLOCK | TFD_CLOEXEC) };
+		if unlikely!(raw_file_descriptor == -1)
+		{
+			return Err(io::Error::last_os_error())
+		}
+
+		Ok
+		(
+			Self
+			{
+				raw_file_descriptor,
+			}
+		)
+	}
+
+	/// Consumes any expirations so that the timer can become ready again.
+	#[inline(always)]
+	pub(crate) fn acknowledge(&self)
+	{
+		let mut number_of_expirations: u64 = 0;
+		unsafe { read(self.raw_file_descriptor, &mut number_of_expirations as *mut u64 as *mut c_void, size_of::<u64>()) };
+	}
+
+	/// `None` disarms the timer.
+	#[inline(always)]
+	pub(crate) fn reactivate_at(&self, reactivate_at: Option<Instant>) -> io::Result<()>
+	{
+		let it_value = match reactivate_at
+		{
+			None => timespec
+			{
+				tv_sec: 0,
+				tv_nsec: 0,
+			},
+
+			Some(reactivate_at) =>
+			{
+				let now = Instant::now();
+
+				// An all-zero `it_value` would disarm the timer rather than expire it immediately.
+				let (seconds, nanoseconds) = if unlikely!(reactivate_at <= now)
+				{
+					(0, 1)
+				}
+				else
+				{
+					let duration = reactivate_at - now;
+					(duration.as_secs(), duration.subsec_nanos())
+				};
+
+				timespec
+				{
+					tv_sec: seconds as time_t,
+					tv_nsec: nanoseconds as c_long,
+				}
+			}
+		};
+
+		let new_value = itimerspec
+		{
+			it_interval: timespec
+			{
+				tv_sec: 0,
+				tv_nsec: 0,
+			},
+			it_value,
+		};
+
+		let result = unsafe { timerfd_settime(self.raw_file_descriptor, 0, &new_value, null_mut()) };
+		if unlikely!(result != 0)
+		{
+			Err(io::Error::last_os_error())
+		}
+		else
+		{
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,9 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Represents an Unix domain socket address for a `SOCK_SEQPACKET` listener.
+///
+/// Accepted connections preserve message boundaries; use them with `SequentialPacketUnixDomainReactor` from the streaming sockets crate rather than a stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequentialPacketUnixDomainSocketAddress(pub UnixDomainSocketAddress);
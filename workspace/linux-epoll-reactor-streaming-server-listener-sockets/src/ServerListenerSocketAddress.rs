@@ -0,0 +1,109 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A socket address that a server listener socket can be bound to.
+pub(crate) trait ServerListenerSocketAddress<SD: SocketData>
+{
+	/// Creates a new server listener socket.
+	///
+	/// *MUST* occur after the current thread has had its affinity forced to the current CPU.
+	fn new_server_listener_socket_file_descriptor(self, settings: &StreamingServerListenerSocketSettings) -> Result<(StreamingServerListenerSocketFileDescriptor<SD>, Option<SocketFileUnlinker>), EventPollRegistrationError>;
+}
+
+impl ServerListenerSocketAddress<sockaddr_in> for SocketAddrV4
+{
+	#[inline(always)]
+	fn new_server_listener_socket_file_descriptor(self, settings: &StreamingServerListenerSocketSettings) -> Result<(StreamingServerListenerSocketFileDescriptor<sockaddr_in>, Option<SocketFileUnlinker>), EventPollRegistrationError>
+	{
+		let streaming_server_listener_socket_file_descriptor = SocketFileDescriptor::<sockaddr_in>::new_transmission_control_protocol_over_internet_protocol_version_4_server_listener
+		(
+			self,
+			settings.send_buffer_size_in_bytes,
+			settings.receive_buffer_size_in_bytes,
+			settings.idles_before_keep_alive_seconds,
+			settings.keep_alive_interval_seconds,
+			settings.maximum_keep_alive_probes,
+			settings.linger_seconds,
+			settings.linger_in_FIN_WAIT2_seconds,
+			settings.maximum_SYN_transmits,
+			settings.back_log,
+			LogicalCores::current_logical_core(),
+		)?;
+
+		settings.apply_transmission_control_protocol_options(streaming_server_listener_socket_file_descriptor.as_raw_fd(), false).map_err(input_or_output_failed)?;
+
+		Ok((streaming_server_listener_socket_file_descriptor, None))
+	}
+}
+
+impl ServerListenerSocketAddress<sockaddr_in6> for SocketAddrV6
+{
+	#[inline(always)]
+	fn new_server_listener_socket_file_descriptor(self, settings: &StreamingServerListenerSocketSettings) -> Result<(StreamingServerListenerSocketFileDescriptor<sockaddr_in6>, Option<SocketFileUnlinker>), EventPollRegistrationError>
+	{
+		let streaming_server_listener_socket_file_descriptor = SocketFileDescriptor::<sockaddr_in6>::new_transmission_control_protocol_over_internet_protocol_version_6_server_listener
+		(
+			self,
+			settings.send_buffer_size_in_bytes,
+			settings.receive_buffer_size_in_bytes,
+			settings.idles_before_keep_alive_seconds,
+			settings.keep_alive_interval_seconds,
+			settings.maximum_keep_alive_probes,
+			settings.linger_seconds,
+			settings.linger_in_FIN_WAIT2_seconds,
+			settings.maximum_SYN_transmits,
+			settings.back_log,
+			LogicalCores::current_logical_core(),
+		)?;
+
+		settings.apply_transmission_control_protocol_options(streaming_server_listener_socket_file_descriptor.as_raw_fd(), true).map_err(input_or_output_failed)?;
+
+		Ok((streaming_server_listener_socket_file_descriptor, None))
+	}
+}
+
+impl ServerListenerSocketAddress<sockaddr_un> for UnixDomainSocketAddress
+{
+	#[inline(always)]
+	fn new_server_listener_socket_file_descriptor(self, settings: &StreamingServerListenerSocketSettings) -> Result<(StreamingServerListenerSocketFileDescriptor<sockaddr_un>, Option<SocketFileUnlinker>), EventPollRegistrationError>
+	{
+		match self
+		{
+			UnixDomainSocketAddress::Path { socket_file_path, parent_folder_mode, socket_file_settings } =>
+			{
+				socket_file_settings.unlink_stale_socket_file(&socket_file_path).map_err(input_or_output_failed)?;
+
+				let streaming_server_listener_socket_file_descriptor = SocketFileDescriptor::<sockaddr_un>::new_streaming_unix_domain_socket_server_listener
+				(
+					&UnixSocketAddress::File { socket_file_path: socket_file_path.clone(), parent_folder_mode },
+					settings.send_buffer_size_in_bytes,
+					settings.back_log,
+					LogicalCores::current_logical_core(),
+				)?;
+
+				let socket_file_unlinker = socket_file_settings.apply(&socket_file_path).map_err(input_or_output_failed)?;
+
+				Ok((streaming_server_listener_socket_file_descriptor, socket_file_unlinker))
+			}
+
+			// `new_streaming_unix_domain_socket_server_listener()` only supports file system paths.
+			_ => self.new_unix_domain_socket_server_listener(settings, SOCK_STREAM),
+		}
+	}
+}
+
+impl ServerListenerSocketAddress<sockaddr_un> for SequentialPacketUnixDomainSocketAddress
+{
+	#[inline(always)]
+	fn new_server_listener_socket_file_descriptor(self, settings: &StreamingServerListenerSocketSettings) -> Result<(StreamingServerListenerSocketFileDescriptor<sockaddr_un>, Option<SocketFileUnlinker>), EventPollRegistrationError>
+	{
+		self.0.new_unix_domain_socket_server_listener(settings, SOCK_SEQPACKET)
+	}
+}
+
+#[inline(always)]
+fn input_or_output_failed(error: io::Error) -> EventPollRegistrationError
+{
+	EventPollRegistrationError::InitialInputOrOutputFailed(Box::new(error))
+}
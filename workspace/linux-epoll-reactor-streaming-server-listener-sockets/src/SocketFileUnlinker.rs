@@ -0,0 +1,46 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Unlinks an Unix domain socket file when dropped, if it has not since been replaced.
+#[derive(Debug)]
+pub(crate) struct SocketFileUnlinker
+{
+	socket_file_path: PathBuf,
+	device: u64,
+	inode: u64,
+}
+
+impl Drop for SocketFileUnlinker
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		if let Ok(metadata) = symlink_metadata(&self.socket_file_path)
+		{
+			if metadata.dev() == self.device && metadata.ino() == self.inode
+			{
+				let _ = remove_file(&self.socket_file_path);
+			}
+		}
+	}
+}
+
+impl SocketFileUnlinker
+{
+	#[inline(always)]
+	fn new(socket_file_path: &Path) -> io::Result<Self>
+	{
+		let metadata = symlink_metadata(socket_file_path)?;
+
+		Ok
+		(
+			Self
+			{
+				socket_file_path: socket_file_path.to_path_buf(),
+				device: metadata.dev(),
+				inode: metadata.ino(),
+			}
+		)
+	}
+}
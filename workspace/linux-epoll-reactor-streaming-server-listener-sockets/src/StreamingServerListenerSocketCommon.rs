@@ -3,28 +3,39 @@
 
 
 #[derive(Debug)]
-struct StreamingServerListenerSocketCommon<SD: SocketData, AC: AccessControl<SD>>
+struct StreamingServerListenerSocketCommon<SD: RemotePeerConnectionKey, AC: AccessControl<SD>>
 {
 	streaming_server_listener_socket_file_descriptor: StreamingServerListenerSocketFileDescriptor<SD>,
+	socket_file_unlinker: Option<SocketFileUnlinker>,
 	access_control: AC,
+	tarpit: Tarpit<SD>,
+	accept_limiter: AcceptLimiter<SD>,
+	reactivation_timer: ReactivationTimer,
 	publisher: QueuePerThreadQueuesPublisher<(), String>,
 	accepted_streaming_socket_message_compressed_type_identifier: CompressedTypeIdentifier,
 	streaming_socket_service_identifier: u8,
 }
 
-impl<SD: SocketData, AC: AccessControl<SD>> StreamingServerListenerSocketCommon<SD, AC>
+impl<SD: RemotePeerConnectionKey, AC: AccessControl<SD>> StreamingServerListenerSocketCommon<SD, AC>
 {
 	#[inline(always)]
-	fn do_initial_input_and_output_and_register_with_epoll_if_necesssary<A: Arena<SSLSR>, SSLSR: StreamingServerListenerSocketReactor<SD, AC>, EPR: EventPollRegister>(event_poll_register: &EPR, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, streaming_server_listener_socket_file_descriptor: SSLSR::FileDescriptor, access_control: AC, publisher: QueuePerThreadQueuesPublisher<(), String>, accepted_streaming_socket_message_compressed_type_identifier: CompressedTypeIdentifier, streaming_socket_service_identifier: u8) -> Result<(), EventPollRegistrationError>
+	fn do_initial_input_and_output_and_register_with_epoll_if_necesssary<A: Arena<SSLSR>, SSLSR: StreamingServerListenerSocketReactor<SD, AC>, EPR: EventPollRegister>(event_poll_register: &EPR, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, streaming_server_listener_socket_file_descriptor: SSLSR::FileDescriptor, socket_file_unlinker: Option<SocketFileUnlinker>, access_control: AC, accept_limits: Arc<AcceptLimits<SD>>, publisher: QueuePerThreadQueuesPublisher<(), String>, accepted_streaming_socket_message_compressed_type_identifier: CompressedTypeIdentifier, streaming_socket_service_identifier: u8) -> Result<(), EventPollRegistrationError>
 	{
-		event_poll_register.register::<A, SSLSR, _>(arena, reactor_compressed_type_identifier, streaming_server_listener_socket_file_descriptor, EPollAddFlags::EdgeTriggeredInputExclusive, |uninitialized_reactor, streaming_server_listener_socket_file_descriptor|
+		let reactivation_timer = ReactivationTimer::new().map_err(|error| EventPollRegistrationError::InitialInputOrOutputFailed(Box::new(error)))?;
+		let reactivation_timer_raw_file_descriptor = reactivation_timer.as_raw_fd();
+
+		event_poll_register.register::<A, SSLSR, _>(arena, reactor_compressed_type_identifier, streaming_server_listener_socket_file_descriptor, EPollAddFlags::EdgeTriggeredInputExclusive, Some((reactivation_timer_raw_file_descriptor, EPollAddFlags::EdgeTriggeredInput)), |uninitialized_reactor, streaming_server_listener_socket_file_descriptor|
 		{
 			uninitialized_reactor.initialize
 			(
 				Self
 				{
 					streaming_server_listener_socket_file_descriptor,
+					socket_file_unlinker,
 					access_control,
+					tarpit: Tarpit::new(accept_limits.settings().maximum_tarpitted_connections),
+					accept_limiter: AcceptLimiter::new(accept_limits, Instant::now()),
+					reactivation_timer,
 					publisher,
 					accepted_streaming_socket_message_compressed_type_identifier,
 					streaming_socket_service_identifier,
@@ -39,23 +50,89 @@ impl<SD: SocketData, AC: AccessControl<SD>> StreamingServerListenerSocketCommon<
 	{
 		debug_assert_eq!(event_flags, EPollEventFlags::Input, "flags contained a flag other than `Input`");
 
+		self.reactivation_timer.acknowledge();
+
+		self.tarpit.release_expired();
+
+		let resume_accepting_at = self.accept_until_would_block_or_limited(terminate);
+
+		let reactivate_at = match (resume_accepting_at, self.tarpit.next_expires_at())
+		{
+			(None, None) => None,
+
+			(Some(resume_accepting_at), None) => Some(resume_accepting_at),
+
+			(None, Some(next_expires_at)) => Some(next_expires_at),
+
+			(Some(resume_accepting_at), Some(next_expires_at)) => Some(min(resume_accepting_at, next_expires_at)),
+		};
+
+		if let Err(error) = self.reactivation_timer.reactivate_at(reactivate_at)
+		{
+			return Err(format!("Could not arm reactivation timer: {}", error))
+		}
+
+		Ok(false)
+	}
+
+	/// Returns when accepting can resume if it was stopped by a limit; connections not yet accepted are left queued in the back log.
+	#[inline(always)]
+	fn accept_until_would_block_or_limited(&mut self, terminate: &impl Terminate) -> Option<Instant>
+	{
 		while terminate.should_continue()
 		{
 			use self::SocketAcceptError::*;
 
+			let now = Instant::now();
+
+			let resume_accepting_at = self.accept_limiter.resume_accepting_at(now);
+			if unlikely!(resume_accepting_at.is_some())
+			{
+				return resume_accepting_at
+			}
+
 			match self.streaming_server_listener_socket_file_descriptor.accept()
 			{
-				Ok(AcceptedConnection { streaming_socket_file_descriptor, peer_address }) => if likely!(self.is_remote_peer_allowed(peer_address, &streaming_socket_file_descriptor))
+				Ok(AcceptedConnection { streaming_socket_file_descriptor, peer_address }) =>
 				{
+					let accept_token_taken = self.accept_limiter.try_take_accept_token(now);
+					debug_assert!(accept_token_taken, "accept token should have been available");
+
+					let remote_peer_connection_key = peer_address.remote_peer_connection_key(&streaming_socket_file_descriptor);
+
+					{
+						use self::AccessControlDecision::*;
+
+						match self.access_control.remote_peer_access_control_decision(self.streaming_socket_service_identifier, &peer_address, &streaming_socket_file_descriptor)
+						{
+							Allow => (),
+
+							Deny | DenyWithReason(_) | Abstain => continue,
+
+							Tarpit(duration) =>
+							{
+								self.tarpit.hold(streaming_socket_file_descriptor, duration);
+								continue
+							}
+						}
+					}
+
+					let connection_permit = match self.accept_limiter.try_acquire_connection_permit(remote_peer_connection_key)
+					{
+						None => continue,
+
+						Some(connection_permit) => connection_permit,
+					};
+
 					let logical_core_identifier = streaming_socket_file_descriptor.logical_core_identifier();
-					self.publisher.publish_message::<AcceptedStreamingSocketMessage<SD>, _>(logical_core_identifier, self.accepted_streaming_socket_message_compressed_type_identifier, |receiver| AcceptedStreamingSocketMessage::<SD>::initialize(receiver, streaming_socket_file_descriptor, self.streaming_socket_service_identifier));
+					self.publisher.publish_message::<AcceptedStreamingSocketMessage<SD>, _>(logical_core_identifier, self.accepted_streaming_socket_message_compressed_type_identifier, |receiver| AcceptedStreamingSocketMessage::<SD>::initialize(receiver, streaming_socket_file_descriptor, connection_permit, self.streaming_socket_service_identifier));
 				},
 
 				Err(error) => match error
 				{
-					Again => break,
+					Again => return None,
 
-					PerProcessLimitOnNumberOfFileDescriptorsWouldBeExceeded | SystemWideLimitOnTotalNumberOfFileDescriptorsWouldBeExceeded | KernelWouldBeOutOfMemory => continue,
+					PerProcessLimitOnNumberOfFileDescriptorsWouldBeExceeded | SystemWideLimitOnTotalNumberOfFileDescriptorsWouldBeExceeded | KernelWouldBeOutOfMemory => self.accept_limiter.begin_back_off(now),
 
 					ConnectionFailed(_connection_failed_reason @ _) => continue,
 
@@ -64,71 +141,6 @@ impl<SD: SocketData, AC: AccessControl<SD>> StreamingServerListenerSocketCommon<
 			}
 		}
 
-		Ok(false)
-	}
-
-	#[inline(always)]
-	fn is_remote_peer_allowed(&self, remote_peer_address: SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>) -> bool
-	{
-		self.access_control.is_remote_peer_allowed(remote_peer_address, streaming_socket_file_descriptor)
-	}
-}
-
-impl<AC: AccessControl<sockaddr_in>> StreamingServerListenerSocketCommon<sockaddr_in, AC>
-{
-	#[inline(always)]
-	fn new_streaming_socket_file_descriptor(settings: &StreamingServerListenerSocketSettings, socket_address: SocketAddrV4) -> Result<StreamingServerListenerSocketFileDescriptor<sockaddr_in>, NewSocketServerListenerError>
-	{
-		SocketFileDescriptor::<sockaddr_in>::new_transmission_control_protocol_over_internet_protocol_version_4_server_listener
-		(
-			socket_address,
-			settings.send_buffer_size_in_bytes,
-			settings.receive_buffer_size_in_bytes,
-			settings.idles_before_keep_alive_seconds,
-			settings.keep_alive_interval_seconds,
-			settings.maximum_keep_alive_probes,
-			settings.linger_seconds,
-			settings.linger_in_FIN_WAIT2_seconds,
-			settings.maximum_SYN_transmits,
-			settings.back_log,
-			LogicalCores::current_logical_core(),
-		)
-	}
-}
-
-impl<AC: AccessControl<sockaddr_in6>> StreamingServerListenerSocketCommon<sockaddr_in6, AC>
-{
-	#[inline(always)]
-	fn new_streaming_socket_file_descriptor(settings: &StreamingServerListenerSocketSettings, socket_address: SocketAddrV6) -> Result<StreamingServerListenerSocketFileDescriptor<sockaddr_in6>, NewSocketServerListenerError>
-	{
-		SocketFileDescriptor::<sockaddr_in6>::new_transmission_control_protocol_over_internet_protocol_version_6_server_listener
-		(
-			socket_address,
-			settings.send_buffer_size_in_bytes,
-			settings.receive_buffer_size_in_bytes,
-			settings.idles_before_keep_alive_seconds,
-			settings.keep_alive_interval_seconds,
-			settings.maximum_keep_alive_probes,
-			settings.linger_seconds,
-			settings.linger_in_FIN_WAIT2_seconds,
-			settings.maximum_SYN_transmits,
-			settings.back_log,
-			LogicalCores::current_logical_core(),
-		)
-	}
-}
-
-impl<AC: AccessControl<sockaddr_un>> StreamingServerListenerSocketCommon<sockaddr_un, AC>
-{
-	#[inline(always)]
-	fn new_streaming_socket_file_descriptor(settings: &StreamingServerListenerSocketSettings, socket_address: UnixDomainSocketAddress) -> Result<StreamingServerListenerSocketFileDescriptor<sockaddr_un>, NewSocketServerListenerError>
-	{
-		SocketFileDescriptor::<sockaddr_un>::new_streaming_unix_domain_socket_server_listener
-		(
-			&socket_address.0,
-			settings.send_buffer_size_in_bytes,
-			settings.back_log,
-			LogicalCores::current_logical_core(),
-		)
+		None
 	}
 }
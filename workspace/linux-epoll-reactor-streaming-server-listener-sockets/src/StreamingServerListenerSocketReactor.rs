@@ -2,7 +2,7 @@
 // Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
 
 
-trait StreamingServerListenerSocketReactor<SD: SocketData, AC: AccessControl<SD>>: Reactor<FileDescriptor=StreamingServerListenerSocketFileDescriptor<SD>>
+trait StreamingServerListenerSocketReactor<SD: RemotePeerConnectionKey, AC: AccessControl<SD>>: Reactor<FileDescriptor=StreamingServerListenerSocketFileDescriptor<SD>>
 {
 	fn initialize(&mut self, common: StreamingServerListenerSocketCommon<SD, AC>);
 }
@@ -4,6 +4,8 @@
 
 /// Settings to apply to a connection established by `accept()`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Deserialize)]
+#[serde(default)]
 pub struct StreamingServerListenerSocketSettings
 {
 	/// Send buffer size (in bytes).
@@ -34,6 +36,20 @@ pub struct StreamingServerListenerSocketSettings
 
 	/// Back log of prior connections to honour (typically capped by Linux to 128).
 	pub back_log: u32,
+
+	/// TCP Fast Open queue length (`TCP_FASTOPEN`); the maximum number of pending connections which have not yet completed the three-way handshake but whose SYN carried data.
+	///
+	/// Requires bit 2 (`0x2`) of `/proc/sys/net/ipv4/tcp_fastopen` to be set.
+	/// Only use for protocols which tolerate replayed initial data (eg TLS, but not plain HTTP POST).
+	pub fast_open_queue_length: Option<u32>,
+
+	/// Only wake the listener when data has arrived, waiting at most this number of seconds after the handshake completes (`TCP_DEFER_ACCEPT`).
+	///
+	/// Useful for protocols where the client speaks first, such as HTTP and TLS.
+	pub defer_accept_seconds: Option<u32>,
+
+	/// Other TCP options, inherited by accepted connections.
+	pub transmission_control_protocol_options: TransmissionControlProtocolOptions,
 }
 
 impl Default for StreamingServerListenerSocketSettings
@@ -52,6 +68,28 @@ impl Default for StreamingServerListenerSocketSettings
 			linger_in_FIN_WAIT2_seconds: 0,
 			maximum_SYN_transmits: 1,
 			back_log: 128,
+			fast_open_queue_length: None,
+			defer_accept_seconds: None,
+			transmission_control_protocol_options: TransmissionControlProtocolOptions::default(),
 		}
 	}
 }
+
+impl StreamingServerListenerSocketSettings
+{
+	#[inline(always)]
+	pub(crate) fn apply_transmission_control_protocol_options(&self, raw_file_descriptor: RawFd, is_internet_protocol_version_6: bool) -> io::Result<()>
+	{
+		if let Some(fast_open_queue_length) = self.fast_open_queue_length
+		{
+			TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_FASTOPEN, fast_open_queue_length as c_int)?;
+		}
+
+		if let Some(defer_accept_seconds) = self.defer_accept_seconds
+		{
+			TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_DEFER_ACCEPT, defer_accept_seconds as c_int)?;
+		}
+
+		self.transmission_control_protocol_options.apply(raw_file_descriptor, is_internet_protocol_version_6)
+	}
+}
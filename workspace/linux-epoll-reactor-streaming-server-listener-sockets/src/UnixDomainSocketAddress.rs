@@ -3,5 +3,143 @@
 
 
 /// Represents an Unix domain socket address.
-#[derive(Debug)]
-pub struct UnixDomainSocketAddress(pub UnixSocketAddress<PathBuf>);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnixDomainSocketAddress
+{
+	/// A socket file in a file system.
+	Path
+	{
+		/// Path of the socket file; at most 107 bytes.
+		socket_file_path: PathBuf,
+
+		/// Mode used when creating any missing parent folders.
+		parent_folder_mode: mode_t,
+
+		/// Settings to apply to the socket file.
+		socket_file_settings: UnixDomainSocketFileSettings,
+	},
+
+	/// A Linux abstract namespace address.
+	///
+	/// The name should not include the leading ASCII NUL; it may contain ASCII NULs and is at most 107 bytes.
+	///
+	/// Abstract addresses are not visible in the file system, so are never left behind after a crash, but are also not subject to file system permissions; use access control instead.
+	Abstract
+	{
+		/// Abstract name.
+		abstract_name: Vec<u8>,
+	},
+
+	/// A Linux abstract namespace address chosen by the kernel (autobind).
+	///
+	/// The kernel chooses a unique name of five hexadecimal digits.
+	Autobind,
+}
+
+impl UnixDomainSocketAddress
+{
+	/// Maximum length of a socket file path or abstract name.
+	pub const MaximumLength: usize = UnixDomainSocketRawAddress::MaximumLength;
+
+	/// New instance for a socket file path with default settings.
+	#[inline(always)]
+	pub fn path(socket_file_path: impl Into<PathBuf>) -> Self
+	{
+		UnixDomainSocketAddress::Path
+		{
+			socket_file_path: socket_file_path.into(),
+			parent_folder_mode: 0o0700,
+			socket_file_settings: UnixDomainSocketFileSettings::default(),
+		}
+	}
+
+	/// New instance for an abstract name.
+	#[inline(always)]
+	pub fn abstract_name(abstract_name: impl Into<Vec<u8>>) -> Self
+	{
+		UnixDomainSocketAddress::Abstract
+		{
+			abstract_name: abstract_name.into(),
+		}
+	}
+}
+
+impl UnixDomainSocketAddress
+{
+	/// Binds and listens using `libc` directly, as `new_streaming_unix_domain_socket_server_listener()` only supports `SOCK_STREAM` sockets bound to file system paths.
+	#[inline(always)]
+	pub(crate) fn new_unix_domain_socket_server_listener(self, settings: &StreamingServerListenerSocketSettings, socket_type: c_int) -> Result<(StreamingServerListenerSocketFileDescriptor<sockaddr_un>, Option<SocketFileUnlinker>), EventPollRegistrationError>
+	{
+		let (socket_address, socket_address_length) = self.to_sockaddr_un().map_err(input_or_output_failed)?;
+
+		if let UnixDomainSocketAddress::Path { ref socket_file_path, parent_folder_mode, ref socket_file_settings } = self
+		{
+			if let Some(parent_folder_path) = socket_file_path.parent()
+			{
+				DirBuilder::new().recursive(true).mode(parent_folder_mode).create(parent_folder_path).map_err(input_or_output_failed)?;
+			}
+			socket_file_settings.unlink_stale_socket_file(socket_file_path).map_err(input_or_output_failed)?;
+		}
+
+		let streaming_server_listener_socket_file_descriptor = Self::bind_and_listen(settings, socket_type, &socket_address, socket_address_length).map_err(input_or_output_failed)?;
+
+		let socket_file_unlinker = match self
+		{
+			UnixDomainSocketAddress::Path { ref socket_file_path, ref socket_file_settings, .. } => socket_file_settings.apply(socket_file_path).map_err(input_or_output_failed)?,
+
+			_ => None,
+		};
+
+		Ok((streaming_server_listener_socket_file_descriptor, socket_file_unlinker))
+	}
+
+	/// An `Autobind` address is just the address family, which causes the kernel to autobind.
+	#[inline(always)]
+	fn to_sockaddr_un(&self) -> io::Result<(libc_sockaddr_un, socklen_t)>
+	{
+		use self::UnixDomainSocketAddress::*;
+
+		let raw_address = match self
+		{
+			&Path { ref socket_file_path, .. } => UnixDomainSocketRawAddress::Path(socket_file_path),
+
+			&Abstract { ref abstract_name } => UnixDomainSocketRawAddress::Abstract(&abstract_name[..]),
+
+			&Autobind => UnixDomainSocketRawAddress::Unnamed,
+		};
+
+		raw_address.to_sockaddr_un()
+	}
+
+	#[inline(always)]
+	fn bind_and_listen(settings: &StreamingServerListenerSocketSettings, socket_type: c_int, socket_address: &libc_sockaddr_un, socket_address_length: socklen_t) -> io::Result<StreamingServerListenerSocketFileDescriptor<sockaddr_un>>
+	{
+		let raw_file_descriptor = unsafe { socket(AF_UNIX, socket_type | SOCK_NONBLOCK | SOCK_CLOEXEC, 0) };
+		if unlikely!(raw_file_descriptor == -1)
+		{
+			return Err(io::Error::last_os_error())
+		}
+		let streaming_server_listener_socket_file_descriptor = unsafe { StreamingServerListenerSocketFileDescriptor::from_raw_fd(raw_file_descriptor) };
+
+		let send_buffer_size_in_bytes = settings.send_buffer_size_in_bytes as c_int;
+		let result = unsafe { setsockopt(raw_file_descriptor, SOL_SOCKET, SO_SNDBUF, &send_buffer_size_in_bytes as *const c_int as *const c_void, size_of::<c_int>() as socklen_t) };
+		if unlikely!(result != 0)
+		{
+			return Err(io::Error::last_os_error())
+		}
+
+		let result = unsafe { bind(raw_file_descriptor, socket_address as *const libc_sockaddr_un as *const sockaddr, socket_address_length) };
+		if unlikely!(result != 0)
+		{
+			return Err(io::Error::last_os_error())
+		}
+
+		let result = unsafe { listen(raw_file_descriptor, settings.back_log as c_int) };
+		if unlikely!(result != 0)
+		{
+			return Err(io::Error::last_os_error())
+		}
+
+		Ok(streaming_server_listener_socket_file_descriptor)
+	}
+}
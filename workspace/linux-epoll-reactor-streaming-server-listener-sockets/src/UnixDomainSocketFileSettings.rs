@@ -0,0 +1,128 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Settings for the socket file of an Unix domain socket bound to a file system path.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnixDomainSocketFileSettings
+{
+	/// If a socket file already exists at the path (eg left behind after a crash), unlink it before binding.
+	///
+	/// A socket file is only considered stale, and unlinked, if connecting to it is refused (`ECONNREFUSED`); socket files still being listened on, and files which are not sockets, are never unlinked.
+	pub unlink_stale_socket_file_before_bind: bool,
+
+	/// Unlink the socket file when the listener is dropped.
+	///
+	/// The socket file is only unlinked if it is still the one created by the listener.
+	pub unlink_socket_file_on_drop: bool,
+
+	/// File mode (permissions) to apply to the socket file after binding, eg `0o0660`.
+	///
+	/// There is a short window after binding before the mode is changed in which the process' `umask` applies.
+	pub socket_file_mode: Option<mode_t>,
+
+	/// User to own the socket file.
+	pub socket_file_owner: Option<uid_t>,
+
+	/// Group to own the socket file.
+	pub socket_file_group: Option<gid_t>,
+}
+
+impl Default for UnixDomainSocketFileSettings
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		UnixDomainSocketFileSettings
+		{
+			unlink_stale_socket_file_before_bind: true,
+			unlink_socket_file_on_drop: true,
+			socket_file_mode: None,
+			socket_file_owner: None,
+			socket_file_group: None,
+		}
+	}
+}
+
+impl UnixDomainSocketFileSettings
+{
+	#[inline(always)]
+	pub(crate) fn unlink_stale_socket_file(&self, socket_file_path: &Path) -> io::Result<()>
+	{
+		if !self.unlink_stale_socket_file_before_bind
+		{
+			return Ok(())
+		}
+
+		match symlink_metadata(socket_file_path)
+		{
+			Ok(metadata) => if metadata.file_type().is_socket() && Self::is_stale(socket_file_path)?
+			{
+				remove_file(socket_file_path)
+			}
+			else
+			{
+				Ok(())
+			},
+
+			Err(error) => if error.kind() == io::ErrorKind::NotFound
+			{
+				Ok(())
+			}
+			else
+			{
+				Err(error)
+			},
+		}
+	}
+
+	/// Uses a non-blocking connect; any outcome other than `ECONNREFUSED` (eg success or `EAGAIN` because the back log is full) means another process is listening.
+	#[inline(always)]
+	fn is_stale(socket_file_path: &Path) -> io::Result<bool>
+	{
+		let (socket_address, socket_address_length) = UnixDomainSocketRawAddress::Path(socket_file_path).to_sockaddr_un()?;
+
+		let raw_file_descriptor = unsafe { socket(AF_UNIX, SOCK_STREAM | SOCK_NONBLOCK | SOCK_CLOEXEC, 0) };
+		if unlikely!(raw_file_descriptor == -1)
+		{
+			return Err(io::Error::last_os_error())
+		}
+
+		let result = unsafe { connect(raw_file_descriptor, &socket_address as *const libc_sockaddr_un as *const sockaddr, socket_address_length) };
+		let is_stale = result == -1 && io::Error::last_os_error().raw_os_error() == Some(ECONNREFUSED);
+
+		unsafe { close(raw_file_descriptor) };
+
+		Ok(is_stale)
+	}
+
+	#[inline(always)]
+	pub(crate) fn apply(&self, socket_file_path: &Path) -> io::Result<Option<SocketFileUnlinker>>
+	{
+		if let Some(socket_file_mode) = self.socket_file_mode
+		{
+			set_permissions(socket_file_path, Permissions::from_mode(socket_file_mode))?;
+		}
+
+		if self.socket_file_owner.is_some() || self.socket_file_group.is_some()
+		{
+			const Unchanged: u32 = !0;
+
+			let socket_file_path_c_string = CString::new(socket_file_path.as_os_str().as_bytes()).map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+			let result = unsafe { chown(socket_file_path_c_string.as_ptr(), self.socket_file_owner.unwrap_or(Unchanged), self.socket_file_group.unwrap_or(Unchanged)) };
+			if unlikely!(result != 0)
+			{
+				return Err(io::Error::last_os_error())
+			}
+		}
+
+		if self.unlink_socket_file_on_drop
+		{
+			Ok(Some(SocketFileUnlinker::new(socket_file_path)?))
+		}
+		else
+		{
+			Ok(None)
+		}
+	}
+}
@@ -0,0 +1,76 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+#[derive(Debug)]
+pub(crate) struct AcceptLimiter<SD: RemotePeerConnectionKey>
+{
+	accept_limits: Arc<AcceptLimits<SD>>,
+	token_bucket: Option<TokenBucket>,
+	backing_off_until: Option<Instant>,
+}
+
+impl<SD: RemotePeerConnectionKey> AcceptLimiter<SD>
+{
+	#[inline(always)]
+	pub(crate) fn new(accept_limits: Arc<AcceptLimits<SD>>, now: Instant) -> Self
+	{
+		Self
+		{
+			token_bucket: accept_limits.settings().accept_rate_limit.as_ref().map(|accept_rate_limit| TokenBucket::new(accept_rate_limit, now)),
+			accept_limits,
+			backing_off_until: None,
+		}
+	}
+
+	/// Returns `None` if accepting can continue now.
+	#[inline(always)]
+	pub(crate) fn resume_accepting_at(&mut self, now: Instant) -> Option<Instant>
+	{
+		if let Some(backing_off_until) = self.backing_off_until
+		{
+			if now < backing_off_until
+			{
+				return Some(backing_off_until)
+			}
+			self.backing_off_until = None;
+		}
+
+		match self.token_bucket
+		{
+			None => None,
+
+			Some(ref mut token_bucket) => if unlikely!(token_bucket.is_empty(now))
+			{
+				Some(token_bucket.next_token_available_at())
+			}
+			else
+			{
+				None
+			}
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn begin_back_off(&mut self, now: Instant)
+	{
+		self.backing_off_until = Some(now + Duration::from_millis(self.accept_limits.settings().back_off_milliseconds as u64))
+	}
+
+	#[inline(always)]
+	pub(crate) fn try_take_accept_token(&mut self, now: Instant) -> bool
+	{
+		match self.token_bucket
+		{
+			None => true,
+
+			Some(ref mut token_bucket) => token_bucket.try_take(now),
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn try_acquire_connection_permit(&self, remote_peer_connection_key: SD::Key) -> Option<ConnectionPermit<SD>>
+	{
+		AcceptLimits::try_acquire_connection_permit(&self.accept_limits, remote_peer_connection_key)
+	}
+}
@@ -0,0 +1,130 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Limits on concurrent connections shared by one or more listeners.
+///
+/// Typically one instance is created for each listening socket address and shared (using an `Arc`) by the listeners for that address on every logical core, as accepted connections may be closed on a different logical core to the one which accepted them.
+pub struct AcceptLimits<SD: RemotePeerConnectionKey>
+{
+	settings: AcceptLimitsSettings,
+	concurrent_connections: AtomicUsize,
+	concurrent_connections_per_remote_peer: Mutex<HashMap<SD::Key, usize>>,
+	marker: PhantomData<SD>,
+}
+
+impl<SD: RemotePeerConnectionKey> Debug for AcceptLimits<SD>
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "AcceptLimits {{ settings: {:?}, concurrent_connections: {:?}, concurrent_connections_per_remote_peer: _ }}", self.settings, self.concurrent_connections)
+	}
+}
+
+impl<SD: RemotePeerConnectionKey> AcceptLimits<SD>
+{
+	/// Creates a new instance.
+	#[inline(always)]
+	pub fn new(settings: AcceptLimitsSettings) -> Self
+	{
+		Self
+		{
+			settings,
+			concurrent_connections: AtomicUsize::new(0),
+			concurrent_connections_per_remote_peer: Mutex::new(HashMap::default()),
+			marker: PhantomData,
+		}
+	}
+
+	/// Settings.
+	#[inline(always)]
+	pub fn settings(&self) -> &AcceptLimitsSettings
+	{
+		&self.settings
+	}
+
+	/// Number of connections currently holding a `ConnectionPermit`.
+	#[inline(always)]
+	pub fn concurrent_connections(&self) -> usize
+	{
+		self.concurrent_connections.load(Relaxed)
+	}
+
+	#[inline(always)]
+	pub(crate) fn try_acquire_connection_permit(this: &Arc<Self>, remote_peer_connection_key: SD::Key) -> Option<ConnectionPermit<SD>>
+	{
+		match this.settings.maximum_concurrent_connections
+		{
+			None =>
+			{
+				this.concurrent_connections.fetch_add(1, Acquire);
+			}
+
+			Some(maximum_concurrent_connections) =>
+			{
+				let mut concurrent_connections = this.concurrent_connections.load(Relaxed);
+				loop
+				{
+					if unlikely!(concurrent_connections >= maximum_concurrent_connections)
+					{
+						return None
+					}
+
+					match this.concurrent_connections.compare_exchange_weak(concurrent_connections, concurrent_connections + 1, Acquire, Relaxed)
+					{
+						Ok(_) => break,
+
+						Err(actual_concurrent_connections) => concurrent_connections = actual_concurrent_connections,
+					}
+				}
+			}
+		}
+
+		if let Some(maximum_concurrent_connections_per_remote_peer) = this.settings.maximum_concurrent_connections_per_remote_peer
+		{
+			let mut concurrent_connections_per_remote_peer = this.concurrent_connections_per_remote_peer.lock().unwrap();
+			let concurrent_connections = concurrent_connections_per_remote_peer.entry(remote_peer_connection_key).or_insert(0);
+
+			if unlikely!(*concurrent_connections >= maximum_concurrent_connections_per_remote_peer)
+			{
+				this.concurrent_connections.fetch_sub(1, Release);
+				return None
+			}
+
+			*concurrent_connections += 1;
+		}
+
+		Some
+		(
+			ConnectionPermit
+			{
+				accept_limits: this.clone(),
+				remote_peer_connection_key,
+			}
+		)
+	}
+
+	#[inline(always)]
+	fn release(&self, remote_peer_connection_key: SD::Key)
+	{
+		if self.settings.maximum_concurrent_connections_per_remote_peer.is_some()
+		{
+			let mut concurrent_connections_per_remote_peer = self.concurrent_connections_per_remote_peer.lock().unwrap();
+
+			let is_last_connection_from_remote_peer =
+			{
+				let concurrent_connections = concurrent_connections_per_remote_peer.get_mut(&remote_peer_connection_key).expect("Released a connection permit for a remote peer which was never acquired");
+				*concurrent_connections -= 1;
+				*concurrent_connections == 0
+			};
+
+			if is_last_connection_from_remote_peer
+			{
+				concurrent_connections_per_remote_peer.remove(&remote_peer_connection_key);
+			}
+		}
+
+		self.concurrent_connections.fetch_sub(1, Release);
+	}
+}
@@ -0,0 +1,51 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Limits to apply to connections accepted by a listener.
+///
+/// All limits are optional; `None` means unlimited.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AcceptLimitsSettings
+{
+	/// Maximum number of concurrent connections.
+	///
+	/// A connection counts towards this limit for as long as its `ConnectionPermit` is alive.
+	pub maximum_concurrent_connections: Option<usize>,
+
+	/// Maximum number of concurrent connections from the same remote peer.
+	///
+	/// For Internet Protocol version 4 and version 6 sockets, the remote peer is identified by its address (but not port); for Unix domain sockets, it is identified by its user identifier.
+	pub maximum_concurrent_connections_per_remote_peer: Option<usize>,
+
+	/// Token bucket rate limit on connections accepted.
+	///
+	/// Applied independently by each listener (ie per thread); when exhausted, connections are left in the back log until a token becomes available.
+	pub accept_rate_limit: Option<AcceptRateLimit>,
+
+	/// How long to stop accepting connections for (in milliseconds) when the per-process or system-wide limit on file descriptors is reached or the kernel runs out of memory.
+	///
+	/// Connections queued whilst backing off are left in the back log and accepted when the back off expires.
+	pub back_off_milliseconds: u16,
+
+	/// Maximum number of connections denied with `AccessControlDecision::Tarpit` to hold open at once.
+	///
+	/// Applied independently by each listener (ie per thread); zero disables tarpitting (such connections are closed immediately).
+	pub maximum_tarpitted_connections: usize,
+}
+
+impl Default for AcceptLimitsSettings
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			maximum_concurrent_connections: None,
+			maximum_concurrent_connections_per_remote_peer: None,
+			accept_rate_limit: None,
+			back_off_milliseconds: 100,
+			maximum_tarpitted_connections: 64,
+		}
+	}
+}
@@ -0,0 +1,33 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A token bucket rate limit on the number of connections accepted.
+///
+/// Applied independently by each listener (ie per thread); when the bucket is empty, the listener stops accepting and connections are left in the back log until a token becomes available.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AcceptRateLimit
+{
+	/// Maximum number of connections that can be accepted in a burst (ie the size of the bucket).
+	///
+	/// Zero is rounded up to one.
+	pub maximum_burst: u32,
+
+	/// Rate at which the bucket refills.
+	///
+	/// Zero is rounded up to one.
+	pub accepts_per_second: u32,
+}
+
+impl Default for AcceptRateLimit
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			maximum_burst: 1024,
+			accepts_per_second: 1024,
+		}
+	}
+}
@@ -0,0 +1,30 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Permits an accepted connection to count against the limits in `AcceptLimits`.
+///
+/// It should be held for as long as the connection is open; when it is dropped, the connection no longer counts towards the limits.
+pub struct ConnectionPermit<SD: RemotePeerConnectionKey>
+{
+	accept_limits: Arc<AcceptLimits<SD>>,
+	remote_peer_connection_key: SD::Key,
+}
+
+impl<SD: RemotePeerConnectionKey> Debug for ConnectionPermit<SD>
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "ConnectionPermit {{ accept_limits: {:?}, remote_peer_connection_key: {:?} }}", self.accept_limits, self.remote_peer_connection_key)
+	}
+}
+
+impl<SD: RemotePeerConnectionKey> Drop for ConnectionPermit<SD>
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		self.accept_limits.release(self.remote_peer_connection_key)
+	}
+}
@@ -0,0 +1,47 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Identifies a remote peer for the purposes of counting concurrent connections from it.
+pub trait RemotePeerConnectionKey: SocketData
+{
+	/// Type of the key.
+	type Key: Debug + Copy + Eq + Hash + Send + Sync;
+
+	/// Key for this remote peer.
+	fn remote_peer_connection_key(&self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<Self>) -> Self::Key;
+}
+
+impl RemotePeerConnectionKey for sockaddr_in
+{
+	type Key = Ipv4Addr;
+
+	#[inline(always)]
+	fn remote_peer_connection_key(&self, _streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<Self>) -> Self::Key
+	{
+		unsafe { transmute(self.sin_addr) }
+	}
+}
+
+impl RemotePeerConnectionKey for sockaddr_in6
+{
+	type Key = Ipv6Addr;
+
+	#[inline(always)]
+	fn remote_peer_connection_key(&self, _streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<Self>) -> Self::Key
+	{
+		unsafe { transmute(self.sin6_addr) }
+	}
+}
+
+impl RemotePeerConnectionKey for sockaddr_un
+{
+	/// Remote peers connecting over Unix domain sockets rarely have a meaningful address, so they are identified by their user identifier instead.
+	type Key = uid_t;
+
+	#[inline(always)]
+	fn remote_peer_connection_key(&self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<Self>) -> Self::Key
+	{
+		streaming_socket_file_descriptor.remote_peer_credentials().user_identifier
+	}
+}
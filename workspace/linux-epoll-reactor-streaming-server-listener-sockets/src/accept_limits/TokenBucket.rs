@@ -0,0 +1,152 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+#[derive(Debug)]
+pub(crate) struct TokenBucket
+{
+	maximum_tokens: u32,
+	available_tokens: u32,
+	nanoseconds_per_token: u64,
+	last_refilled_at: Instant,
+}
+
+impl TokenBucket
+{
+	const NanosecondsPerSecond: u64 = 1_000_000_000;
+
+	#[inline(always)]
+	pub(crate) fn new(accept_rate_limit: &AcceptRateLimit, now: Instant) -> Self
+	{
+		let maximum_tokens = max(accept_rate_limit.maximum_burst, 1);
+		let accepts_per_second = max(accept_rate_limit.accepts_per_second, 1) as u64;
+
+		Self
+		{
+			maximum_tokens,
+			available_tokens: maximum_tokens,
+			nanoseconds_per_token: max(Self::NanosecondsPerSecond / accepts_per_second, 1),
+			last_refilled_at: now,
+		}
+	}
+
+	/// Returns `false` if the bucket is empty.
+	#[inline(always)]
+	pub(crate) fn try_take(&mut self, now: Instant) -> bool
+	{
+		if unlikely!(self.is_empty(now))
+		{
+			return false
+		}
+
+		self.available_tokens -= 1;
+		true
+	}
+
+	/// Refills the bucket before checking.
+	#[inline(always)]
+	pub(crate) fn is_empty(&mut self, now: Instant) -> bool
+	{
+		if unlikely!(self.available_tokens == 0)
+		{
+			self.refill(now);
+		}
+
+		self.available_tokens == 0
+	}
+
+	/// Only meaningful if the bucket is empty.
+	#[inline(always)]
+	pub(crate) fn next_token_available_at(&self) -> Instant
+	{
+		self.last_refilled_at + Duration::from_nanos(self.nanoseconds_per_token)
+	}
+
+	#[inline(always)]
+	fn refill(&mut self, now: Instant)
+	{
+		let elapsed = now.duration_since(self.last_refilled_at);
+		let elapsed_nanoseconds = elapsed.as_secs() * Self::NanosecondsPerSecond + elapsed.subsec_nanos() as u64;
+
+		let new_tokens = elapsed_nanoseconds / self.nanoseconds_per_token;
+		if new_tokens == 0
+		{
+			return
+		}
+
+		let available_tokens = (self.available_tokens as u64) + new_tokens;
+		if available_tokens >= self.maximum_tokens as u64
+		{
+			self.available_tokens = self.maximum_tokens;
+			self.last_refilled_at = now;
+		}
+		else
+		{
+			self.available_tokens = available_tokens as u32;
+			self.last_refilled_at += Duration::from_nanos(new_tokens * self.nanoseconds_per_token);
+		}
+	}
+}
+
+#[cfg(test)]
+mod token_bucket_tests
+{
+	use super::*;
+
+	#[test]
+	fn starts_full_and_empties_after_maximum_burst()
+	{
+		let now = Instant::now();
+		let mut token_bucket = TokenBucket::new(&AcceptRateLimit { maximum_burst: 3, accepts_per_second: 1 }, now);
+
+		assert!(token_bucket.try_take(now));
+		assert!(token_bucket.try_take(now));
+		assert!(token_bucket.try_take(now));
+		assert!(!token_bucket.try_take(now));
+		assert!(token_bucket.is_empty(now));
+	}
+
+	#[test]
+	fn zero_maximum_burst_and_zero_rate_are_rounded_up_to_one()
+	{
+		let now = Instant::now();
+		let mut token_bucket = TokenBucket::new(&AcceptRateLimit { maximum_burst: 0, accepts_per_second: 0 }, now);
+
+		assert_eq!(token_bucket.maximum_tokens, 1);
+		assert_eq!(token_bucket.nanoseconds_per_token, TokenBucket::NanosecondsPerSecond);
+		assert!(token_bucket.try_take(now));
+		assert!(!token_bucket.try_take(now));
+	}
+
+	#[test]
+	fn refills_in_proportion_to_elapsed_time()
+	{
+		let now = Instant::now();
+		let mut token_bucket = TokenBucket::new(&AcceptRateLimit { maximum_burst: 10, accepts_per_second: 1000 }, now);
+		token_bucket.available_tokens = 0;
+
+		assert!(!token_bucket.is_empty(now + Duration::from_micros(5_500)));
+		assert_eq!(token_bucket.available_tokens, 5);
+		assert_eq!(token_bucket.last_refilled_at, now + Duration::from_millis(5));
+	}
+
+	#[test]
+	fn refill_does_not_exceed_maximum_burst()
+	{
+		let now = Instant::now();
+		let mut token_bucket = TokenBucket::new(&AcceptRateLimit { maximum_burst: 10, accepts_per_second: 1000 }, now);
+		token_bucket.available_tokens = 0;
+
+		assert!(!token_bucket.is_empty(now + Duration::from_secs(1)));
+		assert_eq!(token_bucket.available_tokens, 10);
+	}
+
+	#[test]
+	fn next_token_is_available_one_interval_after_last_refill()
+	{
+		let now = Instant::now();
+		let token_bucket = TokenBucket::new(&AcceptRateLimit { maximum_burst: 1, accepts_per_second: 4 }, now);
+
+		assert_eq!(token_bucket.next_token_available_at(), now + Duration::from_millis(250));
+	}
+}
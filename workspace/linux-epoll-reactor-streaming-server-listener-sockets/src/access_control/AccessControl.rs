@@ -5,15 +5,52 @@
 /// Holds data that determines if a connection is permitted.
 pub trait AccessControl<SD: SocketData>
 {
+	/// Decides if the remote peer is allowed based on its address or credentials.
+	///
+	/// `streaming_socket_service_identifier` identifies the listener which accepted the connection.
+	fn remote_peer_access_control_decision(&self, streaming_socket_service_identifier: u8, remote_peer_address: &SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>) -> AccessControlDecision;
+
 	/// Is the remote peer allowed based on its address or credentials?
-	fn is_remote_peer_allowed(&self, remote_peer_address: SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>) -> bool;
+	#[inline(always)]
+	fn is_remote_peer_allowed(&self, streaming_socket_service_identifier: u8, remote_peer_address: &SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>) -> bool
+	{
+		self.remote_peer_access_control_decision(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor).is_allowed()
+	}
+
+	/// Combines with `other`; both must allow.
+	#[inline(always)]
+	fn and<B: AccessControl<SD>>(self, other: B) -> AndAccessControl<Self, B> where Self: Sized
+	{
+		AndAccessControl(self, other)
+	}
+
+	/// Combines with `other`; either may allow.
+	#[inline(always)]
+	fn or<B: AccessControl<SD>>(self, other: B) -> OrAccessControl<Self, B> where Self: Sized
+	{
+		OrAccessControl(self, other)
+	}
+
+	/// Combines with `other`; `other` is only consulted if this abstains.
+	#[inline(always)]
+	fn then<B: AccessControl<SD>>(self, other: B) -> FirstMatchAccessControl<Self, B> where Self: Sized
+	{
+		FirstMatchAccessControl(self, other)
+	}
+
+	/// Passes every decision to `access_control_audit`.
+	#[inline(always)]
+	fn audited<AA: AccessControlAudit<SD>>(self, access_control_audit: AA) -> AuditedAccessControl<Self, AA> where Self: Sized
+	{
+		AuditedAccessControl(self, access_control_audit)
+	}
 }
 
 impl<A: AccessControl<SD>, SD: SocketData> AccessControl<SD> for Rc<A>
 {
 	#[inline(always)]
-	fn is_remote_peer_allowed(&self, remote_peer_address: SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>) -> bool
+	fn remote_peer_access_control_decision(&self, streaming_socket_service_identifier: u8, remote_peer_address: &SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>) -> AccessControlDecision
 	{
-		self.deref().is_remote_peer_allowed(remote_peer_address, streaming_socket_file_descriptor)
+		self.deref().remote_peer_access_control_decision(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor)
 	}
 }
@@ -0,0 +1,21 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Receives every decision made by an `AuditedAccessControl`, eg to log it.
+pub trait AccessControlAudit<SD: SocketData>
+{
+	/// Audit a decision.
+	///
+	/// `streaming_socket_service_identifier` identifies the listener which accepted the connection.
+	fn audit(&self, streaming_socket_service_identifier: u8, remote_peer_address: &SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, access_control_decision: &AccessControlDecision);
+}
+
+impl<SD: SocketData, F: Fn(u8, &SD, &StreamingSocketFileDescriptor<SD>, &AccessControlDecision)> AccessControlAudit<SD> for F
+{
+	#[inline(always)]
+	fn audit(&self, streaming_socket_service_identifier: u8, remote_peer_address: &SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, access_control_decision: &AccessControlDecision)
+	{
+		self(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor, access_control_decision)
+	}
+}
@@ -0,0 +1,66 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A decision made by an `AccessControl`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AccessControlDecision
+{
+	/// The remote peer is allowed.
+	Allow,
+
+	/// The remote peer is denied; the connection is closed immediately.
+	Deny,
+
+	/// The remote peer is denied for the reason given; the connection is closed immediately.
+	DenyWithReason(Cow<'static, str>),
+
+	/// The remote peer is denied, but the connection is held open (and never read from or written to) for the duration given before being closed.
+	///
+	/// This slows down abusive remote peers that would otherwise immediately reconnect.
+	Tarpit(Duration),
+
+	/// No decision was made.
+	///
+	/// `FirstMatchAccessControl` will consult the next `AccessControl`; a listener treats this as `Deny`.
+	Abstain,
+}
+
+impl AccessControlDecision
+{
+	/// Is this `Allow`?
+	#[inline(always)]
+	pub fn is_allowed(&self) -> bool
+	{
+		match self
+		{
+			&AccessControlDecision::Allow => true,
+
+			_ => false,
+		}
+	}
+
+	/// Is this `Abstain`?
+	#[inline(always)]
+	pub fn is_abstention(&self) -> bool
+	{
+		match self
+		{
+			&AccessControlDecision::Abstain => true,
+
+			_ => false,
+		}
+	}
+
+	/// Reason for a denial, if any.
+	#[inline(always)]
+	pub fn reason(&self) -> Option<&str>
+	{
+		match self
+		{
+			&AccessControlDecision::DenyWithReason(ref reason) => Some(reason.deref()),
+
+			_ => None,
+		}
+	}
+}
@@ -0,0 +1,207 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Deny and permitted lists used to construct a `RemotePeerAddressBasedAccessControl`.
+///
+/// See `RemotePeerAddressBasedAccessControl` for the meaning of each list.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct AccessControlLists
+{
+	/// Denied Internet Protocol version 4 subnets.
+	pub denied_protocol_version_4_subnets: InternetProtocolSubnets<Ipv4Addr>,
+
+	/// Permitted Internet Protocol version 4 subnets; `None` permits all.
+	pub permitted_protocol_version_4_subnets: Option<InternetProtocolSubnets<Ipv4Addr>>,
+
+	/// Denied Internet Protocol version 6 subnets.
+	pub denied_protocol_version_6_subnets: InternetProtocolSubnets<Ipv6Addr>,
+
+	/// Permitted Internet Protocol version 6 subnets; `None` permits all.
+	pub permitted_protocol_version_6_subnets: Option<InternetProtocolSubnets<Ipv6Addr>>,
+
+	/// Denied Unix domain socket user identifiers.
+	pub denied_unix_domain_user_identifierentifiers: HashSet<uid_t>,
+
+	/// Permitted Unix domain socket (primary) group identifiers; `None` permits all.
+	pub permitted_unix_domain_group_identifiers: Option<HashSet<gid_t>>,
+}
+
+impl AccessControlLists
+{
+	/// Loads Internet Protocol deny and (optionally) permitted lists from plain-text CIDR files.
+	///
+	/// See `load_cidr_file()` for the format.
+	/// Unix domain socket lists are left empty.
+	#[inline(always)]
+	pub fn from_cidr_files(denied_file_path: &Path, permitted_file_path: Option<&Path>) -> Result<Self, CidrFileError>
+	{
+		let (denied_protocol_version_4_subnets, denied_protocol_version_6_subnets) = Self::load_cidr_file(denied_file_path)?;
+
+		let (permitted_protocol_version_4_subnets, permitted_protocol_version_6_subnets) = match permitted_file_path
+		{
+			None => (None, None),
+
+			Some(permitted_file_path) =>
+			{
+				let (permitted_protocol_version_4_subnets, permitted_protocol_version_6_subnets) = Self::load_cidr_file(permitted_file_path)?;
+				(Some(permitted_protocol_version_4_subnets), Some(permitted_protocol_version_6_subnets))
+			}
+		};
+
+		Ok
+		(
+			Self
+			{
+				denied_protocol_version_4_subnets,
+				permitted_protocol_version_4_subnets,
+				denied_protocol_version_6_subnets,
+				permitted_protocol_version_6_subnets,
+				denied_unix_domain_user_identifierentifiers: HashSet::default(),
+				permitted_unix_domain_group_identifiers: None,
+			}
+		)
+	}
+
+	/// Loads subnets from a plain-text CIDR file.
+	///
+	/// The file has one subnet per line, eg `192.0.2.0/24` or `2001:db8::/32`.
+	/// A line without a mask, eg `192.0.2.1`, is a single address.
+	/// Blank lines are ignored, as is everything after a `#`.
+	pub fn load_cidr_file(file_path: &Path) -> Result<(InternetProtocolSubnets<Ipv4Addr>, InternetProtocolSubnets<Ipv6Addr>), CidrFileError>
+	{
+		let mut internet_protocol_version_4_subnets: InternetProtocolSubnets<Ipv4Addr> = InternetProtocolSubnets::default();
+		let mut internet_protocol_version_6_subnets: InternetProtocolSubnets<Ipv6Addr> = InternetProtocolSubnets::default();
+
+		let file = File::open(file_path)?;
+
+		for (line_index, line) in BufReader::new(file).lines().enumerate()
+		{
+			let line = line?;
+
+			let subnet = match line.find('#')
+			{
+				None => &line[..],
+				Some(index) => &line[.. index],
+			}.trim();
+
+			if subnet.is_empty()
+			{
+				continue
+			}
+
+			let invalid_line = || CidrFileError::InvalidLine { line_number: line_index + 1, line: line.clone() };
+
+			let (address, mask) = match subnet.find('/')
+			{
+				None => (subnet, None),
+				Some(index) => (&subnet[.. index], Some(subnet[(index + 1) .. ].parse::<u8>().map_err(|_| invalid_line())?)),
+			};
+
+			match address.parse::<IpAddr>().map_err(|_| invalid_line())?
+			{
+				IpAddr::V4(address) =>
+				{
+					let mask = mask.unwrap_or(32);
+					if unlikely!(mask > 32)
+					{
+						return Err(invalid_line())
+					}
+					internet_protocol_version_4_subnets.add_subnet(address, mask)
+				}
+
+				IpAddr::V6(address) =>
+				{
+					let mask = mask.unwrap_or(128);
+					if unlikely!(mask > 128)
+					{
+						return Err(invalid_line())
+					}
+					internet_protocol_version_6_subnets.add_subnet(address, mask)
+				}
+			}
+		}
+
+		Ok((internet_protocol_version_4_subnets, internet_protocol_version_6_subnets))
+	}
+}
+
+#[cfg(test)]
+mod access_control_lists_tests
+{
+	use super::*;
+	use ::std::env::temp_dir;
+	use ::std::fs::remove_file;
+	use ::std::fs::write;
+	use ::std::process;
+
+	fn load_cidr_file_contents(test_name: &str, contents: &str) -> Result<(InternetProtocolSubnets<Ipv4Addr>, InternetProtocolSubnets<Ipv6Addr>), CidrFileError>
+	{
+		let file_path = temp_dir().join(format!("linux-epoll-access-control-lists-{}-{}.cidr", process::id(), test_name));
+		write(&file_path, contents).unwrap();
+		let result = AccessControlLists::load_cidr_file(&file_path);
+		remove_file(&file_path).unwrap();
+		result
+	}
+
+	fn invalid_line_number(result: Result<(InternetProtocolSubnets<Ipv4Addr>, InternetProtocolSubnets<Ipv6Addr>), CidrFileError>) -> usize
+	{
+		match result
+		{
+			Err(CidrFileError::InvalidLine { line_number, .. }) => line_number,
+
+			unexpected @ _ => panic!("Expected an invalid line, not {:?}", unexpected),
+		}
+	}
+
+	#[test]
+	fn loads_subnets_single_addresses_comments_and_blank_lines()
+	{
+		let (internet_protocol_version_4_subnets, internet_protocol_version_6_subnets) = load_cidr_file_contents("valid", "# Header comment\n\n192.0.2.0/24\n  198.51.100.7  # A single address\n2001:db8::/32\n::1\n").unwrap();
+
+		assert_eq!(internet_protocol_version_4_subnets.len(), 2);
+		assert_eq!(internet_protocol_version_4_subnets.get(&Ipv4Addr::new(192, 0, 2, 0)), Some(&24));
+		assert_eq!(internet_protocol_version_4_subnets.get(&Ipv4Addr::new(198, 51, 100, 7)), Some(&32));
+
+		assert_eq!(internet_protocol_version_6_subnets.len(), 2);
+		assert_eq!(internet_protocol_version_6_subnets.get(&"2001:db8::".parse::<Ipv6Addr>().unwrap()), Some(&32));
+		assert_eq!(internet_protocol_version_6_subnets.get(&Ipv6Addr::LOCALHOST), Some(&128));
+	}
+
+	#[test]
+	fn empty_file_has_no_subnets()
+	{
+		let (internet_protocol_version_4_subnets, internet_protocol_version_6_subnets) = load_cidr_file_contents("empty", "").unwrap();
+
+		assert!(internet_protocol_version_4_subnets.is_empty());
+		assert!(internet_protocol_version_6_subnets.is_empty());
+	}
+
+	#[test]
+	fn rejects_mask_too_large_for_address_family()
+	{
+		assert_eq!(invalid_line_number(load_cidr_file_contents("mask-4", "192.0.2.0/24\n192.0.2.0/33\n")), 2);
+		assert_eq!(invalid_line_number(load_cidr_file_contents("mask-6", "2001:db8::/129\n")), 1);
+	}
+
+	#[test]
+	fn rejects_malformed_lines()
+	{
+		assert_eq!(invalid_line_number(load_cidr_file_contents("not-an-address", "example.com\n")), 1);
+		assert_eq!(invalid_line_number(load_cidr_file_contents("truncated-address", "192.0.2\n")), 1);
+		assert_eq!(invalid_line_number(load_cidr_file_contents("empty-mask", "192.0.2.0/\n")), 1);
+		assert_eq!(invalid_line_number(load_cidr_file_contents("negative-mask", "192.0.2.0/-1\n")), 1);
+		assert_eq!(invalid_line_number(load_cidr_file_contents("two-masks", "192.0.2.0/24/8\n")), 1);
+	}
+
+	#[test]
+	fn missing_file_is_an_input_output_error()
+	{
+		match AccessControlLists::load_cidr_file(&temp_dir().join("linux-epoll-access-control-lists-does-not-exist.cidr"))
+		{
+			Err(CidrFileError::InputOutput(_)) => (),
+
+			unexpected @ _ => panic!("Expected an input output error, not {:?}", unexpected),
+		}
+	}
+}
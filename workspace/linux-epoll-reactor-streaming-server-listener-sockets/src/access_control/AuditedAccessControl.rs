@@ -0,0 +1,18 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Passes every decision made by the wrapped `AccessControl` to an `AccessControlAudit`.
+#[derive(Debug)]
+pub struct AuditedAccessControl<AC, AA>(pub AC, pub AA);
+
+impl<SD: SocketData, AC: AccessControl<SD>, AA: AccessControlAudit<SD>> AccessControl<SD> for AuditedAccessControl<AC, AA>
+{
+	#[inline(always)]
+	fn remote_peer_access_control_decision(&self, streaming_socket_service_identifier: u8, remote_peer_address: &SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>) -> AccessControlDecision
+	{
+		let access_control_decision = self.0.remote_peer_access_control_decision(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor);
+		self.1.audit(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor, &access_control_decision);
+		access_control_decision
+	}
+}
@@ -0,0 +1,55 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// An error when loading a plain-text CIDR file.
+#[derive(Debug)]
+pub enum CidrFileError
+{
+	/// Could not open or read the file.
+	InputOutput(io::Error),
+
+	/// A line was not an Internet Protocol version 4 or version 6 address with an optional valid mask.
+	InvalidLine
+	{
+		/// One-based line number.
+		line_number: usize,
+
+		/// The line.
+		line: String,
+	},
+}
+
+impl Display for CidrFileError
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		Debug::fmt(self, f)
+	}
+}
+
+impl error::Error for CidrFileError
+{
+	#[inline(always)]
+	fn source(&self) -> Option<&(error::Error + 'static)>
+	{
+		use self::CidrFileError::*;
+
+		match self
+		{
+			&InputOutput(ref error) => Some(error),
+
+			&InvalidLine { .. } => None,
+		}
+	}
+}
+
+impl From<io::Error> for CidrFileError
+{
+	#[inline(always)]
+	fn from(error: io::Error) -> Self
+	{
+		CidrFileError::InputOutput(error)
+	}
+}
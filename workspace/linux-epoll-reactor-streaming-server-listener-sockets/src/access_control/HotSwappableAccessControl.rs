@@ -0,0 +1,79 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A per-thread `RemotePeerAddressBasedAccessControl` which follows replacements made to a shared `HotSwappableAccessControlList`.
+///
+/// Checking for a replacement costs one atomic load per decision.
+pub struct HotSwappableAccessControl
+{
+	hot_swappable_access_control_list: Arc<HotSwappableAccessControlList>,
+	current: RefCell<(usize, RemotePeerAddressBasedAccessControl)>,
+}
+
+impl Debug for HotSwappableAccessControl
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "HotSwappableAccessControl {{ hot_swappable_access_control_list: {:?}, current: {:?} }}", self.hot_swappable_access_control_list, self.current)
+	}
+}
+
+impl AccessControl<sockaddr_in> for HotSwappableAccessControl
+{
+	#[inline(always)]
+	fn remote_peer_access_control_decision(&self, streaming_socket_service_identifier: u8, remote_peer_address: &sockaddr_in, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_in>) -> AccessControlDecision
+	{
+		self.current().remote_peer_access_control_decision(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor)
+	}
+}
+
+impl AccessControl<sockaddr_in6> for HotSwappableAccessControl
+{
+	#[inline(always)]
+	fn remote_peer_access_control_decision(&self, streaming_socket_service_identifier: u8, remote_peer_address: &sockaddr_in6, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_in6>) -> AccessControlDecision
+	{
+		self.current().remote_peer_access_control_decision(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor)
+	}
+}
+
+impl AccessControl<sockaddr_un> for HotSwappableAccessControl
+{
+	#[inline(always)]
+	fn remote_peer_access_control_decision(&self, streaming_socket_service_identifier: u8, remote_peer_address: &sockaddr_un, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_un>) -> AccessControlDecision
+	{
+		self.current().remote_peer_access_control_decision(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor)
+	}
+}
+
+impl HotSwappableAccessControl
+{
+	/// Creates a new instance.
+	///
+	/// Create one per thread.
+	#[inline(always)]
+	pub fn new(hot_swappable_access_control_list: Arc<HotSwappableAccessControlList>) -> Self
+	{
+		let current = hot_swappable_access_control_list.current();
+
+		Self
+		{
+			hot_swappable_access_control_list,
+			current: RefCell::new(current),
+		}
+	}
+
+	#[inline(always)]
+	fn current(&self) -> Ref<RemotePeerAddressBasedAccessControl>
+	{
+		let generation = self.hot_swappable_access_control_list.generation();
+
+		if unlikely!(generation != self.current.borrow().0)
+		{
+			*self.current.borrow_mut() = self.hot_swappable_access_control_list.current();
+		}
+
+		Ref::map(self.current.borrow(), |&(_, ref remote_peer_address_based_access_control)| remote_peer_address_based_access_control)
+	}
+}
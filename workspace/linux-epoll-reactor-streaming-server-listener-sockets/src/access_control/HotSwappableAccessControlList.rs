@@ -0,0 +1,67 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Access control lists which can be replaced at runtime, eg to block an abusive range of addresses during an incident without restarting.
+///
+/// Shared (using an `Arc`) by a `HotSwappableAccessControl` on each thread; each thread rebuilds its lookup tables the next time it makes a decision after a replacement (in a similar fashion to read-copy-update, RCU).
+#[derive(Debug)]
+pub struct HotSwappableAccessControlList
+{
+	generation: AtomicUsize,
+	access_control_lists: Mutex<Arc<AccessControlLists>>,
+}
+
+impl HotSwappableAccessControlList
+{
+	/// Creates a new instance.
+	#[inline(always)]
+	pub fn new(access_control_lists: AccessControlLists) -> Self
+	{
+		Self
+		{
+			generation: AtomicUsize::new(0),
+			access_control_lists: Mutex::new(Arc::new(access_control_lists)),
+		}
+	}
+
+	/// Atomically replaces the access control lists.
+	///
+	/// Connections already accepted are unaffected.
+	#[inline(always)]
+	pub fn replace(&self, access_control_lists: AccessControlLists)
+	{
+		let mut current = self.access_control_lists.lock().unwrap();
+		*current = Arc::new(access_control_lists);
+		self.generation.fetch_add(1, Release);
+	}
+
+	/// Atomically replaces the access control lists with those loaded from plain-text CIDR files.
+	///
+	/// If loading fails, the current access control lists are left unchanged.
+	#[inline(always)]
+	pub fn replace_from_cidr_files(&self, denied_file_path: &Path, permitted_file_path: Option<&Path>) -> Result<(), CidrFileError>
+	{
+		let access_control_lists = AccessControlLists::from_cidr_files(denied_file_path, permitted_file_path)?;
+		self.replace(access_control_lists);
+		Ok(())
+	}
+
+	#[inline(always)]
+	fn generation(&self) -> usize
+	{
+		self.generation.load(Acquire)
+	}
+
+	#[inline(always)]
+	fn current(&self) -> (usize, RemotePeerAddressBasedAccessControl)
+	{
+		let (generation, access_control_lists) =
+		{
+			let current = self.access_control_lists.lock().unwrap();
+			(self.generation.load(Acquire), current.clone())
+		};
+
+		(generation, RemotePeerAddressBasedAccessControl::from(access_control_lists.deref().clone()))
+	}
+}
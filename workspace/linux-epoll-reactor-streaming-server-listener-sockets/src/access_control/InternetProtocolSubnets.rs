@@ -3,9 +3,18 @@
 
 
 /// `A` is either an Internet Protocol Version 4 address (`Ipv4Addr`) or an Internet Protocol Version 6 address (`Ipv6Addr`).
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InternetProtocolSubnets<A: Debug + Clone + PartialEq + Eq + PartialOrd + Hash>(HashMap<A, u8>);
 
+impl<A: Debug + Clone + PartialEq + Eq + PartialOrd + Hash> Default for InternetProtocolSubnets<A>
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		InternetProtocolSubnets(HashMap::default())
+	}
+}
+
 impl<A: Debug + Clone + PartialEq + Eq + PartialOrd + Hash> Deref for InternetProtocolSubnets<A>
 {
 	type Target = HashMap<A, u8>;
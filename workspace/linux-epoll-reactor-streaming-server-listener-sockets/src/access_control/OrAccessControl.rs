@@ -0,0 +1,32 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Either access control may allow.
+///
+/// The second is not consulted if the first allows; if neither allows, the decision of the second is returned, unless it abstained.
+#[derive(Debug)]
+pub struct OrAccessControl<A, B>(pub A, pub B);
+
+impl<SD: SocketData, A: AccessControl<SD>, B: AccessControl<SD>> AccessControl<SD> for OrAccessControl<A, B>
+{
+	#[inline(always)]
+	fn remote_peer_access_control_decision(&self, streaming_socket_service_identifier: u8, remote_peer_address: &SD, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>) -> AccessControlDecision
+	{
+		let first = self.0.remote_peer_access_control_decision(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor);
+		if first.is_allowed()
+		{
+			return first
+		}
+
+		let second = self.1.remote_peer_access_control_decision(streaming_socket_service_identifier, remote_peer_address, streaming_socket_file_descriptor);
+		if second.is_abstention()
+		{
+			first
+		}
+		else
+		{
+			second
+		}
+	}
+}
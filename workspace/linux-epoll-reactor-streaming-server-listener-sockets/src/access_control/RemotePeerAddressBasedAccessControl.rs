@@ -31,6 +31,27 @@ impl Debug for RemotePeerAddressBasedAccessControl
 
 impl RemotePeerAddressBasedAccessControl
 {
+	const DeniedSubnet: AccessControlDecision = AccessControlDecision::DenyWithReason(Cow::Borrowed("remote peer address is in a denied subnet"));
+
+	const NotInPermittedSubnet: AccessControlDecision = AccessControlDecision::DenyWithReason(Cow::Borrowed("remote peer address is not in a permitted subnet"));
+
+	const DeniedUserIdentifier: AccessControlDecision = AccessControlDecision::DenyWithReason(Cow::Borrowed("remote peer user identifier is denied"));
+
+	const NotInPermittedGroup: AccessControlDecision = AccessControlDecision::DenyWithReason(Cow::Borrowed("remote peer group identifier is not permitted"));
+
+	#[inline(always)]
+	fn allow_if(is_allowed: bool, otherwise: AccessControlDecision) -> AccessControlDecision
+	{
+		if likely!(is_allowed)
+		{
+			AccessControlDecision::Allow
+		}
+		else
+		{
+			otherwise
+		}
+	}
+
 	/// Creates a new instance.
 	///
 	/// Permitted lists are `Option`s.
@@ -50,22 +71,31 @@ impl RemotePeerAddressBasedAccessControl
 	}
 }
 
+impl From<AccessControlLists> for RemotePeerAddressBasedAccessControl
+{
+	#[inline(always)]
+	fn from(access_control_lists: AccessControlLists) -> Self
+	{
+		Self::new(access_control_lists.denied_protocol_version_4_subnets, access_control_lists.permitted_protocol_version_4_subnets, access_control_lists.denied_protocol_version_6_subnets, access_control_lists.permitted_protocol_version_6_subnets, access_control_lists.denied_unix_domain_user_identifierentifiers, access_control_lists.permitted_unix_domain_group_identifiers)
+	}
+}
+
 impl AccessControl<sockaddr_in> for RemotePeerAddressBasedAccessControl
 {
 	#[inline(always)]
-	fn is_remote_peer_allowed(&self, remote_peer_address: sockaddr_in, _streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_in>) -> bool
+	fn remote_peer_access_control_decision(&self, _streaming_socket_service_identifier: u8, remote_peer_address: &sockaddr_in, _streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_in>) -> AccessControlDecision
 	{
 		let remote_peer_address: Ipv4Addr = unsafe { transmute(remote_peer_address.sin_addr) };
 
 		if unlikely!(self.denied_protocol_version_4_subnets.is_match(remote_peer_address))
 		{
-			return false
+			return Self::DeniedSubnet
 		}
 
 		match self.permitted_protocol_version_4_subnets
 		{
-			None => true,
-			Some(ref ip_lookup_table) => ip_lookup_table.is_match(remote_peer_address)
+			None => AccessControlDecision::Allow,
+			Some(ref ip_lookup_table) => Self::allow_if(ip_lookup_table.is_match(remote_peer_address), Self::NotInPermittedSubnet),
 		}
 	}
 }
@@ -73,19 +103,19 @@ impl AccessControl<sockaddr_in> for RemotePeerAddressBasedAccessControl
 impl AccessControl<sockaddr_in6> for RemotePeerAddressBasedAccessControl
 {
 	#[inline(always)]
-	fn is_remote_peer_allowed(&self, remote_peer_address: sockaddr_in6, _streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_in6>) -> bool
+	fn remote_peer_access_control_decision(&self, _streaming_socket_service_identifier: u8, remote_peer_address: &sockaddr_in6, _streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_in6>) -> AccessControlDecision
 	{
 		let remote_peer_address: Ipv6Addr = unsafe { transmute(remote_peer_address.sin6_addr) };
 
 		if unlikely!(self.denied_protocol_version_6_subnets.is_match(remote_peer_address))
 		{
-			return false
+			return Self::DeniedSubnet
 		}
 
 		match self.permitted_protocol_version_6_subnets
 		{
-			None => true,
-			Some(ref ip_lookup_table) => ip_lookup_table.is_match(remote_peer_address)
+			None => AccessControlDecision::Allow,
+			Some(ref ip_lookup_table) => Self::allow_if(ip_lookup_table.is_match(remote_peer_address), Self::NotInPermittedSubnet),
 		}
 	}
 }
@@ -93,19 +123,19 @@ impl AccessControl<sockaddr_in6> for RemotePeerAddressBasedAccessControl
 impl AccessControl<sockaddr_un> for RemotePeerAddressBasedAccessControl
 {
 	#[inline(always)]
-	fn is_remote_peer_allowed(&self, _remote_peer_address: sockaddr_un, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_un>) -> bool
+	fn remote_peer_access_control_decision(&self, _streaming_socket_service_identifier: u8, _remote_peer_address: &sockaddr_un, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_un>) -> AccessControlDecision
 	{
 		let credentials = streaming_socket_file_descriptor.remote_peer_credentials();
 
-		if unlikely!(self.denied_unix_domain_user_identifierentifiers.contains(&credentials.user_identifierentifier))
+		if unlikely!(self.denied_unix_domain_user_identifierentifiers.contains(&credentials.user_identifier))
 		{
-			return false
+			return Self::DeniedUserIdentifier
 		}
 
 		match self.permitted_unix_domain_group_identifiers
 		{
-			None => true,
-			Some(ref group_identifiers) => group_identifiers.contains(&credentials.group_identifier),
+			None => AccessControlDecision::Allow,
+			Some(ref group_identifiers) => Self::allow_if(group_identifiers.contains(&credentials.group_identifier), Self::NotInPermittedGroup),
 		}
 	}
 }
@@ -0,0 +1,61 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Holds connections denied with `AccessControlDecision::Tarpit` open until they expire.
+#[derive(Debug)]
+pub(crate) struct Tarpit<SD: SocketData>
+{
+	maximum_tarpitted_connections: usize,
+	tarpitted_connections: VecDeque<(Instant, StreamingSocketFileDescriptor<SD>)>,
+}
+
+impl<SD: SocketData> Tarpit<SD>
+{
+	#[inline(always)]
+	pub(crate) fn new(maximum_tarpitted_connections: usize) -> Self
+	{
+		Self
+		{
+			maximum_tarpitted_connections,
+			tarpitted_connections: VecDeque::with_capacity(maximum_tarpitted_connections),
+		}
+	}
+
+	/// If the tarpit is full, the connection held the longest is closed to make room.
+	#[inline(always)]
+	pub(crate) fn hold(&mut self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, duration: Duration)
+	{
+		if unlikely!(self.maximum_tarpitted_connections == 0)
+		{
+			return
+		}
+
+		if unlikely!(self.tarpitted_connections.len() == self.maximum_tarpitted_connections)
+		{
+			self.tarpitted_connections.pop_front();
+		}
+
+		self.tarpitted_connections.push_back((Instant::now() + duration, streaming_socket_file_descriptor))
+	}
+
+	/// Closes connections that have been held for their duration.
+	#[inline(always)]
+	pub(crate) fn release_expired(&mut self)
+	{
+		if likely!(self.tarpitted_connections.is_empty())
+		{
+			return
+		}
+
+		let now = Instant::now();
+		self.tarpitted_connections.retain(|&(expires_at, _)| expires_at > now)
+	}
+
+	/// When the next held connection expires, if any.
+	#[inline(always)]
+	pub(crate) fn next_expires_at(&self) -> Option<Instant>
+	{
+		self.tarpitted_connections.iter().map(|&(expires_at, _)| expires_at).min()
+	}
+}
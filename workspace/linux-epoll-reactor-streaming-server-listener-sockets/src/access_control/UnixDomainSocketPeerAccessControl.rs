@@ -0,0 +1,176 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Unix domain socket access control based on the remote peer's process.
+///
+/// This allows local control sockets to be restricted to specific sibling services rather than to whole user identifiers; combine it with `RemotePeerAddressBasedAccessControl` using `AccessControl::and()`.
+///
+/// The process identifier is that of the process which called `connect()`; it may have exited and been re-used by the time the connection is accepted, so the executable path and cgroup checks are best-effort.
+/// Security labels (SELinux or AppArmor) are obtained using `SO_PEERSEC` and are not affected by this race.
+///
+/// Each permitted list is optional; if `None`, it is not checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixDomainSocketPeerAccessControl
+{
+	/// Permitted process identifiers.
+	pub permitted_process_identifiers: Option<HashSet<pid_t>>,
+
+	/// Permitted executable paths, as obtained from `/proc/<pid>/exe`.
+	pub permitted_executable_paths: Option<HashSet<PathBuf>>,
+
+	/// Permitted cgroup path prefixes, eg `/system.slice/example.service`, as obtained from `/proc/<pid>/cgroup`.
+	///
+	/// Matching is by path component; the remote peer is permitted if any of its cgroups (for cgroup version 1, one per hierarchy) starts with any prefix.
+	pub permitted_cgroup_path_prefixes: Option<Vec<PathBuf>>,
+
+	/// Permitted security labels, eg `system_u:system_r:example_t:s0` for SELinux or `/usr/bin/example (enforce)` for AppArmor, without a trailing ASCII NUL.
+	pub permitted_security_labels: Option<HashSet<Vec<u8>>>,
+
+	/// Location of the `/proc` file system.
+	pub proc_path: PathBuf,
+}
+
+impl Default for UnixDomainSocketPeerAccessControl
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			permitted_process_identifiers: None,
+			permitted_executable_paths: None,
+			permitted_cgroup_path_prefixes: None,
+			permitted_security_labels: None,
+			proc_path: PathBuf::from("/proc"),
+		}
+	}
+}
+
+impl AccessControl<sockaddr_un> for UnixDomainSocketPeerAccessControl
+{
+	#[inline(always)]
+	fn remote_peer_access_control_decision(&self, _streaming_socket_service_identifier: u8, _remote_peer_address: &sockaddr_un, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_un>) -> AccessControlDecision
+	{
+		#[inline(always)]
+		fn deny(reason: &'static str) -> AccessControlDecision
+		{
+			AccessControlDecision::DenyWithReason(Cow::Borrowed(reason))
+		}
+
+		let process_identifier = streaming_socket_file_descriptor.remote_peer_credentials().process_identifier;
+
+		if let Some(ref permitted_process_identifiers) = self.permitted_process_identifiers
+		{
+			if unlikely!(!permitted_process_identifiers.contains(&process_identifier))
+			{
+				return deny("remote peer process identifier is not permitted")
+			}
+		}
+
+		if let Some(ref permitted_executable_paths) = self.permitted_executable_paths
+		{
+			match self.executable_path(process_identifier)
+			{
+				Err(_) => return deny("remote peer executable path could not be read"),
+
+				Ok(executable_path) => if unlikely!(!permitted_executable_paths.contains(&executable_path))
+				{
+					return deny("remote peer executable path is not permitted")
+				},
+			}
+		}
+
+		if let Some(ref permitted_cgroup_path_prefixes) = self.permitted_cgroup_path_prefixes
+		{
+			match self.cgroup_paths(process_identifier)
+			{
+				Err(_) => return deny("remote peer cgroups could not be read"),
+
+				Ok(cgroup_paths) => if unlikely!(!cgroup_paths.iter().any(|cgroup_path| permitted_cgroup_path_prefixes.iter().any(|permitted_cgroup_path_prefix| cgroup_path.starts_with(permitted_cgroup_path_prefix))))
+				{
+					return deny("remote peer cgroup is not permitted")
+				},
+			}
+		}
+
+		if let Some(ref permitted_security_labels) = self.permitted_security_labels
+		{
+			match Self::security_label(streaming_socket_file_descriptor)
+			{
+				Err(_) => return deny("remote peer security label could not be obtained"),
+
+				Ok(security_label) => if unlikely!(!permitted_security_labels.contains(&security_label))
+				{
+					return deny("remote peer security label is not permitted")
+				},
+			}
+		}
+
+		AccessControlDecision::Allow
+	}
+}
+
+impl UnixDomainSocketPeerAccessControl
+{
+	#[inline(always)]
+	fn process_path(&self, process_identifier: pid_t, file_name: &str) -> PathBuf
+	{
+		let mut path = self.proc_path.clone();
+		path.push(process_identifier.to_string());
+		path.push(file_name);
+		path
+	}
+
+	#[inline(always)]
+	fn executable_path(&self, process_identifier: pid_t) -> io::Result<PathBuf>
+	{
+		read_link(self.process_path(process_identifier, "exe"))
+	}
+
+	/// Lines are of the form `hierarchy-ID:controller-list:cgroup-path`; for cgroup version 2, there is one line, `0::cgroup-path`.
+	#[inline(always)]
+	fn cgroup_paths(&self, process_identifier: pid_t) -> io::Result<Vec<PathBuf>>
+	{
+		let cgroup = read_to_string(self.process_path(process_identifier, "cgroup"))?;
+
+		Ok(cgroup.lines().filter_map(|line| line.splitn(3, ':').nth(2)).map(PathBuf::from).collect())
+	}
+
+	#[inline(always)]
+	fn security_label(streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<sockaddr_un>) -> io::Result<Vec<u8>>
+	{
+		const InitialSecurityLabelCapacity: usize = 256;
+
+		let mut security_label: Vec<u8> = Vec::with_capacity(InitialSecurityLabelCapacity);
+
+		loop
+		{
+			let mut length = security_label.capacity() as socklen_t;
+
+			let result = unsafe { getsockopt(streaming_socket_file_descriptor.as_raw_fd(), SOL_SOCKET, SO_PEERSEC, security_label.as_mut_ptr() as *mut c_void, &mut length) };
+
+			if likely!(result == 0)
+			{
+				unsafe { security_label.set_len(length as usize) };
+
+				// Security labels are usually, but not always, terminated by an ASCII NUL.
+				if security_label.last() == Some(&0)
+				{
+					security_label.pop();
+				}
+
+				return Ok(security_label)
+			}
+
+			let error = io::Error::last_os_error();
+			if error.raw_os_error() == Some(ERANGE)
+			{
+				security_label.reserve(length as usize);
+				continue
+			}
+
+			return Err(error)
+		}
+	}
+}
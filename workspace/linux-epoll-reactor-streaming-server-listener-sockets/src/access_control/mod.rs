@@ -6,6 +6,18 @@ use super::*;
 
 
 include!("AccessControl.rs");
+include!("AccessControlAudit.rs");
+include!("AccessControlDecision.rs");
+include!("AccessControlLists.rs");
+include!("AndAccessControl.rs");
+include!("AuditedAccessControl.rs");
+include!("CidrFileError.rs");
+include!("FirstMatchAccessControl.rs");
+include!("HotSwappableAccessControl.rs");
+include!("HotSwappableAccessControlList.rs");
 include!("InternetProtocolSubnets.rs");
 include!("IpLookupTableMatch.rs");
+include!("OrAccessControl.rs");
 include!("RemotePeerAddressBasedAccessControl.rs");
+include!("Tarpit.rs");
+include!("UnixDomainSocketPeerAccessControl.rs");
@@ -4,11 +4,51 @@
 
 #[macro_use] extern crate likely;
 extern crate linux_epoll;
+#[macro_use] extern crate serde_derive;
 extern crate treebitmap;
 
 
+use self::accept_limits::*;
 use self::access_control::*;
+use ::linux_epoll::libc::AF_UNIX;
+use ::linux_epoll::libc::bind;
+use ::linux_epoll::libc::c_int;
+use ::linux_epoll::libc::c_long;
+use ::linux_epoll::libc::c_void;
+use ::linux_epoll::libc::chown;
+use ::linux_epoll::libc::CLOCK_MONOTONIC;
+use ::linux_epoll::libc::close;
+use ::linux_epoll::libc::connect;
+use ::linux_epoll::libc::ECONNREFUSED;
+use ::linux_epoll::libc::ERANGE;
+use ::linux_epoll::libc::getsockopt;
 use ::linux_epoll::libc::gid_t;
+use ::linux_epoll::libc::IPPROTO_TCP;
+use ::linux_epoll::libc::itimerspec;
+use ::linux_epoll::libc::listen;
+use ::linux_epoll::libc::mode_t;
+use ::linux_epoll::libc::pid_t;
+use ::linux_epoll::libc::read;
+use ::linux_epoll::libc::setsockopt;
+use ::linux_epoll::libc::SO_PEERSEC;
+use ::linux_epoll::libc::SO_SNDBUF;
+use ::linux_epoll::libc::SOCK_CLOEXEC;
+use ::linux_epoll::libc::SOCK_NONBLOCK;
+use ::linux_epoll::libc::SOCK_SEQPACKET;
+use ::linux_epoll::libc::SOCK_STREAM;
+use ::linux_epoll::libc::sockaddr;
+use ::linux_epoll::libc::sockaddr_un as libc_sockaddr_un;
+use ::linux_epoll::libc::socket;
+use ::linux_epoll::libc::SOL_SOCKET;
+use ::linux_epoll::libc::socklen_t;
+use ::linux_epoll::libc::TCP_DEFER_ACCEPT;
+use ::linux_epoll::libc::TCP_FASTOPEN;
+use ::linux_epoll::libc::TFD_CLOEXEC;
+use ::linux_epoll::libc::TFD_NONBLOCK;
+use ::linux_epoll::libc::time_t;
+use ::linux_epoll::libc::timerfd_create;
+use ::linux_epoll::libc::timerfd_settime;
+use ::linux_epoll::libc::timespec;
 use ::linux_epoll::libc::uid_t;
 use ::linux_epoll::*;
 use ::linux_epoll::arena::*;
@@ -21,35 +61,85 @@ use ::linux_epoll::file_descriptors::socket::syscall::sockaddr_un;
 use ::linux_epoll::hashbrown::*;
 use ::linux_epoll::message_dispatch::QueuePerThreadQueuesPublisher;
 use ::linux_epoll::reactor::*;
+use ::std::borrow::Cow;
+use ::std::cell::Ref;
+use ::std::cell::RefCell;
+use ::std::cmp::max;
+use ::std::cmp::min;
+use ::std::collections::VecDeque;
+use ::std::error;
+use ::std::ffi::CString;
 use ::std::fmt;
 use ::std::fmt::Debug;
+use ::std::fmt::Display;
 use ::std::fmt::Formatter;
+use ::std::fs::DirBuilder;
+use ::std::fs::File;
+use ::std::fs::Permissions;
+use ::std::fs::read_link;
+use ::std::fs::read_to_string;
+use ::std::fs::remove_file;
+use ::std::fs::set_permissions;
+use ::std::fs::symlink_metadata;
 use ::std::mem::transmute;
 use ::std::hash::Hash;
+use ::std::io;
+use ::std::io::BufRead;
+use ::std::io::BufReader;
+use ::std::marker::PhantomData;
+use ::std::mem::size_of;
+use ::std::net::IpAddr;
 use ::std::net::Ipv4Addr;
 use ::std::net::Ipv6Addr;
 use ::std::net::SocketAddrV4;
 use ::std::net::SocketAddrV6;
 use ::std::ops::Deref;
 use ::std::ops::DerefMut;
+use ::std::os::unix::ffi::OsStrExt;
+use ::std::os::unix::fs::DirBuilderExt;
+use ::std::os::unix::fs::FileTypeExt;
+use ::std::os::unix::fs::MetadataExt;
+use ::std::os::unix::fs::PermissionsExt;
+use ::std::os::unix::io::AsRawFd;
+use ::std::os::unix::io::FromRawFd;
+use ::std::os::unix::io::RawFd;
+use ::std::path::Path;
 use ::std::path::PathBuf;
 use ::std::ptr::NonNull;
+use ::std::ptr::null_mut;
 use ::std::ptr::write;
 use ::std::rc::Rc;
 use ::std::sync::Arc;
+use ::std::sync::Mutex;
+use ::std::sync::atomic::AtomicUsize;
+use ::std::sync::atomic::Ordering::Acquire;
+use ::std::sync::atomic::Ordering::Relaxed;
+use ::std::sync::atomic::Ordering::Release;
+use ::std::time::Duration;
+use ::std::time::Instant;
 use ::treebitmap::IpLookupTable;
 
 
+/// Limits on the rate of accepting and the number of concurrent connections.
+pub mod accept_limits;
+
+
 /// Access control for streaming sockets.
 pub mod access_control;
 
 
 include!("AcceptedStreamingSocketMessage.rs");
+include!("ReactivationTimer.rs");
+include!("SequentialPacketUnixDomainSocketAddress.rs");
+include!("ServerListenerSocketAddress.rs");
+include!("SocketFileUnlinker.rs");
 include!("streaming_server_listener_reactor.rs");
 include!("StreamingServerListenerSocketCommon.rs");
 include!("StreamingServerListenerSocketInternetProtocolVersion4Reactor.rs");
 include!("StreamingServerListenerSocketInternetProtocolVersion6Reactor.rs");
 include!("StreamingServerListenerSocketReactor.rs");
+include!("StreamingServerListenerSocketSequentialPacketUnixDomainReactor.rs");
 include!("StreamingServerListenerSocketSettings.rs");
 include!("StreamingServerListenerSocketUnixDomainReactor.rs");
 include!("UnixDomainSocketAddress.rs");
+include!("UnixDomainSocketFileSettings.rs");
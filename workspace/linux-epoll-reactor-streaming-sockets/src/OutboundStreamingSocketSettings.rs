@@ -0,0 +1,168 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Settings to apply to an outbound connection established by `connect_internet_protocol_version_4()` or `connect_internet_protocol_version_6()`.
+///
+/// The counterpart of `StreamingServerListenerSocketSettings`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct OutboundStreamingSocketSettings
+{
+	/// Send buffer size (in bytes).
+	pub send_buffer_size_in_bytes: usize,
+
+	/// Receive buffer size (in bytes).
+	pub receive_buffer_size_in_bytes: usize,
+
+	/// Idle for how many seconds before TCP keep-alive is started?
+	pub idles_before_keep_alive_seconds: u16,
+
+	/// Interval in seconds between TCP keep-alive probes.
+	pub keep_alive_interval_seconds: u16,
+
+	/// Maximum number of TCP keep-alive probes to send.
+	pub maximum_keep_alive_probes: u16,
+
+	/// How long to linger (in seconds).
+	pub linger_seconds: u16,
+
+	/// How long to linger in the TCP FIN-WAIT2 state (in seconds).
+	pub linger_in_FIN_WAIT2_seconds: u16,
+
+	/// Zero is rounded up to one.
+	///
+	/// How many TCP SYN segments to try to transmit before giving up on a connection attempt?
+	pub maximum_SYN_transmits: u16,
+
+	/// Send initial data in the SYN using TCP Fast Open if a cookie for the remote peer is cached (`TCP_FASTOPEN_CONNECT`).
+	///
+	/// Requires bit 1 (`0x1`) of `/proc/sys/net/ipv4/tcp_fastopen` to be set.
+	/// Only use for protocols which tolerate replayed initial data (eg TLS).
+	pub fast_open_connect: bool,
+
+	/// Other TCP options.
+	pub transmission_control_protocol_options: TransmissionControlProtocolOptions,
+}
+
+impl Default for OutboundStreamingSocketSettings
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		OutboundStreamingSocketSettings
+		{
+			send_buffer_size_in_bytes: 64 * 1024,
+			receive_buffer_size_in_bytes: 64 * 1024,
+			idles_before_keep_alive_seconds: 60,
+			keep_alive_interval_seconds: 5,
+			maximum_keep_alive_probes: 5,
+			linger_seconds: 60,
+			linger_in_FIN_WAIT2_seconds: 0,
+			maximum_SYN_transmits: 1,
+			fast_open_connect: false,
+			transmission_control_protocol_options: TransmissionControlProtocolOptions::default(),
+		}
+	}
+}
+
+impl OutboundStreamingSocketSettings
+{
+	/// Not yet defined by the `libc` crate.
+	const TCP_FASTOPEN_CONNECT: c_int = 30;
+
+	/// Creates a non-blocking Transmission Control Protocol (TCP) socket over Internet Protocol version 4 with these settings and starts connecting it.
+	///
+	/// The connection completes asynchronously; register the socket with a streaming socket reactor, which waits for it to become writable.
+	#[inline(always)]
+	pub fn connect_internet_protocol_version_4(&self, remote_peer_address: SocketAddrV4) -> io::Result<StreamingSocketFileDescriptor<sockaddr_in>>
+	{
+		let mut socket_address: libc_sockaddr_in = unsafe { zeroed() };
+		socket_address.sin_family = AF_INET as sa_family_t;
+		socket_address.sin_port = remote_peer_address.port().to_be();
+		socket_address.sin_addr.s_addr = u32::from(*remote_peer_address.ip()).to_be();
+
+		self.new_socket_and_connect(AF_INET, &socket_address, false)
+	}
+
+	/// Creates a non-blocking Transmission Control Protocol (TCP) socket over Internet Protocol version 6 with these settings and starts connecting it.
+	///
+	/// The connection completes asynchronously; register the socket with a streaming socket reactor, which waits for it to become writable.
+	#[inline(always)]
+	pub fn connect_internet_protocol_version_6(&self, remote_peer_address: SocketAddrV6) -> io::Result<StreamingSocketFileDescriptor<sockaddr_in6>>
+	{
+		let mut socket_address: libc_sockaddr_in6 = unsafe { zeroed() };
+		socket_address.sin6_family = AF_INET6 as sa_family_t;
+		socket_address.sin6_port = remote_peer_address.port().to_be();
+		socket_address.sin6_flowinfo = remote_peer_address.flowinfo();
+		socket_address.sin6_addr.s6_addr = remote_peer_address.ip().octets();
+		socket_address.sin6_scope_id = remote_peer_address.scope_id();
+
+		self.new_socket_and_connect(AF_INET6, &socket_address, true)
+	}
+
+	/// Applies `fast_open_connect` and `transmission_control_protocol_options`, which must be done before `connect()`.
+	#[inline(always)]
+	pub fn apply_transmission_control_protocol_options(&self, raw_file_descriptor: RawFd, is_internet_protocol_version_6: bool) -> io::Result<()>
+	{
+		if self.fast_open_connect
+		{
+			TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, IPPROTO_TCP, Self::TCP_FASTOPEN_CONNECT, 1)?;
+		}
+
+		self.transmission_control_protocol_options.apply(raw_file_descriptor, is_internet_protocol_version_6)
+	}
+
+	#[inline(always)]
+	fn new_socket_and_connect<SD: SocketData, SA>(&self, domain: c_int, socket_address: &SA, is_internet_protocol_version_6: bool) -> io::Result<StreamingSocketFileDescriptor<SD>>
+	{
+		let raw_file_descriptor = unsafe { socket(domain, SOCK_STREAM | SOCK_NONBLOCK | SOCK_CLOEXEC, 0) };
+		if unlikely!(raw_file_descriptor == -1)
+		{
+			return Err(io::Error::last_os_error())
+		}
+		let streaming_socket_file_descriptor = unsafe { StreamingSocketFileDescriptor::from_raw_fd(raw_file_descriptor) };
+
+		self.apply_socket_options(raw_file_descriptor)?;
+		self.apply_transmission_control_protocol_options(raw_file_descriptor, is_internet_protocol_version_6)?;
+
+		let result = unsafe { connect(raw_file_descriptor, socket_address as *const SA as *const sockaddr, size_of::<SA>() as socklen_t) };
+		if unlikely!(result != 0)
+		{
+			let error = io::Error::last_os_error();
+			if error.raw_os_error() != Some(EINPROGRESS)
+			{
+				return Err(error)
+			}
+		}
+
+		Ok(streaming_socket_file_descriptor)
+	}
+
+	#[inline(always)]
+	fn apply_socket_options(&self, raw_file_descriptor: RawFd) -> io::Result<()>
+	{
+		TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, SOL_SOCKET, SO_SNDBUF, self.send_buffer_size_in_bytes as c_int)?;
+		TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, SOL_SOCKET, SO_RCVBUF, self.receive_buffer_size_in_bytes as c_int)?;
+
+		TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, SOL_SOCKET, SO_KEEPALIVE, 1)?;
+		TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_KEEPIDLE, self.idles_before_keep_alive_seconds as c_int)?;
+		TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_KEEPINTVL, self.keep_alive_interval_seconds as c_int)?;
+		TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_KEEPCNT, self.maximum_keep_alive_probes as c_int)?;
+
+		let linger_option = linger
+		{
+			l_onoff: 1,
+			l_linger: self.linger_seconds as c_int,
+		};
+		let result = unsafe { setsockopt(raw_file_descriptor, SOL_SOCKET, SO_LINGER, &linger_option as *const linger as *const c_void, size_of::<linger>() as socklen_t) };
+		if unlikely!(result != 0)
+		{
+			return Err(io::Error::last_os_error())
+		}
+
+		TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_LINGER2, self.linger_in_FIN_WAIT2_seconds as c_int)?;
+		TransmissionControlProtocolOptions::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_SYNCNT, max(self.maximum_SYN_transmits, 1) as c_int)
+	}
+}
@@ -22,5 +22,7 @@ pub enum ReactEdgeTriggeredStatus
 	/// The remote peer closed cleanly.
 	///
 	/// This includes TCP 'half-close' shutdowns (which are near useless on modern socket protocols that use TLS).
+	///
+	/// A coroutine may continue after this (eg to write to a half-closed remote peer); once it does, it will receive this status again on every subsequent event.
 	RemotePeerClosedCleanly,
 }
@@ -2,13 +2,14 @@
 // Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
 
 
-struct StreamingSocketCommon<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: SocketData>
+#[doc(hidden)]
+pub struct StreamingSocketCommon<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: RemotePeerConnectionKey>
 {
 	started_coroutine: StartedStackAndTypeSafeTransfer<SimpleStack, Self>,
 }
 
 #[doc(hidden)]
-impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: SocketData> Debug for StreamingSocketCommon<SF, SU, SD>
+impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: RemotePeerConnectionKey> Debug for StreamingSocketCommon<SF, SU, SD>
 {
 	#[inline(always)]
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result
@@ -18,9 +19,9 @@ impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>,
 }
 
 #[doc(hidden)]
-impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: SocketData> Coroutine for StreamingSocketCommon<SF, SU, SD>
+impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: RemotePeerConnectionKey> Coroutine for StreamingSocketCommon<SF, SU, SD>
 {
-	type StartArguments = (StreamingSocketFileDescriptor<SD>, Rc<SF>, SF::AdditionalArguments, Rc<SU>);
+	type StartArguments = (StreamingSocketFileDescriptor<SD>, Rc<SF>, SF::AdditionalArguments, Rc<SU>, Option<ConnectionPermit<SD>>);
 
 	type ResumeArguments = ReactEdgeTriggeredStatus;
 
@@ -31,7 +32,7 @@ impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>,
 	#[inline(always)]
 	fn coroutine<'yielder>(start_arguments: Self::StartArguments, yielder: Yielder<'yielder, Self::ResumeArguments, Self::Yields, Self::Complete>) -> Self::Complete
 	{
-		let (streaming_socket_file_descriptor, server_stream_factory, additional_arguments, stream_user) = start_arguments;
+		let (streaming_socket_file_descriptor, server_stream_factory, additional_arguments, stream_user, _connection_permit) = start_arguments;
 
 		let (stream, proxy_or_tunnel_information) = server_stream_factory.new_stream_and_handshake(streaming_socket_file_descriptor, yielder, additional_arguments)?;
 
@@ -40,10 +41,10 @@ impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>,
 }
 
 #[doc(hidden)]
-impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: SocketData> StreamingSocketCommon<SF, SU, SD>
+impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: RemotePeerConnectionKey> StreamingSocketCommon<SF, SU, SD>
 {
 	#[inline(always)]
-	fn do_initial_input_and_output_and_register_with_epoll_if_necesssary<A: Arena<SSR>, SSR: StreamingSocketReactor<SF, SU, SD>, EPR: EventPollRegister>(event_poll_register: &EPR, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, (streaming_socket_file_descriptor, server_stream_factory, additional_arguments, stream_user): (SSR::FileDescriptor, Rc<SF>, SF::AdditionalArguments, Rc<SU>)) -> Result<(), EventPollRegistrationError>
+	fn do_initial_input_and_output_and_register_with_epoll_if_necesssary<A: Arena<SSR>, SSR: StreamingSocketReactor<SF, SU, SD>, EPR: EventPollRegister>(event_poll_register: &EPR, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, (streaming_socket_file_descriptor, server_stream_factory, additional_arguments, stream_user, connection_permit): (SSR::FileDescriptor, Rc<SF>, SF::AdditionalArguments, Rc<SU>, Option<ConnectionPermit<SD>>)) -> Result<(), EventPollRegistrationError>
 	{
 		let start_arguments =
 		(
@@ -51,6 +52,7 @@ impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>,
 			server_stream_factory,
 			additional_arguments,
 			stream_user,
+			connection_permit,
 		);
 
 		use self::StartOutcome::*;
@@ -64,7 +66,7 @@ impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>,
 			WouldLikeToResume((), started_coroutine) => started_coroutine,
 		};
 
-		event_poll_register.register::<A, SSR, _>(arena, reactor_compressed_type_identifier, streaming_socket_file_descriptor, EPollAddFlags::Streaming, |uninitialized_reactor, streaming_socket_file_descriptor|
+		event_poll_register.register::<A, SSR, _>(arena, reactor_compressed_type_identifier, streaming_socket_file_descriptor, EPollAddFlags::Streaming, None, |uninitialized_reactor, streaming_socket_file_descriptor|
 		{
 			forget(streaming_socket_file_descriptor);
 
@@ -106,14 +108,8 @@ impl<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>,
 		{
 			match self.started_coroutine.resume(RemotePeerClosedCleanly)
 			{
-				WouldLikeToResume(_yields @ ()) => if cfg!(debug_assertions)
-				{
-					panic!("Should have terminated")
-				}
-				else
-				{
-					unreachable!()
-				},
+				// The remote peer has only half-closed, and can still receive (eg a `StreamRelay` relaying bytes to it).
+				WouldLikeToResume(_yields @ ()) => Ok(false),
 
 				Complete(_complete) => Ok(true),
 			}
@@ -3,7 +3,9 @@
 
 
 /// A trait implemented by streaming socket reactors.
-pub trait StreamingSocketReactor<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: SocketData>: Reactor<FileDescriptor=StreamingSocketFileDescriptor<SD>, RegistrationData=(StreamingSocketFileDescriptor<SD>, Rc<SF>, SF::AdditionalArguments, Rc<SU>)>
+///
+/// The last element of the registration data is a listener's `ConnectionPermit`, if any, which is held for as long as the connection is open; outbound connections have none.
+pub trait StreamingSocketReactor<SF: StreamFactory<SD>, SU: StreamUser<SF::S, SF::ProxyOrTunnelInformation>, SD: RemotePeerConnectionKey>: Reactor<FileDescriptor=StreamingSocketFileDescriptor<SD>, RegistrationData=(StreamingSocketFileDescriptor<SD>, Rc<SF>, SF::AdditionalArguments, Rc<SU>, Option<ConnectionPermit<SD>>)>
 {
 	#[doc(hidden)]
 	fn initialize(&mut self, common: StreamingSocketCommon<SF, SU, SD>);
@@ -16,6 +16,9 @@ pub enum TlsInputOutputError
 
 	/// When reading from a socket, a CloseNotify fatal alart was received.
 	BufferReadCloseNotifyAlertReceived,
+
+	/// The encryption and decryption of records was required to be offloaded to the kernel (kTLS) but could not be, eg because TLS 1.3 was not negotiated or the kernel does not support the negotiated cipher suite.
+	KernelOffloadImpossible,
 }
 
 impl Display for TlsInputOutputError
@@ -41,6 +44,8 @@ impl error::Error for TlsInputOutputError
 			&EndOfFileWhilstHandshaking => None,
 
 			&BufferReadCloseNotifyAlertReceived => None,
+
+			&KernelOffloadImpossible => None,
 		}
 	}
 }
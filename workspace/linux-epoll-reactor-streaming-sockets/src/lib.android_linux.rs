@@ -3,13 +3,22 @@
 
 
 extern crate arrayvec;
+#[cfg(test)] extern crate context;
 extern crate context_coroutine;
 extern crate hashbrown;
 #[macro_use] extern crate likely;
 extern crate linux_epoll;
+extern crate linux_epoll_protocols_dns;
+extern crate linux_epoll_reactor_streaming_server_listener_sockets;
+extern crate ring;
+extern crate rustls;
 extern crate rustls_extra;
+#[macro_use] extern crate serde_derive;
+extern crate untrusted;
+extern crate webpki;
 
 
+use self::relay::*;
 use self::stream_factories::*;
 use self::streams::*;
 use ::arrayvec::ArrayVec;
@@ -24,10 +33,101 @@ use ::linux_epoll::file_descriptors::socket::*;
 use ::linux_epoll::file_descriptors::socket::syscall::sockaddr_in;
 use ::linux_epoll::file_descriptors::socket::syscall::sockaddr_in6;
 use ::linux_epoll::file_descriptors::socket::syscall::sockaddr_un;
+use ::linux_epoll::libc::AF_INET;
+use ::linux_epoll::libc::AF_INET6;
+use ::linux_epoll::libc::AF_UNIX;
+use ::linux_epoll::libc::bind;
+use ::linux_epoll::libc::c_int;
+use ::linux_epoll::libc::c_void;
+use ::linux_epoll::libc::close;
+use ::linux_epoll::libc::CMSG_DATA;
+use ::linux_epoll::libc::CMSG_FIRSTHDR;
+use ::linux_epoll::libc::CMSG_LEN;
+use ::linux_epoll::libc::CMSG_NXTHDR;
+use ::linux_epoll::libc::CMSG_SPACE;
+use ::linux_epoll::libc::connect;
+use ::linux_epoll::libc::EINPROGRESS;
+use ::linux_epoll::libc::getgid;
+use ::linux_epoll::libc::getpid;
+use ::linux_epoll::libc::getuid;
+use ::linux_epoll::libc::gid_t;
+use ::linux_epoll::libc::iovec;
+use ::linux_epoll::libc::IPPROTO_TCP;
+use ::linux_epoll::libc::linger;
+use ::linux_epoll::libc::MSG_CMSG_CLOEXEC;
+use ::linux_epoll::libc::MSG_CTRUNC;
+use ::linux_epoll::libc::MSG_NOSIGNAL;
+use ::linux_epoll::libc::MSG_TRUNC;
+use ::linux_epoll::libc::msghdr;
+use ::linux_epoll::libc::off_t;
+use ::linux_epoll::libc::pid_t;
+use ::linux_epoll::libc::recvmsg;
+use ::linux_epoll::libc::sa_family_t;
+use ::linux_epoll::libc::SCM_CREDENTIALS;
+use ::linux_epoll::libc::SCM_RIGHTS;
+use ::linux_epoll::libc::sendmsg;
+use ::linux_epoll::libc::setsockopt;
+use ::linux_epoll::libc::SO_KEEPALIVE;
+use ::linux_epoll::libc::SO_LINGER;
+use ::linux_epoll::libc::SO_PASSCRED;
+use ::linux_epoll::libc::SO_RCVBUF;
+use ::linux_epoll::libc::SO_SNDBUF;
+use ::linux_epoll::libc::SOCK_CLOEXEC;
+use ::linux_epoll::libc::SOCK_DGRAM;
+use ::linux_epoll::libc::SOCK_NONBLOCK;
+use ::linux_epoll::libc::SOCK_SEQPACKET;
+use ::linux_epoll::libc::SOCK_STREAM;
+use ::linux_epoll::libc::sockaddr;
+use ::linux_epoll::libc::sockaddr_in as libc_sockaddr_in;
+use ::linux_epoll::libc::sockaddr_in6 as libc_sockaddr_in6;
+use ::linux_epoll::libc::sockaddr_un as libc_sockaddr_un;
+use ::linux_epoll::libc::socket;
+use ::linux_epoll::libc::socketpair;
+use ::linux_epoll::libc::socklen_t;
+use ::linux_epoll::libc::SOL_SOCKET;
+use ::linux_epoll::libc::TCP_KEEPCNT;
+use ::linux_epoll::libc::TCP_KEEPIDLE;
+use ::linux_epoll::libc::TCP_KEEPINTVL;
+use ::linux_epoll::libc::TCP_LINGER2;
+use ::linux_epoll::libc::TCP_SYNCNT;
+use ::linux_epoll::libc::ucred;
+use ::linux_epoll::libc::uid_t;
 use ::linux_epoll::reactor::*;
+use ::linux_epoll_protocols_dns::resource_data::dns_based_authentication_of_named_entities::CertificateUsage;
+use ::linux_epoll_protocols_dns::resource_data::dns_based_authentication_of_named_entities::DnsBasedAuthenticationOfNamedEntities;
+use ::linux_epoll_protocols_dns::resource_data::dns_based_authentication_of_named_entities::MatchingType;
+use ::linux_epoll_protocols_dns::resource_data::dns_based_authentication_of_named_entities::Selector;
+use ::linux_epoll_reactor_streaming_server_listener_sockets::accept_limits::ConnectionPermit;
+use ::linux_epoll_reactor_streaming_server_listener_sockets::accept_limits::RemotePeerConnectionKey;
+use ::ring::aead::Aad;
+use ::ring::aead::CHACHA20_POLY1305;
+use ::ring::aead::Nonce;
+use ::ring::aead::OpeningKey;
+use ::ring::aead::SealingKey;
+use ::ring::aead::open_in_place;
+use ::ring::aead::seal_in_place;
+use ::ring::error::Unspecified;
+use ::ring::rand::SecureRandom;
+use ::ring::rand::SystemRandom;
+use ::rustls::BulkAlgorithm;
+use ::rustls::KeyLog;
+use ::rustls::ProducesTickets;
+use ::rustls::ProtocolVersion;
+use ::rustls::ServerCertVerified;
+use ::rustls::ServerCertVerifier;
+use ::rustls::StoresClientSessions;
+use ::rustls::SupportedCipherSuite;
 use ::rustls_extra::*;
 pub use ::rustls_extra::supported_cipher_suites;
+use ::std::borrow::Cow;
+use ::std::cell::Cell;
+use ::std::cell::RefCell;
+use ::std::cmp::max;
+use ::std::cmp::min;
+use ::std::collections::VecDeque;
+use ::std::convert::TryFrom;
 use ::std::error;
+use ::std::ffi::OsStr;
 use ::std::fmt;
 use ::std::fmt::Debug;
 use ::std::fmt::Display;
@@ -36,20 +136,58 @@ use ::std::mem::transmute;
 use ::std::io;
 use ::std::io::ErrorKind;
 use ::std::io::Initializer;
+use ::std::io::IoSlice;
+use ::std::io::IoSliceMut;
 use ::std::io::Read;
 use ::std::io::Write;
+use ::std::marker::PhantomData;
 use ::std::mem::forget;
 use ::std::mem::transmute_copy;
+use ::std::mem::size_of;
 use ::std::mem::uninitialized;
+use ::std::mem::zeroed;
 use ::std::net::IpAddr;
 use ::std::net::Ipv4Addr;
 use ::std::net::Ipv6Addr;
 use ::std::net::SocketAddrV4;
+use ::std::net::SocketAddrV6;
 use ::std::ops::Deref;
+use ::std::os::unix::ffi::OsStrExt;
+use ::std::os::unix::io::AsRawFd;
+use ::std::os::unix::io::FromRawFd;
+use ::std::os::unix::io::IntoRawFd;
+use ::std::os::unix::io::RawFd;
+use ::std::path::Path;
+use ::std::path::PathBuf;
+use ::std::ptr::copy;
 use ::std::ptr::copy_nonoverlapping;
+use ::std::ptr::null_mut;
+use ::std::ptr::read_unaligned;
 use ::std::ptr::write;
+use ::std::ptr::write_unaligned;
 use ::std::rc::Rc;
+use ::std::slice::from_raw_parts;
+use ::std::str::FromStr;
+use ::std::str::from_utf8;
 use ::std::sync::Arc;
+use ::std::sync::Mutex;
+use ::std::sync::RwLock;
+use ::std::sync::atomic::AtomicUsize;
+use ::std::sync::atomic::Ordering;
+use ::std::time::Duration;
+use ::std::time::Instant;
+use ::std::time::SystemTime;
+use ::untrusted::Input;
+use ::webpki::DNSNameRef;
+use ::webpki::EndEntityCert;
+use ::webpki::SignatureAlgorithm;
+use ::webpki::TLSServerTrustAnchors;
+use ::webpki::Time;
+use ::webpki::TrustAnchor;
+
+
+/// Relaying between two streams, for building proxies.
+pub mod relay;
 
 
 /// Stream factories.
@@ -57,13 +195,18 @@ pub mod stream_factories;
 
 
 /// Streams.
-pub mod streams;
+#[macro_use] pub mod streams;
+
+
+/// Message sockets for Unix domain `SOCK_SEQPACKET` and `SOCK_DGRAM` sockets, with `SCM_RIGHTS` and `SCM_CREDENTIALS` ancillary data.
+pub mod message_sockets;
 
 
 include!("streaming_socket_reactor.rs");
 
 
 include!("CompleteError.rs");
+include!("OutboundStreamingSocketSettings.rs");
 include!("ReactEdgeTriggeredStatus.rs");
 include!("StreamingSocketCommon.rs");
 include!("StreamingSocketInternetProtocolVersion4Reactor.rs");
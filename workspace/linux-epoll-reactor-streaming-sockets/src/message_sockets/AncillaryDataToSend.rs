@@ -0,0 +1,66 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Ancillary data to send with a message.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct AncillaryDataToSend<'a>
+{
+	/// File descriptors to send as `SCM_RIGHTS` ancillary data; at most `ReceivedAncillaryData::MaximumFileDescriptors`.
+	pub file_descriptors: &'a [RawFd],
+
+	/// Credentials to send as `SCM_CREDENTIALS` ancillary data.
+	pub credentials: Option<MessageCredentials>,
+}
+
+impl<'a> AncillaryDataToSend<'a>
+{
+	#[inline(always)]
+	pub(crate) fn is_empty(&self) -> bool
+	{
+		self.file_descriptors.is_empty() && self.credentials.is_none()
+	}
+
+	/// Control buffer is of `usize` to ensure `cmsghdr` alignment.
+	#[inline(always)]
+	pub(crate) fn control_buffer(&self) -> Result<Vec<usize>, CompleteError>
+	{
+		let number_of_file_descriptors = self.file_descriptors.len();
+		if unlikely!(number_of_file_descriptors > ReceivedAncillaryData::MaximumFileDescriptors)
+		{
+			return Err(CompleteError::InvalidDataSupplied(format!("Can not send more than {} file descriptors in one message", ReceivedAncillaryData::MaximumFileDescriptors)))
+		}
+
+		let mut control_buffer = ReceivedAncillaryData::new_control_buffer(if number_of_file_descriptors == 0 { None } else { Some(number_of_file_descriptors) }, self.credentials.is_some());
+
+		let mut message_header: msghdr = unsafe { zeroed() };
+		message_header.msg_control = control_buffer.as_mut_ptr() as *mut c_void;
+		message_header.msg_controllen = control_buffer.len() * size_of::<usize>();
+
+		unsafe
+		{
+			let mut control_message_header = CMSG_FIRSTHDR(&message_header);
+
+			if number_of_file_descriptors != 0
+			{
+				let data_length = number_of_file_descriptors * size_of::<RawFd>();
+				(*control_message_header).cmsg_level = SOL_SOCKET;
+				(*control_message_header).cmsg_type = SCM_RIGHTS;
+				(*control_message_header).cmsg_len = CMSG_LEN(data_length as u32) as usize;
+				copy_nonoverlapping(self.file_descriptors.as_ptr() as *const u8, CMSG_DATA(control_message_header), data_length);
+
+				control_message_header = CMSG_NXTHDR(&message_header, control_message_header);
+			}
+
+			if let Some(credentials) = self.credentials
+			{
+				(*control_message_header).cmsg_level = SOL_SOCKET;
+				(*control_message_header).cmsg_type = SCM_CREDENTIALS;
+				(*control_message_header).cmsg_len = CMSG_LEN(size_of::<ucred>() as u32) as usize;
+				write_unaligned(CMSG_DATA(control_message_header) as *mut ucred, credentials.to_ucred());
+			}
+		}
+
+		Ok(control_buffer)
+	}
+}
@@ -0,0 +1,59 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Process credentials sent or received as `SCM_CREDENTIALS` ancillary data.
+///
+/// The kernel checks credentials sent; unprivileged processes can only send their own process identifier, user identifiers and group identifiers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MessageCredentials
+{
+	/// Process identifier.
+	pub process_identifier: pid_t,
+
+	/// User identifier.
+	pub user_identifier: uid_t,
+
+	/// Group identifier.
+	pub group_identifier: gid_t,
+}
+
+impl MessageCredentials
+{
+	/// Credentials of the current process.
+	#[inline(always)]
+	pub fn current_process() -> Self
+	{
+		unsafe
+		{
+			Self
+			{
+				process_identifier: getpid(),
+				user_identifier: getuid(),
+				group_identifier: getgid(),
+			}
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn from_ucred(credentials: ucred) -> Self
+	{
+		Self
+		{
+			process_identifier: credentials.pid,
+			user_identifier: credentials.uid,
+			group_identifier: credentials.gid,
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn to_ucred(self) -> ucred
+	{
+		ucred
+		{
+			pid: self.process_identifier,
+			uid: self.user_identifier,
+			gid: self.group_identifier,
+		}
+	}
+}
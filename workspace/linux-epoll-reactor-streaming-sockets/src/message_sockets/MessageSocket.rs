@@ -0,0 +1,153 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A message-oriented socket, analogous to `Stream`, for Unix domain `SOCK_SEQPACKET` and `SOCK_DGRAM` sockets.
+///
+/// Message boundaries are preserved; each call to `receive_message()` receives exactly one message and each call to `send_message()` sends exactly one message.
+#[derive(Debug)]
+pub struct MessageSocket<'yielder>
+{
+	message_socket_file_descriptor: MessageSocketFileDescriptor,
+	input_output_yielder: InputOutputYielder<'yielder>,
+	byte_counter: ByteCounter,
+}
+
+impl<'yielder> MessageSocket<'yielder>
+{
+	#[inline(always)]
+	pub(crate) fn new(message_socket_file_descriptor: MessageSocketFileDescriptor, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>) -> Self
+	{
+		Self
+		{
+			message_socket_file_descriptor,
+			input_output_yielder: InputOutputYielder::new(yielder),
+			byte_counter: ByteCounter::default(),
+		}
+	}
+
+	/// Underlying file descriptor, eg to change socket options.
+	#[inline(always)]
+	pub fn message_socket_file_descriptor(&self) -> &MessageSocketFileDescriptor
+	{
+		&self.message_socket_file_descriptor
+	}
+
+	/// Receive one message, waiting until one is available.
+	///
+	/// A message longer than `receive_into_buffer` is truncated; check `ReceivedMessage.message_truncated`.
+	#[inline(always)]
+	pub fn receive_message(&mut self, receive_into_buffer: &mut [u8], received_ancillary_data: &mut ReceivedAncillaryData) -> Result<ReceivedMessage, CompleteError>
+	{
+		loop
+		{
+			let mut remote_peer_address: libc_sockaddr_un = unsafe { zeroed() };
+			let mut buffer = iovec
+			{
+				iov_base: receive_into_buffer.as_mut_ptr() as *mut c_void,
+				iov_len: receive_into_buffer.len(),
+			};
+
+			let mut message_header: msghdr = unsafe { zeroed() };
+			message_header.msg_name = &mut remote_peer_address as *mut libc_sockaddr_un as *mut c_void;
+			message_header.msg_namelen = size_of::<libc_sockaddr_un>() as socklen_t;
+			message_header.msg_iov = &mut buffer;
+			message_header.msg_iovlen = 1;
+			received_ancillary_data.prepare(&mut message_header);
+
+			let result = unsafe { recvmsg(self.message_socket_file_descriptor.as_raw_fd(), &mut message_header, MSG_CMSG_CLOEXEC) };
+
+			let bytes_received = if likely!(result >= 0)
+			{
+				result as usize
+			}
+			else
+			{
+				let io_error = io::Error::last_os_error();
+				read_loop_or_await_or_error!(io_error, &mut self.input_output_yielder, SocketRead)
+			};
+
+			received_ancillary_data.parse(&message_header);
+			self.byte_counter.bytes_read(bytes_received);
+
+			return Ok
+			(
+				ReceivedMessage
+				{
+					length: bytes_received,
+					message_truncated: message_header.msg_flags & MSG_TRUNC != 0,
+					ancillary_data_truncated: message_header.msg_flags & MSG_CTRUNC != 0,
+					remote_peer_address: UnixDomainMessageSocketAddress::from_sockaddr_un(&remote_peer_address, message_header.msg_namelen),
+				}
+			)
+		}
+	}
+
+	/// Send one message to the connected remote peer, waiting until there is space to do so.
+	#[inline(always)]
+	pub fn send_message(&mut self, message: &[u8], ancillary_data_to_send: &AncillaryDataToSend) -> Result<usize, CompleteError>
+	{
+		self.send_message_internal(message, ancillary_data_to_send, None)
+	}
+
+	/// Send one message to `remote_peer_address`, waiting until there is space to do so.
+	///
+	/// Only meaningful for unconnected `SOCK_DGRAM` sockets.
+	#[inline(always)]
+	pub fn send_message_to(&mut self, message: &[u8], ancillary_data_to_send: &AncillaryDataToSend, remote_peer_address: &UnixDomainMessageSocketAddress) -> Result<usize, CompleteError>
+	{
+		let remote_peer_address = remote_peer_address.to_sockaddr_un().map_err(|io_error| CompleteError::InvalidDataSupplied(io_error.to_string()))?;
+		self.send_message_internal(message, ancillary_data_to_send, Some(remote_peer_address))
+	}
+
+	#[inline(always)]
+	fn send_message_internal(&mut self, message: &[u8], ancillary_data_to_send: &AncillaryDataToSend, remote_peer_address: Option<(libc_sockaddr_un, socklen_t)>) -> Result<usize, CompleteError>
+	{
+		let mut control_buffer = if ancillary_data_to_send.is_empty()
+		{
+			None
+		}
+		else
+		{
+			Some(ancillary_data_to_send.control_buffer()?)
+		};
+
+		loop
+		{
+			let mut buffer = iovec
+			{
+				iov_base: message.as_ptr() as *mut c_void,
+				iov_len: message.len(),
+			};
+
+			let mut message_header: msghdr = unsafe { zeroed() };
+			if let Some((ref remote_peer_address, remote_peer_address_length)) = remote_peer_address
+			{
+				message_header.msg_name = remote_peer_address as *const libc_sockaddr_un as *mut c_void;
+				message_header.msg_namelen = remote_peer_address_length;
+			}
+			message_header.msg_iov = &mut buffer;
+			message_header.msg_iovlen = 1;
+			if let Some(ref mut control_buffer) = control_buffer
+			{
+				message_header.msg_control = control_buffer.as_mut_ptr() as *mut c_void;
+				message_header.msg_controllen = control_buffer.len() * size_of::<usize>();
+			}
+
+			let result = unsafe { sendmsg(self.message_socket_file_descriptor.as_raw_fd(), &message_header, MSG_NOSIGNAL) };
+
+			let bytes_sent = if likely!(result >= 0)
+			{
+				result as usize
+			}
+			else
+			{
+				let io_error = io::Error::last_os_error();
+				write_loop_or_await_or_error!(io_error, &mut self.input_output_yielder, SocketWrite)
+			};
+
+			self.byte_counter.bytes_written(bytes_sent);
+			return Ok(bytes_sent)
+		}
+	}
+}
@@ -0,0 +1,170 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A non-blocking, close-on-exec file descriptor for an Unix domain `SOCK_SEQPACKET` or `SOCK_DGRAM` socket.
+///
+/// `SOCK_SEQPACKET` sockets are accepted by a listener in the same way as `SOCK_STREAM` sockets; use `accepted_sequential_packet()` to convert them.
+#[derive(Debug)]
+pub struct MessageSocketFileDescriptor(RawFd);
+
+impl Drop for MessageSocketFileDescriptor
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		unsafe { close(self.0) };
+	}
+}
+
+impl AsRawFd for MessageSocketFileDescriptor
+{
+	#[inline(always)]
+	fn as_raw_fd(&self) -> RawFd
+	{
+		self.0
+	}
+}
+
+impl IntoRawFd for MessageSocketFileDescriptor
+{
+	#[inline(always)]
+	fn into_raw_fd(self) -> RawFd
+	{
+		let raw_file_descriptor = self.0;
+		forget(self);
+		raw_file_descriptor
+	}
+}
+
+impl FromRawFd for MessageSocketFileDescriptor
+{
+	#[inline(always)]
+	unsafe fn from_raw_fd(raw_file_descriptor: RawFd) -> Self
+	{
+		MessageSocketFileDescriptor(raw_file_descriptor)
+	}
+}
+
+impl MessageSocketFileDescriptor
+{
+	/// Converts a `SOCK_SEQPACKET` socket accepted by a listener.
+	#[inline(always)]
+	pub fn accepted_sequential_packet(streaming_socket_file_descriptor: StreamingSocketFileDescriptor<sockaddr_un>) -> Self
+	{
+		let raw_file_descriptor = streaming_socket_file_descriptor.as_raw_fd();
+
+		// Otherwise the socket would be shut down.
+		forget(streaming_socket_file_descriptor);
+
+		MessageSocketFileDescriptor(raw_file_descriptor)
+	}
+
+	/// A new `SOCK_SEQPACKET` socket connected to `remote_peer_address`.
+	#[inline(always)]
+	pub fn new_sequential_packet_connected_to(remote_peer_address: &UnixDomainMessageSocketAddress) -> io::Result<Self>
+	{
+		let this = Self::new(MessageSocketType::SequentialPacket)?;
+		this.connect(remote_peer_address)?;
+		Ok(this)
+	}
+
+	/// A new `SOCK_DGRAM` socket bound to `local_address`.
+	///
+	/// Use `UnixDomainMessageSocketAddress::Unnamed` to autobind, so that remote peers can reply.
+	#[inline(always)]
+	pub fn new_datagram(local_address: &UnixDomainMessageSocketAddress) -> io::Result<Self>
+	{
+		let this = Self::new(MessageSocketType::Datagram)?;
+		this.bind(local_address)?;
+		Ok(this)
+	}
+
+	/// A new `SOCK_DGRAM` socket bound to `local_address` which only sends to and receives from `remote_peer_address`.
+	#[inline(always)]
+	pub fn new_datagram_connected_to(local_address: &UnixDomainMessageSocketAddress, remote_peer_address: &UnixDomainMessageSocketAddress) -> io::Result<Self>
+	{
+		let this = Self::new_datagram(local_address)?;
+		this.connect(remote_peer_address)?;
+		Ok(this)
+	}
+
+	/// A new pair of connected sockets, eg for communicating with a child process.
+	#[inline(always)]
+	pub fn new_pair(message_socket_type: MessageSocketType) -> io::Result<(Self, Self)>
+	{
+		let mut raw_file_descriptors: [RawFd; 2] = [-1, -1];
+		let result = unsafe { socketpair(AF_UNIX, message_socket_type.socket_type() | SOCK_NONBLOCK | SOCK_CLOEXEC, 0, raw_file_descriptors.as_mut_ptr()) };
+		if likely!(result == 0)
+		{
+			Ok((MessageSocketFileDescriptor(raw_file_descriptors[0]), MessageSocketFileDescriptor(raw_file_descriptors[1])))
+		}
+		else
+		{
+			Err(io::Error::last_os_error())
+		}
+	}
+
+	/// Enables receiving of `SCM_CREDENTIALS` ancillary data with every message (`SO_PASSCRED`).
+	///
+	/// For `SOCK_DGRAM` sockets, this also causes unbound sockets to be autobound on `send_message()`.
+	#[inline(always)]
+	pub fn enable_receiving_credentials(&self) -> io::Result<()>
+	{
+		let enable: c_int = 1;
+		let result = unsafe { setsockopt(self.0, SOL_SOCKET, SO_PASSCRED, &enable as *const c_int as *const c_void, size_of::<c_int>() as socklen_t) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(io::Error::last_os_error())
+		}
+	}
+
+	#[inline(always)]
+	fn new(message_socket_type: MessageSocketType) -> io::Result<Self>
+	{
+		let raw_file_descriptor = unsafe { socket(AF_UNIX, message_socket_type.socket_type() | SOCK_NONBLOCK | SOCK_CLOEXEC, 0) };
+		if likely!(raw_file_descriptor >= 0)
+		{
+			Ok(MessageSocketFileDescriptor(raw_file_descriptor))
+		}
+		else
+		{
+			Err(io::Error::last_os_error())
+		}
+	}
+
+	#[inline(always)]
+	fn bind(&self, local_address: &UnixDomainMessageSocketAddress) -> io::Result<()>
+	{
+		let (socket_address, socket_address_length) = local_address.to_sockaddr_un()?;
+		let result = unsafe { bind(self.0, &socket_address as *const libc_sockaddr_un as *const sockaddr, socket_address_length) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(io::Error::last_os_error())
+		}
+	}
+
+	/// Connecting a non-blocking Unix domain socket either succeeds immediately or fails with `EAGAIN` if the listener's back log is full.
+	#[inline(always)]
+	fn connect(&self, remote_peer_address: &UnixDomainMessageSocketAddress) -> io::Result<()>
+	{
+		let (socket_address, socket_address_length) = remote_peer_address.to_sockaddr_un()?;
+		let result = unsafe { connect(self.0, &socket_address as *const libc_sockaddr_un as *const sockaddr, socket_address_length) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(io::Error::last_os_error())
+		}
+	}
+}
@@ -0,0 +1,30 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Type of message socket.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessageSocketType
+{
+	/// `SOCK_SEQPACKET`: connection-oriented, reliable and preserves message boundaries.
+	SequentialPacket,
+
+	/// `SOCK_DGRAM`: connection-less and preserves message boundaries; for Unix domain sockets, reliable.
+	Datagram,
+}
+
+impl MessageSocketType
+{
+	#[inline(always)]
+	pub(crate) fn socket_type(self) -> c_int
+	{
+		use self::MessageSocketType::*;
+
+		match self
+		{
+			SequentialPacket => SOCK_SEQPACKET,
+
+			Datagram => SOCK_DGRAM,
+		}
+	}
+}
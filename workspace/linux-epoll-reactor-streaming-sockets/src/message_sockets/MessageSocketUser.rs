@@ -0,0 +1,10 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Use a message socket.
+pub trait MessageSocketUser
+{
+	/// Use message socket as if it was blocking.
+	fn use_message_socket<'yielder>(&self, message_socket: MessageSocket<'yielder>) -> Result<(), CompleteError>;
+}
@@ -0,0 +1,130 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Ancillary data received with a message.
+///
+/// Create once and re-use for each call to `MessageSocket.receive_message()`; any file descriptors and credentials from the previous message are discarded.
+pub struct ReceivedAncillaryData
+{
+	control_buffer: Vec<usize>,
+
+	/// File descriptors received as `SCM_RIGHTS` ancillary data.
+	pub file_descriptors: Vec<ReceivedFileDescriptor>,
+
+	/// Credentials received as `SCM_CREDENTIALS` ancillary data.
+	///
+	/// Only received if `MessageSocketFileDescriptor.enable_receiving_credentials()` was used.
+	pub credentials: Option<MessageCredentials>,
+}
+
+impl Debug for ReceivedAncillaryData
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "ReceivedAncillaryData {{ control_buffer: _, file_descriptors: {:?}, credentials: {:?} }}", self.file_descriptors, self.credentials)
+	}
+}
+
+impl Default for ReceivedAncillaryData
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self::new(Self::DefaultMaximumFileDescriptors)
+	}
+}
+
+impl ReceivedAncillaryData
+{
+	/// Maximum number of file descriptors that can be sent in one message (`SCM_MAX_FD`).
+	pub const MaximumFileDescriptors: usize = 253;
+
+	/// Default maximum number of file descriptors to receive in one message.
+	pub const DefaultMaximumFileDescriptors: usize = 16;
+
+	/// New instance.
+	///
+	/// File descriptors sent in excess of `maximum_file_descriptors` are closed by the kernel and `ReceivedMessage.ancillary_data_truncated` is set.
+	#[inline(always)]
+	pub fn new(maximum_file_descriptors: usize) -> Self
+	{
+		let maximum_file_descriptors = min(maximum_file_descriptors, Self::MaximumFileDescriptors);
+
+		Self
+		{
+			control_buffer: Self::new_control_buffer(if maximum_file_descriptors == 0 { None } else { Some(maximum_file_descriptors) }, true),
+			file_descriptors: Vec::with_capacity(maximum_file_descriptors),
+			credentials: None,
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn new_control_buffer(number_of_file_descriptors: Option<usize>, credentials: bool) -> Vec<usize>
+	{
+		let mut control_buffer_length_in_bytes = 0;
+		if let Some(number_of_file_descriptors) = number_of_file_descriptors
+		{
+			control_buffer_length_in_bytes += unsafe { CMSG_SPACE((number_of_file_descriptors * size_of::<RawFd>()) as u32) } as usize;
+		}
+		if credentials
+		{
+			control_buffer_length_in_bytes += unsafe { CMSG_SPACE(size_of::<ucred>() as u32) } as usize;
+		}
+
+		let control_buffer_length = (control_buffer_length_in_bytes + size_of::<usize>() - 1) / size_of::<usize>();
+		vec![0; control_buffer_length]
+	}
+
+	#[inline(always)]
+	pub(crate) fn prepare(&mut self, message_header: &mut msghdr)
+	{
+		self.file_descriptors.clear();
+		self.credentials = None;
+
+		message_header.msg_control = self.control_buffer.as_mut_ptr() as *mut c_void;
+		message_header.msg_controllen = self.control_buffer.len() * size_of::<usize>();
+	}
+
+	#[inline(always)]
+	pub(crate) fn parse(&mut self, message_header: &msghdr)
+	{
+		unsafe
+		{
+			let mut control_message_header = CMSG_FIRSTHDR(message_header);
+			while !control_message_header.is_null()
+			{
+				let cmsg_level = (*control_message_header).cmsg_level;
+				let cmsg_type = (*control_message_header).cmsg_type;
+				let data = CMSG_DATA(control_message_header);
+				let data_length = ((*control_message_header).cmsg_len as usize).saturating_sub(CMSG_LEN(0) as usize);
+
+				if cmsg_level == SOL_SOCKET
+				{
+					match cmsg_type
+					{
+						SCM_RIGHTS =>
+						{
+							let number_of_file_descriptors = data_length / size_of::<RawFd>();
+							for index in 0 .. number_of_file_descriptors
+							{
+								let raw_file_descriptor = read_unaligned((data as *const RawFd).add(index));
+								self.file_descriptors.push(ReceivedFileDescriptor(raw_file_descriptor));
+							}
+						}
+
+						SCM_CREDENTIALS => if likely!(data_length >= size_of::<ucred>())
+						{
+							self.credentials = Some(MessageCredentials::from_ucred(read_unaligned(data as *const ucred)));
+						}
+
+						_ => (),
+					}
+				}
+
+				control_message_header = CMSG_NXTHDR(message_header, control_message_header);
+			}
+		}
+	}
+}
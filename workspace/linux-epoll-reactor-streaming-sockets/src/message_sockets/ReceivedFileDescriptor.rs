@@ -0,0 +1,38 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A file descriptor received as `SCM_RIGHTS` ancillary data.
+///
+/// Close-on-exec is set; the file descriptor is closed when dropped unless `into_raw_fd()` is used.
+#[derive(Debug)]
+pub struct ReceivedFileDescriptor(RawFd);
+
+impl Drop for ReceivedFileDescriptor
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		unsafe { close(self.0) };
+	}
+}
+
+impl AsRawFd for ReceivedFileDescriptor
+{
+	#[inline(always)]
+	fn as_raw_fd(&self) -> RawFd
+	{
+		self.0
+	}
+}
+
+impl IntoRawFd for ReceivedFileDescriptor
+{
+	#[inline(always)]
+	fn into_raw_fd(self) -> RawFd
+	{
+		let raw_file_descriptor = self.0;
+		forget(self);
+		raw_file_descriptor
+	}
+}
@@ -0,0 +1,24 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Details of a received message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedMessage
+{
+	/// Number of bytes received into the buffer.
+	///
+	/// For `SOCK_SEQPACKET` sockets, zero may also indicate that the remote peer has shut down.
+	pub length: usize,
+
+	/// The message was longer than the buffer and the excess was discarded (`MSG_TRUNC`).
+	pub message_truncated: bool,
+
+	/// Some ancillary data was discarded (`MSG_CTRUNC`), eg because more file descriptors were sent than could be received.
+	pub ancillary_data_truncated: bool,
+
+	/// Address of the remote peer that sent the message.
+	///
+	/// Only meaningful for `SOCK_DGRAM` sockets.
+	pub remote_peer_address: UnixDomainMessageSocketAddress,
+}
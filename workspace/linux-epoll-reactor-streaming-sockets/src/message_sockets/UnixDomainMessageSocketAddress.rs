@@ -0,0 +1,66 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// An Unix domain socket address for a message socket.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UnixDomainMessageSocketAddress
+{
+	/// A socket file in a file system; at most 107 bytes.
+	Path(PathBuf),
+
+	/// A Linux abstract namespace address; at most 107 bytes and without the leading ASCII NUL.
+	Abstract(Vec<u8>),
+
+	/// When binding, the kernel chooses a unique abstract namespace address (autobind).
+	///
+	/// When receiving, the remote peer is not bound to an address.
+	Unnamed,
+}
+
+impl UnixDomainMessageSocketAddress
+{
+	#[inline(always)]
+	pub(crate) fn to_sockaddr_un(&self) -> io::Result<(libc_sockaddr_un, socklen_t)>
+	{
+		use self::UnixDomainMessageSocketAddress::*;
+
+		let raw_address = match self
+		{
+			&Path(ref path) => UnixDomainSocketRawAddress::Path(path),
+
+			&Abstract(ref abstract_name) => UnixDomainSocketRawAddress::Abstract(&abstract_name[..]),
+
+			&Unnamed => UnixDomainSocketRawAddress::Unnamed,
+		};
+
+		raw_address.to_sockaddr_un()
+	}
+
+	#[inline(always)]
+	pub(crate) fn from_sockaddr_un(socket_address: &libc_sockaddr_un, socket_address_length: socklen_t) -> Self
+	{
+		use self::UnixDomainMessageSocketAddress::*;
+
+		let path_length = (socket_address_length as usize).saturating_sub(size_of::<sa_family_t>()).min(socket_address.sun_path.len());
+		let sun_path: &[u8] = unsafe { from_raw_parts(socket_address.sun_path.as_ptr() as *const u8, path_length) };
+
+		if path_length == 0
+		{
+			Unnamed
+		}
+		else if sun_path[0] == 0
+		{
+			Abstract(sun_path[1 .. ].to_vec())
+		}
+		else
+		{
+			let without_trailing_nul = match sun_path.iter().position(|byte| *byte == 0)
+			{
+				None => sun_path,
+				Some(index) => &sun_path[.. index],
+			};
+			Path(PathBuf::from(OsStr::from_bytes(without_trailing_nul)))
+		}
+	}
+}
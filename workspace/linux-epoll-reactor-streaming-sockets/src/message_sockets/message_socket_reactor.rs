@@ -0,0 +1,46 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+macro_rules! message_socket_reactor
+{
+	($reactor_name: ident, $socket_type: literal) =>
+	{
+		#[doc = "This object wraps Unix domain message sockets of type `"]
+		#[doc = $socket_type]
+		#[doc = "`."]
+		#[derive(Debug)]
+		pub struct $reactor_name<MSU: MessageSocketUser>
+		{
+			common: MessageSocketCommon<MSU>,
+		}
+
+		impl<MSU: MessageSocketUser> Reactor for $reactor_name<MSU>
+		{
+			type FileDescriptor = MessageSocketFileDescriptor;
+
+			type RegistrationData = (MessageSocketFileDescriptor, Rc<MSU>);
+
+			#[inline(always)]
+			fn do_initial_input_and_output_and_register_with_epoll_if_necesssary<A: Arena<Self>, EPR: EventPollRegister>(event_poll_register: &EPR, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, registration_data: Self::RegistrationData) -> Result<(), EventPollRegistrationError>
+			{
+				MessageSocketCommon::<MSU>::do_initial_input_and_output_and_register_with_epoll_if_necesssary::<A, Self, EPR>(event_poll_register, arena, reactor_compressed_type_identifier, registration_data)
+			}
+
+			#[inline(always)]
+			fn react(&mut self, event_flags: EPollEventFlags, terminate: &impl Terminate) -> Result<bool, String>
+			{
+				self.common.react(event_flags, terminate)
+			}
+		}
+
+		impl<MSU: MessageSocketUser> MessageSocketReactor<MSU> for $reactor_name<MSU>
+		{
+			#[inline(always)]
+			fn initialize(&mut self, common: MessageSocketCommon<MSU>)
+			{
+				unsafe { write(&mut self.common, common) }
+			}
+		}
+	}
+}
@@ -0,0 +1,24 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+use super::*;
+
+
+include!("message_socket_reactor.rs");
+
+
+include!("AncillaryDataToSend.rs");
+include!("DatagramUnixDomainReactor.rs");
+include!("MessageCredentials.rs");
+include!("MessageSocket.rs");
+include!("MessageSocketCommon.rs");
+include!("MessageSocketFileDescriptor.rs");
+include!("MessageSocketReactor.rs");
+include!("MessageSocketType.rs");
+include!("MessageSocketUser.rs");
+include!("ReceivedAncillaryData.rs");
+include!("ReceivedFileDescriptor.rs");
+include!("ReceivedMessage.rs");
+include!("SequentialPacketUnixDomainReactor.rs");
+include!("UnixDomainMessageSocketAddress.rs");
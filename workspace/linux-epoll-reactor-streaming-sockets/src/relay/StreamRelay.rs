@@ -0,0 +1,178 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Relays bytes in both directions between two streams, each owned by its own reactor (coroutine).
+///
+/// Create one `StreamRelay`, share it (using `Rc`) between the two coroutines and call `relay()` from each with its side and its stream; see `relay()` for the safety requirements.
+/// Whichever coroutine is woken pumps both directions, so an edge-triggered event on either socket makes progress.
+///
+/// Each direction is buffered, to a bounded capacity, once both sides have joined.
+/// If both streams are `UnencryptedStream`s the buffer is a pipe and bytes are moved with `splice(2)`, so they are never copied into userspace; otherwise (eg for TLS streams, whose plaintext the kernel does not have access to) the buffer is in userspace.
+///
+/// When one side reaches end-of-file, the other side is shut down for writing (a 'half-close', preceded by a close notify alert for a TLS stream) once all buffered bytes have been relayed to it.
+/// A side whose remote peer has half-closed (`EPOLLRDHUP`) remains until all bytes destined for it have been relayed.
+///
+/// When one side departs (because it has finished or failed), the other side's socket is shut down for reading so that its coroutine is woken to finish, too.
+#[derive(Debug)]
+pub struct StreamRelay
+{
+	left_to_right: RefCell<StreamRelayDirection>,
+	right_to_left: RefCell<StreamRelayDirection>,
+	joined_endpoints: Cell<[Option<StreamRelayJoinedEndpoint>; 2]>,
+	has_gone: Cell<[bool; 2]>,
+}
+
+impl StreamRelay
+{
+	/// Creates a new instance.
+	#[inline(always)]
+	pub fn new(stream_relay_settings: &StreamRelaySettings) -> Rc<Self>
+	{
+		let capacity = stream_relay_settings.pipe_capacity;
+
+		Rc::new
+		(
+			Self
+			{
+				left_to_right: RefCell::new(StreamRelayDirection::new(capacity)),
+				right_to_left: RefCell::new(StreamRelayDirection::new(capacity)),
+				joined_endpoints: Cell::new([None, None]),
+				has_gone: Cell::new([false, false]),
+			}
+		)
+	}
+
+	/// Statistics so far.
+	#[inline(always)]
+	pub fn statistics(&self) -> StreamRelayStatistics
+	{
+		StreamRelayStatistics
+		{
+			left_to_right_bytes: self.left_to_right.borrow().bytes_relayed(),
+			right_to_left_bytes: self.right_to_left.borrow().bytes_relayed(),
+		}
+	}
+
+	/// Relays using `stream` as `side` until both directions have finished, the other side has departed or an error occurs.
+	///
+	/// Call from the coroutine owning `stream`; once this returns the coroutine should finish.
+	/// Each side may only be relayed once.
+	///
+	/// # Safety
+	///
+	/// Whilst this coroutine is suspended inside this method, the other side's coroutine reads from, writes to and, for a TLS stream, changes the session of `stream` through pointers held by this `StreamRelay`.
+	/// Hence:-
+	///
+	/// * both coroutines must run on the same thread;
+	/// * this coroutine's stack must not be freed without being unwound (eg by leaking or forgetting the coroutine) whilst suspended inside this method, as `stream` would then be used after it has been freed;
+	/// * `stream` must not be moved or used other than by this method until it returns.
+	///
+	/// This side departs (and the other side is woken) when this method returns or is unwound.
+	pub unsafe fn relay<'yielder, S: StreamRelayable<'yielder>>(&self, side: StreamRelaySide, stream: &mut S) -> Result<StreamRelayStatistics, CompleteError>
+	{
+		debug_assert!(!self.has_gone.get()[Self::index(side)], "side {:?} has already been relayed", side);
+
+		let mut stream_relay_endpoint = stream.stream_relay_endpoint();
+
+		self.join(side, stream_relay_endpoint.joined_endpoint());
+		let _stream_relay_departure = StreamRelayDeparture
+		{
+			stream_relay: self,
+			side,
+		};
+		self.relay_until_finished(side, &mut stream_relay_endpoint)
+	}
+
+	#[inline(always)]
+	fn relay_until_finished(&self, side: StreamRelaySide, stream_relay_endpoint: &mut StreamRelayEndpoint) -> Result<StreamRelayStatistics, CompleteError>
+	{
+		loop
+		{
+			self.pump()?;
+
+			if self.is_finished(side)
+			{
+				return Ok(self.statistics())
+			}
+
+			match stream_relay_endpoint.await_further_input_or_output_to_become_available()?
+			{
+				// After a half-close, the remote peer can still receive bytes relayed to it, so carry on until the incoming direction is complete.
+				ReactEdgeTriggeredStatus::InputOrOutputNowAvailable { .. } | ReactEdgeTriggeredStatus::RemotePeerClosedCleanly => continue,
+
+				ReactEdgeTriggeredStatus::ClosedWithError => return Err(CompleteError::ClosedWithError),
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn pump(&self) -> Result<(), CompleteError>
+	{
+		use self::StreamRelaySide::*;
+
+		let joined_endpoints = self.joined_endpoints.get();
+		let has_gone = self.has_gone.get();
+		let (left, right) = (Self::index(Left), Self::index(Right));
+
+		self.left_to_right.borrow_mut().pump(joined_endpoints[left], joined_endpoints[right], has_gone[right])?;
+		self.right_to_left.borrow_mut().pump(joined_endpoints[right], joined_endpoints[left], has_gone[left])
+	}
+
+	#[inline(always)]
+	fn is_finished(&self, side: StreamRelaySide) -> bool
+	{
+		let other_side = side.other();
+		let incoming_is_complete = self.direction_from(other_side).borrow().is_complete();
+		let outgoing_is_complete = self.direction_from(side).borrow().is_complete();
+		incoming_is_complete && (outgoing_is_complete || self.has_gone.get()[Self::index(other_side)])
+	}
+
+	#[inline(always)]
+	fn join(&self, side: StreamRelaySide, joined_endpoint: StreamRelayJoinedEndpoint)
+	{
+		let mut joined_endpoints = self.joined_endpoints.get();
+		joined_endpoints[Self::index(side)] = Some(joined_endpoint);
+		self.joined_endpoints.set(joined_endpoints);
+	}
+
+	#[inline(always)]
+	pub(crate) fn depart(&self, side: StreamRelaySide)
+	{
+		let index = Self::index(side);
+
+		let mut joined_endpoints = self.joined_endpoints.get();
+		joined_endpoints[index] = None;
+		self.joined_endpoints.set(joined_endpoints);
+
+		let mut has_gone = self.has_gone.get();
+		has_gone[index] = true;
+		self.has_gone.set(has_gone);
+
+		self.direction_from(side).borrow_mut().source_has_gone();
+
+		// Otherwise the other side's coroutine could wait forever for an event on its own socket.
+		if let Some(other_joined_endpoint) = joined_endpoints[Self::index(side.other())]
+		{
+			other_joined_endpoint.shut_down_for_reading()
+		}
+	}
+
+	#[inline(always)]
+	fn direction_from(&self, side: StreamRelaySide) -> &RefCell<StreamRelayDirection>
+	{
+		use self::StreamRelaySide::*;
+
+		match side
+		{
+			Left => &self.left_to_right,
+			Right => &self.right_to_left,
+		}
+	}
+
+	#[inline(always)]
+	fn index(side: StreamRelaySide) -> usize
+	{
+		side as usize
+	}
+}
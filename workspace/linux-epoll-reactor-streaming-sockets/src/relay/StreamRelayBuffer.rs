@@ -0,0 +1,96 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+
+/// Buffers one direction of a relay.
+#[derive(Debug)]
+pub(crate) enum StreamRelayBuffer
+{
+	/// Used when both sides are `UnencryptedStream`s; bytes are moved with `splice(2)`, so they are never copied into userspace.
+	Pipe(StreamRelayPipe),
+
+	/// Used otherwise, eg when either side is a TLS stream, as the kernel does not have access to its plaintext.
+	UserSpace(StreamRelayUserSpaceBuffer),
+}
+
+impl StreamRelayBuffer
+{
+	#[inline(always)]
+	pub(crate) fn new(capacity: usize, use_pipe: bool) -> Result<Self, CompleteError>
+	{
+		use self::StreamRelayBuffer::*;
+
+		if use_pipe
+		{
+			StreamRelayPipe::new(capacity).map(Pipe).map_err(CompleteError::Undifferentiated)
+		}
+		else
+		{
+			Ok(UserSpace(StreamRelayUserSpaceBuffer::new(capacity)))
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn is_empty(&self) -> bool
+	{
+		use self::StreamRelayBuffer::*;
+
+		match self
+		{
+			&Pipe(ref pipe) => pipe.is_empty(),
+			&UserSpace(ref user_space_buffer) => user_space_buffer.is_empty(),
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn is_full(&self) -> bool
+	{
+		use self::StreamRelayBuffer::*;
+
+		match self
+		{
+			&Pipe(ref pipe) => pipe.is_full(),
+			&UserSpace(ref user_space_buffer) => user_space_buffer.is_full(),
+		}
+	}
+
+	/// Returns `Ok(None)` if no bytes are available yet and `Ok(Some(0))` at end-of-file.
+	#[inline(always)]
+	pub(crate) fn fill(&mut self, source: StreamRelayJoinedEndpoint) -> Result<Option<usize>, CompleteError>
+	{
+		use self::StreamRelayBuffer::*;
+
+		match self
+		{
+			&mut Pipe(ref mut pipe) => pipe.fill(source),
+			&mut UserSpace(ref mut user_space_buffer) => user_space_buffer.fill(source),
+		}
+	}
+
+	/// Returns `Ok(None)` if no bytes can be written yet.
+	#[inline(always)]
+	pub(crate) fn drain(&mut self, destination: StreamRelayJoinedEndpoint) -> Result<Option<usize>, CompleteError>
+	{
+		use self::StreamRelayBuffer::*;
+
+		match self
+		{
+			&mut Pipe(ref mut pipe) => pipe.drain(destination),
+			&mut UserSpace(ref mut user_space_buffer) => user_space_buffer.drain(destination),
+		}
+	}
+
+	/// Discards buffered bytes, returning the number discarded.
+	#[inline(always)]
+	pub(crate) fn discard(&mut self) -> Result<usize, CompleteError>
+	{
+		use self::StreamRelayBuffer::*;
+
+		match self
+		{
+			&mut Pipe(ref mut pipe) => pipe.discard(),
+			&mut UserSpace(ref mut user_space_buffer) => Ok(user_space_buffer.discard()),
+		}
+	}
+}
@@ -0,0 +1,21 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+
+/// Departs a side from a `StreamRelay` when dropped, so that the other side's coroutine never uses a joined endpoint after `StreamRelay.relay()` has returned or been unwound (eg because this side's coroutine has been dropped whilst suspended).
+#[derive(Debug)]
+pub(crate) struct StreamRelayDeparture<'a>
+{
+	pub(crate) stream_relay: &'a StreamRelay,
+	pub(crate) side: StreamRelaySide,
+}
+
+impl<'a> Drop for StreamRelayDeparture<'a>
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		self.stream_relay.depart(self.side)
+	}
+}
@@ -0,0 +1,295 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+
+/// One direction of a relay.
+///
+/// Bytes are left in the source until the destination has joined (or gone), so that the buffer can suit both sides.
+#[derive(Debug)]
+pub(crate) struct StreamRelayDirection
+{
+	capacity: usize,
+	buffer: Option<StreamRelayBuffer>,
+	source_end_of_file: bool,
+	destination_is_closing: bool,
+	destination_shut_down: bool,
+	bytes_relayed: u64,
+}
+
+impl StreamRelayDirection
+{
+	#[inline(always)]
+	pub(crate) fn new(capacity: usize) -> Self
+	{
+		Self
+		{
+			capacity,
+			buffer: None,
+			source_end_of_file: false,
+			destination_is_closing: false,
+			destination_shut_down: false,
+			bytes_relayed: 0,
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn bytes_relayed(&self) -> u64
+	{
+		self.bytes_relayed
+	}
+
+	/// The source has gone; treat as end-of-file.
+	#[inline(always)]
+	pub(crate) fn source_has_gone(&mut self)
+	{
+		self.source_end_of_file = true;
+	}
+
+	/// Complete once the source has reached end-of-file, all bytes have been written and the destination has been shut down for writing (or has gone).
+	#[inline(always)]
+	pub(crate) fn is_complete(&self) -> bool
+	{
+		self.destination_shut_down
+	}
+
+	/// Moves as many bytes as possible from `source` to `destination`, stopping when neither can make progress (ie `EAGAIN`).
+	///
+	/// `source` and `destination` are `None` if that side has not yet joined or has gone; bytes for a destination which has gone are discarded.
+	pub(crate) fn pump(&mut self, source: Option<StreamRelayJoinedEndpoint>, destination: Option<StreamRelayJoinedEndpoint>, destination_has_gone: bool) -> Result<(), CompleteError>
+	{
+		if self.buffer.is_none()
+		{
+			match (source, destination)
+			{
+				(Some(source), Some(destination)) => self.buffer = Some(StreamRelayBuffer::new(self.capacity, source.is_spliceable() && destination.is_spliceable())?),
+
+				(Some(_), None) if destination_has_gone => self.buffer = Some(StreamRelayBuffer::new(self.capacity, false)?),
+
+				_ => (),
+			}
+		}
+
+		loop
+		{
+			let mut made_progress = false;
+
+			if let Some(ref mut buffer) = self.buffer
+			{
+				if let Some(source) = source
+				{
+					if !self.source_end_of_file && !buffer.is_full()
+					{
+						match buffer.fill(source)?
+						{
+							Some(0) =>
+							{
+								self.source_end_of_file = true;
+								made_progress = true;
+							}
+
+							Some(_) => made_progress = true,
+
+							None => (),
+						}
+					}
+				}
+
+				if !buffer.is_empty()
+				{
+					if destination_has_gone
+					{
+						made_progress = buffer.discard()? != 0 || made_progress;
+					}
+					else if let Some(destination) = destination
+					{
+						if let Some(bytes_written) = buffer.drain(destination)?
+						{
+							self.bytes_relayed += bytes_written as u64;
+							made_progress = made_progress || bytes_written != 0;
+						}
+					}
+				}
+			}
+
+			if !destination_has_gone
+			{
+				if let Some(destination) = destination
+				{
+					destination.flush()?;
+				}
+			}
+
+			let buffer_is_empty = self.buffer.as_ref().map_or(true, StreamRelayBuffer::is_empty);
+			if self.source_end_of_file && buffer_is_empty && !self.destination_shut_down
+			{
+				if destination_has_gone
+				{
+					self.destination_shut_down = true;
+				}
+				else if let Some(destination) = destination
+				{
+					if !self.destination_is_closing
+					{
+						destination.close();
+						self.destination_is_closing = true;
+					}
+
+					if destination.flush()?
+					{
+						destination.shut_down_for_writing();
+						self.destination_shut_down = true;
+					}
+				}
+			}
+
+			if !made_progress
+			{
+				return Ok(())
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod stream_relay_direction_tests
+{
+	use super::*;
+	use ::linux_epoll::libc::SHUT_WR;
+	use ::linux_epoll::libc::shutdown;
+
+	struct SocketPair([RawFd; 2]);
+
+	impl Drop for SocketPair
+	{
+		fn drop(&mut self)
+		{
+			unsafe
+			{
+				close(self.0[0]);
+				close(self.0[1]);
+			}
+		}
+	}
+
+	impl SocketPair
+	{
+		fn new() -> Self
+		{
+			let mut file_descriptors: [c_int; 2] = [-1, -1];
+			assert_eq!(unsafe { socketpair(AF_UNIX, SOCK_STREAM | SOCK_NONBLOCK | SOCK_CLOEXEC, 0, file_descriptors.as_mut_ptr()) }, 0);
+			SocketPair(file_descriptors)
+		}
+
+		fn endpoint(&self, byte_counter: &mut ByteCounter) -> StreamRelayJoinedEndpoint
+		{
+			StreamRelayJoinedEndpoint
+			{
+				raw_file_descriptor: self.0[0],
+				transport: None,
+				byte_counter: byte_counter as *mut ByteCounter,
+			}
+		}
+
+		fn remote_peer_write(&self, bytes: &[u8])
+		{
+			let result = unsafe { ::linux_epoll::libc::write(self.0[1], bytes.as_ptr() as *const c_void, bytes.len()) };
+			assert_eq!(result, bytes.len() as isize);
+		}
+
+		fn remote_peer_shut_down_for_writing(&self)
+		{
+			assert_eq!(unsafe { shutdown(self.0[1], SHUT_WR) }, 0);
+		}
+
+		/// `None` if nothing has been received yet.
+		fn remote_peer_read(&self) -> Option<Vec<u8>>
+		{
+			let mut buffer = [0u8; 256];
+			let result = unsafe { ::linux_epoll::libc::read(self.0[1], buffer.as_mut_ptr() as *mut c_void, buffer.len()) };
+			if result < 0
+			{
+				None
+			}
+			else
+			{
+				Some(buffer[.. result as usize].to_vec())
+			}
+		}
+	}
+
+	fn relays_and_propagates_end_of_file(mut direction: StreamRelayDirection)
+	{
+		let (source, destination) = (SocketPair::new(), SocketPair::new());
+		let (mut source_byte_counter, mut destination_byte_counter) = (ByteCounter::default(), ByteCounter::default());
+		let (source_endpoint, destination_endpoint) = (source.endpoint(&mut source_byte_counter), destination.endpoint(&mut destination_byte_counter));
+
+		source.remote_peer_write(b"0123456789");
+		direction.pump(Some(source_endpoint), Some(destination_endpoint), false).unwrap();
+		assert_eq!(destination.remote_peer_read(), Some(b"0123456789".to_vec()));
+		assert_eq!(direction.bytes_relayed(), 10);
+		assert!(!direction.is_complete());
+
+		source.remote_peer_shut_down_for_writing();
+		direction.pump(Some(source_endpoint), Some(destination_endpoint), false).unwrap();
+		assert_eq!(destination.remote_peer_read(), Some(Vec::new()));
+		assert!(direction.is_complete());
+	}
+
+	#[test]
+	fn relays_using_a_pipe()
+	{
+		relays_and_propagates_end_of_file(StreamRelayDirection::new(4096));
+	}
+
+	#[test]
+	fn relays_using_a_user_space_buffer_smaller_than_the_bytes_relayed()
+	{
+		let mut direction = StreamRelayDirection::new(4);
+		direction.buffer = Some(StreamRelayBuffer::UserSpace(StreamRelayUserSpaceBuffer::new(4)));
+
+		relays_and_propagates_end_of_file(direction);
+	}
+
+	#[test]
+	fn leaves_bytes_in_source_until_destination_joins()
+	{
+		let (source, destination) = (SocketPair::new(), SocketPair::new());
+		let (mut source_byte_counter, mut destination_byte_counter) = (ByteCounter::default(), ByteCounter::default());
+		let mut direction = StreamRelayDirection::new(4096);
+
+		source.remote_peer_write(b"hello");
+		direction.pump(Some(source.endpoint(&mut source_byte_counter)), None, false).unwrap();
+		assert!(direction.buffer.is_none());
+
+		direction.pump(Some(source.endpoint(&mut source_byte_counter)), Some(destination.endpoint(&mut destination_byte_counter)), false).unwrap();
+		assert_eq!(destination.remote_peer_read(), Some(b"hello".to_vec()));
+	}
+
+	#[test]
+	fn discards_bytes_for_a_destination_which_has_gone()
+	{
+		let source = SocketPair::new();
+		let mut source_byte_counter = ByteCounter::default();
+		let mut direction = StreamRelayDirection::new(4096);
+
+		source.remote_peer_write(b"hello");
+		source.remote_peer_shut_down_for_writing();
+		direction.pump(Some(source.endpoint(&mut source_byte_counter)), None, true).unwrap();
+		assert_eq!(direction.bytes_relayed(), 0);
+		assert!(direction.is_complete());
+	}
+
+	#[test]
+	fn source_which_has_gone_shuts_down_destination()
+	{
+		let destination = SocketPair::new();
+		let mut destination_byte_counter = ByteCounter::default();
+		let mut direction = StreamRelayDirection::new(4096);
+
+		direction.source_has_gone();
+		direction.pump(None, Some(destination.endpoint(&mut destination_byte_counter)), false).unwrap();
+		assert_eq!(destination.remote_peer_read(), Some(Vec::new()));
+		assert!(direction.is_complete());
+	}
+}
@@ -0,0 +1,46 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+
+/// The parts of a stream used by a `StreamRelay`.
+///
+/// The file descriptor, byte counter and any transport (eg a TLS session) are used by whichever side's coroutine is pumping; the yielder is only used by the stream's own coroutine.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct StreamRelayEndpoint<'a, 'yielder: 'a>
+{
+	joined_endpoint: StreamRelayJoinedEndpoint,
+	input_output_yielder: &'a mut InputOutputYielder<'yielder>,
+}
+
+impl<'a, 'yielder: 'a> StreamRelayEndpoint<'a, 'yielder>
+{
+	/// `transport` is `None` for a stream whose bytes the kernel can see, ie an `UnencryptedStream`, so that it can be relayed using `splice(2)`.
+	#[inline(always)]
+	pub(crate) fn new(raw_file_descriptor: RawFd, transport: Option<&'a mut (dyn StreamRelayTransport + 'static)>, byte_counter: &'a mut ByteCounter, input_output_yielder: &'a mut InputOutputYielder<'yielder>) -> Self
+	{
+		Self
+		{
+			joined_endpoint: StreamRelayJoinedEndpoint
+			{
+				raw_file_descriptor,
+				transport: transport.map(|transport| transport as *mut dyn StreamRelayTransport),
+				byte_counter: byte_counter as *mut ByteCounter,
+			},
+			input_output_yielder,
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn joined_endpoint(&self) -> StreamRelayJoinedEndpoint
+	{
+		self.joined_endpoint
+	}
+
+	#[inline(always)]
+	pub(crate) fn await_further_input_or_output_to_become_available(&mut self) -> Result<ReactEdgeTriggeredStatus, CompleteError>
+	{
+		self.input_output_yielder.await_further_input_or_output_to_become_available()
+	}
+}
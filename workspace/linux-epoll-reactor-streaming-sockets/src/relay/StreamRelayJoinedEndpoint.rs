@@ -0,0 +1,159 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+
+/// A side which has joined a `StreamRelay`.
+///
+/// Only valid until that side departs; it points into a stream owned by that side's coroutine, which is suspended whilst the other side's coroutine is pumping.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct StreamRelayJoinedEndpoint
+{
+	raw_file_descriptor: RawFd,
+	transport: Option<*mut dyn StreamRelayTransport>,
+	byte_counter: *mut ByteCounter,
+}
+
+impl StreamRelayJoinedEndpoint
+{
+	/// Can bytes be moved to or from this side using `splice(2)`?
+	#[inline(always)]
+	pub(crate) fn is_spliceable(&self) -> bool
+	{
+		self.transport.is_none()
+	}
+
+	#[inline(always)]
+	pub(crate) fn raw_file_descriptor(&self) -> RawFd
+	{
+		self.raw_file_descriptor
+	}
+
+	#[inline(always)]
+	pub(crate) fn record_bytes_read(&self, bytes_read: usize)
+	{
+		self.byte_counter().bytes_read(bytes_read)
+	}
+
+	#[inline(always)]
+	pub(crate) fn record_bytes_written(&self, bytes_written: usize)
+	{
+		self.byte_counter().bytes_written(bytes_written)
+	}
+
+	/// Returns `Ok(None)` if no bytes are available yet and `Ok(Some(0))` at end-of-file.
+	#[inline(always)]
+	pub(crate) fn read(&self, read_into_buffer: &mut [u8]) -> Result<Option<usize>, CompleteError>
+	{
+		use ::linux_epoll::libc::recv;
+
+		match self.transport
+		{
+			Some(transport) => unsafe { &mut *transport }.relay_read(self.raw_file_descriptor, self.byte_counter(), read_into_buffer),
+
+			None => loop
+			{
+				let result = unsafe { recv(self.raw_file_descriptor, read_into_buffer.as_mut_ptr() as *mut c_void, read_into_buffer.len(), 0) };
+				if likely!(result >= 0)
+				{
+					let bytes_read = result as usize;
+					self.record_bytes_read(bytes_read);
+					return Ok(Some(bytes_read))
+				}
+
+				let io_error = io::Error::last_os_error();
+				match io_error.kind()
+				{
+					ErrorKind::Interrupted => continue,
+
+					ErrorKind::WouldBlock => return Ok(None),
+
+					_ => return Err(CompleteError::SocketRead(io_error)),
+				}
+			}
+		}
+	}
+
+	/// Returns `Ok(None)` if no bytes can be written yet.
+	#[inline(always)]
+	pub(crate) fn write(&self, write_from_buffer: &[u8]) -> Result<Option<usize>, CompleteError>
+	{
+		use ::linux_epoll::libc::send;
+
+		match self.transport
+		{
+			Some(transport) => unsafe { &mut *transport }.relay_write(self.raw_file_descriptor, self.byte_counter(), write_from_buffer),
+
+			None => loop
+			{
+				let result = unsafe { send(self.raw_file_descriptor, write_from_buffer.as_ptr() as *const c_void, write_from_buffer.len(), MSG_NOSIGNAL) };
+				if likely!(result >= 0)
+				{
+					let bytes_written = result as usize;
+					self.record_bytes_written(bytes_written);
+					return Ok(Some(bytes_written))
+				}
+
+				let io_error = io::Error::last_os_error();
+				match io_error.kind()
+				{
+					ErrorKind::Interrupted => continue,
+
+					ErrorKind::WouldBlock => return Ok(None),
+
+					_ => return Err(CompleteError::SocketWrite(io_error)),
+				}
+			}
+		}
+	}
+
+	/// Returns `Ok(false)` if written bytes are still held by the transport (eg as TLS records not yet sent).
+	#[inline(always)]
+	pub(crate) fn flush(&self) -> Result<bool, CompleteError>
+	{
+		match self.transport
+		{
+			Some(transport) => unsafe { &mut *transport }.relay_flush(self.raw_file_descriptor, self.byte_counter()),
+
+			None => Ok(true),
+		}
+	}
+
+	/// Ends the stream; once `flush()` returns `Ok(true)`, call `shut_down_for_writing()`.
+	#[inline(always)]
+	pub(crate) fn close(&self)
+	{
+		if let Some(transport) = self.transport
+		{
+			unsafe { &mut *transport }.relay_close()
+		}
+	}
+
+	/// Propagates a half-close; failure (eg `ENOTCONN` because the remote peer has already closed) is not relevant.
+	#[inline(always)]
+	pub(crate) fn shut_down_for_writing(&self)
+	{
+		use ::linux_epoll::libc::shutdown;
+		use ::linux_epoll::libc::SHUT_WR;
+
+		unsafe { shutdown(self.raw_file_descriptor, SHUT_WR) };
+	}
+
+	/// Wakes this side's coroutine, which may be waiting for input that will now never be relayed; failure is not relevant.
+	///
+	/// Shutting down for reading changes the socket's state, which causes an edge-triggered event, yet still permits bytes already relayed to be written.
+	#[inline(always)]
+	pub(crate) fn shut_down_for_reading(&self)
+	{
+		use ::linux_epoll::libc::shutdown;
+		use ::linux_epoll::libc::SHUT_RD;
+
+		unsafe { shutdown(self.raw_file_descriptor, SHUT_RD) };
+	}
+
+	#[inline(always)]
+	fn byte_counter(&self) -> &mut ByteCounter
+	{
+		unsafe { &mut *self.byte_counter }
+	}
+}
@@ -0,0 +1,158 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+
+/// A pipe, so that bytes can be moved between sockets with `splice(2)`.
+#[derive(Debug)]
+pub(crate) struct StreamRelayPipe
+{
+	pipe_read_file_descriptor: RawFd,
+	pipe_write_file_descriptor: RawFd,
+	pipe_capacity: usize,
+	bytes_in_pipe: usize,
+}
+
+impl Drop for StreamRelayPipe
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		unsafe
+		{
+			::linux_epoll::libc::close(self.pipe_read_file_descriptor);
+			::linux_epoll::libc::close(self.pipe_write_file_descriptor);
+		}
+	}
+}
+
+impl StreamRelayPipe
+{
+	#[inline(always)]
+	pub(crate) fn new(pipe_capacity: usize) -> io::Result<Self>
+	{
+		use ::linux_epoll::libc::*;
+
+		let mut pipe_file_descriptors: [c_int; 2] = [-1, -1];
+		let result = unsafe { pipe2(pipe_file_descriptors.as_mut_ptr(), O_NONBLOCK | O_CLOEXEC) };
+		if unlikely!(result == -1)
+		{
+			return Err(io::Error::last_os_error())
+		}
+
+		let mut this = Self
+		{
+			pipe_read_file_descriptor: pipe_file_descriptors[0],
+			pipe_write_file_descriptor: pipe_file_descriptors[1],
+			pipe_capacity,
+			bytes_in_pipe: 0,
+		};
+
+		let result = unsafe { fcntl(this.pipe_write_file_descriptor, F_SETPIPE_SZ, pipe_capacity as c_int) };
+		if unlikely!(result == -1)
+		{
+			return Err(io::Error::last_os_error())
+		}
+		this.pipe_capacity = result as usize;
+
+		Ok(this)
+	}
+
+	#[inline(always)]
+	pub(crate) fn is_empty(&self) -> bool
+	{
+		self.bytes_in_pipe == 0
+	}
+
+	#[inline(always)]
+	pub(crate) fn is_full(&self) -> bool
+	{
+		self.bytes_in_pipe == self.pipe_capacity
+	}
+
+	#[inline(always)]
+	pub(crate) fn fill(&mut self, source: StreamRelayJoinedEndpoint) -> Result<Option<usize>, CompleteError>
+	{
+		match Self::splice(source.raw_file_descriptor(), self.pipe_write_file_descriptor, self.pipe_capacity - self.bytes_in_pipe)
+		{
+			Ok(Some(bytes_read)) =>
+			{
+				self.bytes_in_pipe += bytes_read;
+				source.record_bytes_read(bytes_read);
+				Ok(Some(bytes_read))
+			}
+
+			Ok(None) => Ok(None),
+
+			Err(io_error) => Err(CompleteError::SocketRead(io_error)),
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn drain(&mut self, destination: StreamRelayJoinedEndpoint) -> Result<Option<usize>, CompleteError>
+	{
+		match Self::splice(self.pipe_read_file_descriptor, destination.raw_file_descriptor(), self.bytes_in_pipe)
+		{
+			Ok(Some(bytes_written)) =>
+			{
+				self.bytes_in_pipe -= bytes_written;
+				destination.record_bytes_written(bytes_written);
+				Ok(Some(bytes_written))
+			}
+
+			Ok(None) => Ok(None),
+
+			Err(io_error) => Err(CompleteError::SocketWrite(io_error)),
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn discard(&mut self) -> Result<usize, CompleteError>
+	{
+		let mut buffer: [u8; 4096] = unsafe { uninitialized() };
+		let length = min(self.bytes_in_pipe, buffer.len());
+		let result = unsafe { ::linux_epoll::libc::read(self.pipe_read_file_descriptor, buffer.as_mut_ptr() as *mut _, length) };
+		if likely!(result >= 0)
+		{
+			let discarded = result as usize;
+			self.bytes_in_pipe -= discarded;
+			Ok(discarded)
+		}
+		else
+		{
+			let io_error = io::Error::last_os_error();
+			match io_error.kind()
+			{
+				ErrorKind::Interrupted | ErrorKind::WouldBlock => Ok(0),
+
+				_ => Err(CompleteError::Undifferentiated(io_error)),
+			}
+		}
+	}
+
+	/// Returns `Ok(None)` for `EAGAIN`.
+	#[inline(always)]
+	fn splice(from: RawFd, to: RawFd, length: usize) -> io::Result<Option<usize>>
+	{
+		use ::linux_epoll::libc::*;
+
+		loop
+		{
+			let result = unsafe { splice(from, null_mut(), to, null_mut(), length, SPLICE_F_MOVE | SPLICE_F_NONBLOCK) };
+			if likely!(result >= 0)
+			{
+				return Ok(Some(result as usize))
+			}
+
+			let io_error = io::Error::last_os_error();
+			match io_error.kind()
+			{
+				ErrorKind::Interrupted => continue,
+
+				ErrorKind::WouldBlock => return Ok(None),
+
+				_ => return Err(io_error),
+			}
+		}
+	}
+}
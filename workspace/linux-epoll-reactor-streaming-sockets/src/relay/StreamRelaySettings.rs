@@ -0,0 +1,27 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Stream relay settings.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct StreamRelaySettings
+{
+	/// Capacity of the buffer for each direction; this bounds the bytes held between the two streams.
+	///
+	/// When the buffer is a pipe, rounded up by Linux to a power of two pages and limited by `/proc/sys/fs/pipe-max-size` for unprivileged processes.
+	pub pipe_capacity: usize,
+}
+
+impl Default for StreamRelaySettings
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			pipe_capacity: 64 * 1024,
+		}
+	}
+}
@@ -0,0 +1,29 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A side of a `StreamRelay`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StreamRelaySide
+{
+	/// Left, eg the accepted (inbound) stream.
+	Left,
+
+	/// Right, eg the connected (outbound) stream.
+	Right,
+}
+
+impl StreamRelaySide
+{
+	#[inline(always)]
+	pub(crate) fn other(self) -> Self
+	{
+		use self::StreamRelaySide::*;
+
+		match self
+		{
+			Left => Right,
+			Right => Left,
+		}
+	}
+}
@@ -0,0 +1,172 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+
+/// Non-blocking input and output for a stream whose bytes the kernel can not see, eg a TLS stream.
+///
+/// Used by a `StreamRelay` from the coroutine of either side, so implementations must never yield.
+pub(crate) trait StreamRelayTransport
+{
+	/// Reads plaintext; returns `Ok(None)` if none is available yet and `Ok(Some(0))` at end-of-file.
+	fn relay_read(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, read_into_buffer: &mut [u8]) -> Result<Option<usize>, CompleteError>;
+
+	/// Writes plaintext; returns `Ok(None)` if previously written plaintext has not yet been sent, so that at most one buffer's worth is held.
+	fn relay_write(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, write_from_buffer: &[u8]) -> Result<Option<usize>, CompleteError>;
+
+	/// Sends previously written plaintext; returns `Ok(false)` if some remains unsent.
+	fn relay_flush(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter) -> Result<bool, CompleteError>;
+
+	/// Ends the stream (eg by queueing a TLS close notify alert); follow with `relay_flush()`.
+	fn relay_close(&mut self);
+}
+
+impl<S: Session> StreamRelayTransport for S
+{
+	#[inline(always)]
+	fn relay_read(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, read_into_buffer: &mut [u8]) -> Result<Option<usize>, CompleteError>
+	{
+		struct ReadAdaptor(RawFd);
+
+		impl Read for ReadAdaptor
+		{
+			#[inline(always)]
+			fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+			{
+				use ::linux_epoll::libc::recv;
+
+				let result = unsafe { recv(self.0, buf.as_mut_ptr() as *mut c_void, buf.len(), 0) };
+				if likely!(result >= 0)
+				{
+					Ok(result as usize)
+				}
+				else
+				{
+					Err(io::Error::last_os_error())
+				}
+			}
+
+			#[inline(always)]
+			unsafe fn initializer(&self) -> Initializer
+			{
+				Initializer::nop()
+			}
+		}
+
+		loop
+		{
+			match self.read(read_into_buffer)
+			{
+				// rustls returns `Ok(0)` when there is no plaintext yet.
+				Ok(0) => (),
+
+				Ok(bytes_read) => return Ok(Some(bytes_read)),
+
+				Err(ref io_error) if io_error.kind() == ErrorKind::ConnectionAborted => return Ok(Some(0)),
+
+				Err(io_error) => return Err(CompleteError::SocketRead(io_error)),
+			}
+
+			let bytes_read = loop
+			{
+				match self.read_tls(&mut ReadAdaptor(raw_file_descriptor))
+				{
+					Ok(bytes_read) => break bytes_read,
+
+					Err(io_error) => match io_error.kind()
+					{
+						ErrorKind::Interrupted => continue,
+
+						ErrorKind::WouldBlock => return Ok(None),
+
+						_ => return Err(CompleteError::SocketRead(io_error)),
+					}
+				}
+			};
+
+			// The remote peer closed without sending a close notify alert; treated as end-of-file, as for an unencrypted stream.
+			if unlikely!(bytes_read == 0)
+			{
+				return Ok(Some(0))
+			}
+			byte_counter.bytes_read(bytes_read);
+
+			if let Err(tls_error) = self.process_new_packets()
+			{
+				// In case there is a TLS alert message to send describing this error.
+				let _ = self.relay_flush(raw_file_descriptor, byte_counter);
+				return Err(CompleteError::from(TlsInputOutputError::ProcessNewPackets(tls_error)))
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn relay_write(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, write_from_buffer: &[u8]) -> Result<Option<usize>, CompleteError>
+	{
+		if !self.relay_flush(raw_file_descriptor, byte_counter)?
+		{
+			return Ok(None)
+		}
+
+		let bytes_written = self.write(write_from_buffer).expect("Internal implementation in self (Session) send_some_plaintext() does not return errors");
+		if unlikely!(bytes_written == 0)
+		{
+			return Ok(None)
+		}
+
+		self.relay_flush(raw_file_descriptor, byte_counter)?;
+		Ok(Some(bytes_written))
+	}
+
+	#[inline(always)]
+	fn relay_flush(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter) -> Result<bool, CompleteError>
+	{
+		struct WriteVAdaptor(RawFd);
+
+		impl WriteV for WriteVAdaptor
+		{
+			#[inline(always)]
+			fn writev(&mut self, vbytes: &[&[u8]]) -> io::Result<usize>
+			{
+				use ::linux_epoll::libc::writev;
+
+				// Writing fewer buffers than offered is a short write, which rustls handles.
+				let iovecs: ArrayVec<[iovec; 64]> = vbytes.iter().take(64).map(|bytes| iovec { iov_base: bytes.as_ptr() as *mut c_void, iov_len: bytes.len() }).collect();
+				let result = unsafe { writev(self.0, iovecs.as_ptr(), iovecs.len() as c_int) };
+				if likely!(result >= 0)
+				{
+					Ok(result as usize)
+				}
+				else
+				{
+					Err(io::Error::last_os_error())
+				}
+			}
+		}
+
+		while self.wants_write()
+		{
+			match self.writev_tls(&mut WriteVAdaptor(raw_file_descriptor))
+			{
+				Ok(bytes_written) => byte_counter.bytes_written(bytes_written),
+
+				Err(io_error) => match io_error.kind()
+				{
+					ErrorKind::Interrupted => continue,
+
+					ErrorKind::WouldBlock => return Ok(false),
+
+					_ => return Err(CompleteError::SocketVectoredWrite(io_error)),
+				}
+			}
+		}
+
+		Ok(true)
+	}
+
+	#[inline(always)]
+	fn relay_close(&mut self)
+	{
+		self.send_close_notify()
+	}
+}
@@ -0,0 +1,83 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+
+/// A bounded userspace buffer, for streams whose bytes the kernel can not see.
+#[derive(Debug)]
+pub(crate) struct StreamRelayUserSpaceBuffer
+{
+	bytes: Box<[u8]>,
+	start: usize,
+	end: usize,
+}
+
+impl StreamRelayUserSpaceBuffer
+{
+	#[inline(always)]
+	pub(crate) fn new(capacity: usize) -> Self
+	{
+		Self
+		{
+			bytes: vec![0; capacity].into_boxed_slice(),
+			start: 0,
+			end: 0,
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn is_empty(&self) -> bool
+	{
+		self.start == self.end
+	}
+
+	#[inline(always)]
+	pub(crate) fn is_full(&self) -> bool
+	{
+		self.start == 0 && self.end == self.bytes.len()
+	}
+
+	#[inline(always)]
+	pub(crate) fn fill(&mut self, source: StreamRelayJoinedEndpoint) -> Result<Option<usize>, CompleteError>
+	{
+		if self.end == self.bytes.len()
+		{
+			let length = self.end - self.start;
+			unsafe { copy(self.bytes.as_ptr().add(self.start), self.bytes.as_mut_ptr(), length) };
+			self.start = 0;
+			self.end = length;
+		}
+
+		let bytes_read = source.read(&mut self.bytes[self.end .. ])?;
+		if let Some(bytes_read) = bytes_read
+		{
+			self.end += bytes_read;
+		}
+		Ok(bytes_read)
+	}
+
+	#[inline(always)]
+	pub(crate) fn drain(&mut self, destination: StreamRelayJoinedEndpoint) -> Result<Option<usize>, CompleteError>
+	{
+		let bytes_written = destination.write(&self.bytes[self.start .. self.end])?;
+		if let Some(bytes_written) = bytes_written
+		{
+			self.start += bytes_written;
+			if self.start == self.end
+			{
+				self.start = 0;
+				self.end = 0;
+			}
+		}
+		Ok(bytes_written)
+	}
+
+	#[inline(always)]
+	pub(crate) fn discard(&mut self) -> usize
+	{
+		let discarded = self.end - self.start;
+		self.start = 0;
+		self.end = 0;
+		discarded
+	}
+}
@@ -0,0 +1,13 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+
+/// A stream which a `StreamRelay` can relay.
+///
+/// Implemented for `UnencryptedStream`, `TlsServerStream` and `TlsClientStream`.
+pub trait StreamRelayable<'yielder>: Stream
+{
+	#[doc(hidden)]
+	fn stream_relay_endpoint<'a>(&'a mut self) -> StreamRelayEndpoint<'a, 'yielder> where 'yielder: 'a;
+}
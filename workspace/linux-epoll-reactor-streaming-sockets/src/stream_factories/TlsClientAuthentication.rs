@@ -0,0 +1,66 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Client certificate authentication (mutual TLS) policy for a TLS server.
+///
+/// Verified client identities are available to a `StreamUser` from `TlsServerStream::post_handshake_information()`.
+#[derive(Debug, Clone)]
+pub enum TlsClientAuthentication
+{
+	/// Client certificates are not requested.
+	NotRequested,
+
+	/// Clients may present a certificate; if they do, it must chain to one of these certificate authorities.
+	Optional(RootCertStore),
+
+	/// Clients must present a certificate which chains to one of these certificate authorities.
+	Required(RootCertStore),
+}
+
+impl Default for TlsClientAuthentication
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		TlsClientAuthentication::NotRequested
+	}
+}
+
+impl TlsClientAuthentication
+{
+	/// Loads certificate authorities from a PEM bundle file (eg `/etc/ssl/internal-ca.pem`).
+	///
+	/// Fails if the file can not be read or contains no valid certificates.
+	pub fn load_certificate_authorities(pem_file_path: &Path) -> io::Result<RootCertStore>
+	{
+		let pem_file = ::std::fs::read(pem_file_path)?;
+
+		let mut certificate_authorities = RootCertStore::empty();
+		match certificate_authorities.add_pem_file(&mut &pem_file[..])
+		{
+			Ok((valid, _invalid)) if valid != 0 => Ok(certificate_authorities),
+
+			_ => Err(io::Error::new(ErrorKind::InvalidData, format!("No valid certificate authorities in {:?}", pem_file_path))),
+		}
+	}
+
+	/// Creates a TLS server configuration using this policy.
+	///
+	/// Its certificate chain and private key (and, optionally, cipher suites, protocols, etc) need to be set before use, eg with `TlsServerStreamFactory` or in `TlsServerNameIndicationConfigurations::load_from_directory()`.
+	#[inline(always)]
+	pub fn server_configuration(self) -> ServerConfig
+	{
+		use self::TlsClientAuthentication::*;
+
+		let client_certificate_verifier = match self
+		{
+			NotRequested => NoClientAuth::new(),
+
+			Optional(certificate_authorities) => AllowAnyAnonymousOrAuthenticatedClient::new(certificate_authorities),
+
+			Required(certificate_authorities) => AllowAnyAuthenticatedClient::new(certificate_authorities),
+		};
+		ServerConfig::new(client_certificate_verifier)
+	}
+}
@@ -0,0 +1,50 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A factory to create instances of `TlsClientStream` which send initial data as TLS 1.3 0-RTT early data when resuming a session, saving a round trip.
+///
+/// Early data can be replayed by an attacker, so should only be used for idempotent requests.
+pub struct TlsClientEarlyDataStreamFactory
+{
+	/// TLS client configuration.
+	///
+	/// `enable_early_data` needs to be `true` and a session cache should be set (see `TlsClientSessionCache`).
+	pub tls_configuration: Arc<ClientConfig>,
+
+	/// TLS session buffer size limit.
+	pub session_buffer_limit: usize,
+}
+
+impl Debug for TlsClientEarlyDataStreamFactory
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "TlsClientEarlyDataStreamFactory {{ tls_configuration: _, session_buffer_limit: {:?} }}", self.session_buffer_limit)
+	}
+}
+
+impl<SD: SocketData> StreamFactory<SD> for TlsClientEarlyDataStreamFactory
+{
+	/// This is a lie; the lifetime is ***NOT*** `'static` but actually `'yielder` in `new_stream_and_handshake()`.
+	type S = TlsClientStream<'static, SD>;
+
+	type ProxyOrTunnelInformation = TlsEarlyDataOutcome;
+
+	/// The ASCII host name and the initial data to send.
+	type AdditionalArguments = (Rc<DNSName>, Vec<u8>);
+
+	#[inline(always)]
+	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
+	{
+		let (ascii_host_name, early_data) = additional_arguments;
+
+		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
+		let (stream, tls_early_data_outcome) = TlsClientStream::new_with_early_data(generic_stream, &self.tls_configuration, self.session_buffer_limit, ascii_host_name, &early_data)?;
+
+		// Grotesque hack which extends lifetime from 'yielder to 'static.
+		let stream: Self::S = unsafe { transmute(stream) };
+		Ok((stream, tls_early_data_outcome))
+	}
+}
@@ -0,0 +1,366 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// The Server Name Indication (SNI) host name in a TLS `ClientHello`, found by parsing the first TLS records received.
+///
+/// A `ClientHello` fragmented across more than one record is reassembled.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum TlsClientHelloServerName<'a>
+{
+	/// The `ClientHello` has not yet been completely received.
+	NeedMoreBytes,
+
+	/// There is no host name, or the records are not a (parseable) `ClientHello`; the TLS handshake will report any error.
+	Absent,
+
+	/// A host name; owned if the `ClientHello` was reassembled.
+	Present(Cow<'a, str>),
+}
+
+impl<'a> TlsClientHelloServerName<'a>
+{
+	/// TLS record header length (content type, version and length).
+	const RecordHeaderLength: usize = 5;
+
+	/// Maximum length of a TLS plaintext record, including its header.
+	pub(crate) const MaximumRecordLength: usize = Self::RecordHeaderLength + 16_384;
+
+	/// Maximum length of the records holding a `ClientHello` which will be reassembled.
+	pub(crate) const MaximumRecordsLength: usize = 4 * Self::MaximumRecordLength;
+
+	/// Handshake message header length (handshake type and length).
+	const HandshakeHeaderLength: usize = 4;
+
+	const HandshakeContentType: u8 = 22;
+
+	const ClientHelloHandshakeType: u8 = 1;
+
+	const ServerNameExtensionType: u16 = 0;
+
+	const HostNameNameType: u8 = 0;
+
+	/// Parses `bytes`, which are the bytes received so far.
+	#[inline(always)]
+	pub(crate) fn parse(bytes: &'a [u8]) -> Self
+	{
+		let mut records = bytes;
+
+		let first_fragment = match Self::take_handshake_record_fragment(&mut records)
+		{
+			Err(outcome) => return outcome,
+
+			Ok(first_fragment) => first_fragment,
+		};
+
+		match Self::handshake_message_length(first_fragment)
+		{
+			Some(length) if first_fragment.len() >= length => return Self::from_host_name(Self::parse_client_hello(&first_fragment[.. length]).map(Cow::Borrowed)),
+
+			_ => (),
+		}
+
+		let mut handshake_message = first_fragment.to_vec();
+		loop
+		{
+			let fragment = match Self::take_handshake_record_fragment(&mut records)
+			{
+				Err(outcome) => return outcome,
+
+				Ok(fragment) => fragment,
+			};
+			handshake_message.extend_from_slice(fragment);
+
+			match Self::handshake_message_length(&handshake_message[..])
+			{
+				Some(length) if handshake_message.len() >= length => return Self::from_host_name(Self::parse_client_hello(&handshake_message[.. length]).map(|host_name| Cow::Owned(host_name.to_string()))),
+
+				_ => (),
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn from_host_name(host_name: Option<Cow<'a, str>>) -> Self
+	{
+		use self::TlsClientHelloServerName::*;
+
+		match host_name
+		{
+			None => Absent,
+
+			Some(host_name) => Present(host_name),
+		}
+	}
+
+	#[inline(always)]
+	fn take_handshake_record_fragment(records: &mut &'a [u8]) -> Result<&'a [u8], Self>
+	{
+		use self::TlsClientHelloServerName::*;
+
+		if records.len() < Self::RecordHeaderLength
+		{
+			return Err(NeedMoreBytes)
+		}
+
+		if records[0] != Self::HandshakeContentType
+		{
+			return Err(Absent)
+		}
+
+		let record_length = Self::RecordHeaderLength + (((records[3] as usize) << 8) | (records[4] as usize));
+		if records.len() < record_length
+		{
+			return Err(NeedMoreBytes)
+		}
+
+		let fragment = &records[Self::RecordHeaderLength .. record_length];
+		*records = &records[record_length .. ];
+		Ok(fragment)
+	}
+
+	/// Length of the handshake message, including its header, once the header has been received.
+	#[inline(always)]
+	fn handshake_message_length(handshake_message: &[u8]) -> Option<usize>
+	{
+		let mut header = handshake_message;
+		Self::take(&mut header, 1)?;
+		Some(Self::HandshakeHeaderLength + Self::take_length(&mut header, 3)?)
+	}
+
+	/// `handshake_message` is exactly one handshake message, including its header.
+	#[inline(always)]
+	fn parse_client_hello<'b>(mut handshake_message: &'b [u8]) -> Option<&'b str>
+	{
+		if Self::take_u8(&mut handshake_message)? != Self::ClientHelloHandshakeType
+		{
+			return None
+		}
+
+		let handshake_length = Self::take_length(&mut handshake_message, 3)?;
+		let mut client_hello = Self::take(&mut handshake_message, handshake_length)?;
+
+		// Legacy version and random.
+		Self::take(&mut client_hello, 2 + 32)?;
+
+		// Legacy session identifier.
+		let length = Self::take_length(&mut client_hello, 1)?;
+		Self::take(&mut client_hello, length)?;
+
+		// Cipher suites.
+		let length = Self::take_length(&mut client_hello, 2)?;
+		Self::take(&mut client_hello, length)?;
+
+		// Legacy compression methods.
+		let length = Self::take_length(&mut client_hello, 1)?;
+		Self::take(&mut client_hello, length)?;
+
+		let length = Self::take_length(&mut client_hello, 2)?;
+		let mut extensions = Self::take(&mut client_hello, length)?;
+		while !extensions.is_empty()
+		{
+			let extension_type = Self::take_length(&mut extensions, 2)? as u16;
+			let length = Self::take_length(&mut extensions, 2)?;
+			let mut extension_data = Self::take(&mut extensions, length)?;
+
+			if extension_type != Self::ServerNameExtensionType
+			{
+				continue
+			}
+
+			let length = Self::take_length(&mut extension_data, 2)?;
+			let mut server_name_list = Self::take(&mut extension_data, length)?;
+			while !server_name_list.is_empty()
+			{
+				let name_type = Self::take_u8(&mut server_name_list)?;
+				let length = Self::take_length(&mut server_name_list, 2)?;
+				let name = Self::take(&mut server_name_list, length)?;
+
+				if name_type == Self::HostNameNameType
+				{
+					return from_utf8(name).ok()
+				}
+			}
+			return None
+		}
+
+		None
+	}
+
+	#[inline(always)]
+	fn take_u8<'b>(bytes: &mut &'b [u8]) -> Option<u8>
+	{
+		Self::take(bytes, 1).map(|byte| byte[0])
+	}
+
+	#[inline(always)]
+	fn take_length<'b>(bytes: &mut &'b [u8], number_of_bytes: usize) -> Option<usize>
+	{
+		Some(Self::take(bytes, number_of_bytes)?.iter().fold(0, |length, &byte| (length << 8) | (byte as usize)))
+	}
+
+	#[inline(always)]
+	fn take<'b>(bytes: &mut &'b [u8], length: usize) -> Option<&'b [u8]>
+	{
+		if bytes.len() < length
+		{
+			return None
+		}
+
+		let (taken, remaining) = bytes.split_at(length);
+		*bytes = remaining;
+		Some(taken)
+	}
+}
+
+#[cfg(test)]
+mod tls_client_hello_server_name_tests
+{
+	use super::*;
+	use self::TlsClientHelloServerName::*;
+
+	fn length_prefixed(number_of_bytes: usize, value: &[u8]) -> Vec<u8>
+	{
+		let length = value.len().to_be_bytes();
+		let mut bytes = length[length.len() - number_of_bytes .. ].to_vec();
+		bytes.extend_from_slice(value);
+		bytes
+	}
+
+	fn server_name_extension(name_type: u8, name: &[u8]) -> Vec<u8>
+	{
+		let mut server_name = vec![name_type];
+		server_name.extend_from_slice(&length_prefixed(2, name));
+
+		let mut extension = vec![0x00, 0x00];
+		extension.extend_from_slice(&length_prefixed(2, &length_prefixed(2, &server_name)));
+		extension
+	}
+
+	fn client_hello(extensions: &[u8]) -> Vec<u8>
+	{
+		let mut body = vec![0x03, 0x03];
+		body.extend_from_slice(&[0xAA; 32]);
+		body.extend_from_slice(&length_prefixed(1, &[0xBB; 32]));
+		body.extend_from_slice(&length_prefixed(2, &[0x13, 0x01, 0x13, 0x02]));
+		body.extend_from_slice(&length_prefixed(1, &[0x00]));
+		body.extend_from_slice(&length_prefixed(2, extensions));
+
+		let mut handshake_message = vec![0x01];
+		handshake_message.extend_from_slice(&length_prefixed(3, &body));
+		handshake_message
+	}
+
+	fn records(handshake_message: &[u8], fragment_length: usize) -> Vec<u8>
+	{
+		let mut records = Vec::new();
+		for fragment in handshake_message.chunks(fragment_length)
+		{
+			records.extend_from_slice(&[0x16, 0x03, 0x01]);
+			records.extend_from_slice(&length_prefixed(2, fragment));
+		}
+		records
+	}
+
+	fn client_hello_with_host_name() -> Vec<u8>
+	{
+		let mut extensions = vec![0x00, 0x17, 0x00, 0x00];
+		extensions.extend_from_slice(&server_name_extension(0x00, b"example.com"));
+		client_hello(&extensions)
+	}
+
+	#[test]
+	fn host_name_in_one_record()
+	{
+		let records = records(&client_hello_with_host_name(), 16_384);
+
+		assert_eq!(TlsClientHelloServerName::parse(&records), Present(Cow::Borrowed("example.com")));
+	}
+
+	#[test]
+	fn host_name_in_fragmented_records()
+	{
+		let client_hello = client_hello_with_host_name();
+
+		for &fragment_length in [1, 3, 4, 5, 50, client_hello.len() - 1].iter()
+		{
+			let records = records(&client_hello, fragment_length);
+			assert_eq!(TlsClientHelloServerName::parse(&records), Present(Cow::Owned("example.com".to_string())), "fragment length {}", fragment_length);
+		}
+	}
+
+	#[test]
+	fn bytes_after_the_client_hello_are_ignored()
+	{
+		let mut records = records(&client_hello_with_host_name(), 50);
+		records.extend_from_slice(&[0x17, 0x03, 0x03, 0x00, 0x01, 0xFF]);
+
+		assert_eq!(TlsClientHelloServerName::parse(&records), Present(Cow::Owned("example.com".to_string())));
+	}
+
+	#[test]
+	fn truncated_records_need_more_bytes()
+	{
+		for &fragment_length in [16_384, 50].iter()
+		{
+			let records = records(&client_hello_with_host_name(), fragment_length);
+			for length in 0 .. records.len()
+			{
+				assert_eq!(TlsClientHelloServerName::parse(&records[.. length]), NeedMoreBytes, "fragment length {} length {}", fragment_length, length);
+			}
+		}
+	}
+
+	#[test]
+	fn absent()
+	{
+		let records = records(&client_hello(&[0x00, 0x17, 0x00, 0x00]), 16_384);
+
+		assert_eq!(TlsClientHelloServerName::parse(&records), Absent);
+	}
+
+	#[test]
+	fn not_a_handshake_record_is_absent()
+	{
+		assert_eq!(TlsClientHelloServerName::parse(b"GET / HTTP/1.1\r\n"), Absent);
+		assert_eq!(TlsClientHelloServerName::parse(&[0x15, 0x03, 0x01, 0x00, 0x02, 0x02, 0x28]), Absent);
+	}
+
+	#[test]
+	fn non_handshake_record_between_fragments_is_absent()
+	{
+		let client_hello = client_hello_with_host_name();
+		let mut records = records(&client_hello[.. 10], 10);
+		records.extend_from_slice(&[0x15, 0x03, 0x01, 0x00, 0x02, 0x01, 0x00]);
+		records.extend_from_slice(&self::records(&client_hello[10 .. ], 16_384));
+
+		assert_eq!(TlsClientHelloServerName::parse(&records), Absent);
+	}
+
+	#[test]
+	fn not_a_client_hello_is_absent()
+	{
+		let mut handshake_message = client_hello_with_host_name();
+		handshake_message[0] = 0x02;
+
+		assert_eq!(TlsClientHelloServerName::parse(&records(&handshake_message, 16_384)), Absent);
+	}
+
+	#[test]
+	fn malformed_is_absent()
+	{
+		let mut extension_length_too_long = client_hello(&server_name_extension(0x00, b"example.com"));
+		let length = extension_length_too_long.len();
+		extension_length_too_long[length - 17] = 0xFF;
+		assert_eq!(TlsClientHelloServerName::parse(&records(&extension_length_too_long, 16_384)), Absent);
+
+		let not_host_name_type = client_hello(&server_name_extension(0x01, b"example.com"));
+		assert_eq!(TlsClientHelloServerName::parse(&records(&not_host_name_type, 16_384)), Absent);
+
+		let not_utf_8 = client_hello(&server_name_extension(0x00, b"\xFF\xFE"));
+		assert_eq!(TlsClientHelloServerName::parse(&records(&not_utf_8, 16_384)), Absent);
+
+		let empty_client_hello = [0x01, 0x00, 0x00, 0x00];
+		assert_eq!(TlsClientHelloServerName::parse(&records(&empty_client_hello, 16_384)), Absent);
+	}
+}
@@ -0,0 +1,112 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A bounded cache of TLS client sessions, so that connections to a server seen before can resume rather than do a full handshake.
+///
+/// Sessions are keyed by rustls using the server's `DNSName`; when the cache is full, the oldest session is evicted.
+///
+/// Create one per thread, with a `ClientConfig` per thread (eg using `TlsClientStreamFactory::with_session_cache()`), so that the cache is never contended.
+#[derive(Debug)]
+pub struct TlsClientSessionCache
+{
+	maximum_sessions: usize,
+	sessions_and_oldest_first: Mutex<(HashMap<Vec<u8>, Vec<u8>>, VecDeque<Vec<u8>>)>,
+	session_cache_hits: AtomicUsize,
+	session_cache_misses: AtomicUsize,
+}
+
+impl StoresClientSessions for TlsClientSessionCache
+{
+	#[inline(always)]
+	fn put(&self, key: Vec<u8>, value: Vec<u8>) -> bool
+	{
+		let mut guard = self.sessions_and_oldest_first.lock().unwrap();
+		let &mut (ref mut sessions, ref mut oldest_first) = &mut *guard;
+
+		if sessions.insert(key.clone(), value).is_none()
+		{
+			oldest_first.push_back(key);
+			while sessions.len() > self.maximum_sessions
+			{
+				match oldest_first.pop_front()
+				{
+					None => break,
+
+					Some(oldest) =>
+					{
+						sessions.remove(&oldest);
+					}
+				}
+			}
+		}
+		true
+	}
+
+	#[inline(always)]
+	fn get(&self, key: &[u8]) -> Option<Vec<u8>>
+	{
+		let session = self.sessions_and_oldest_first.lock().unwrap().0.get(key).cloned();
+
+		// rustls also stores and retrieves other values (eg the key exchange group last used with a server).
+		if Self::is_session_key(key)
+		{
+			let counter = if session.is_some()
+			{
+				&self.session_cache_hits
+			}
+			else
+			{
+				&self.session_cache_misses
+			};
+			counter.fetch_add(1, Ordering::Relaxed);
+		}
+
+		session
+	}
+}
+
+impl TlsClientSessionCache
+{
+	/// Creates a new instance which holds at most `maximum_sessions` (which must not be zero).
+	#[inline(always)]
+	pub fn new(maximum_sessions: usize) -> Arc<Self>
+	{
+		debug_assert_ne!(maximum_sessions, 0, "maximum_sessions can not be zero");
+
+		Arc::new
+		(
+			Self
+			{
+				maximum_sessions,
+				sessions_and_oldest_first: Mutex::new((HashMap::default(), VecDeque::with_capacity(maximum_sessions))),
+				session_cache_hits: AtomicUsize::new(0),
+				session_cache_misses: AtomicUsize::new(0),
+			}
+		)
+	}
+
+	/// Number of handshakes for which a session was available to resume.
+	///
+	/// This is not the number of resumed handshakes, as a server can decline to resume a session, in which case a full handshake occurs.
+	#[inline(always)]
+	pub fn session_cache_hits(&self) -> usize
+	{
+		self.session_cache_hits.load(Ordering::Relaxed)
+	}
+
+	/// Number of handshakes for which no session was available, and so were full handshakes.
+	#[inline(always)]
+	pub fn session_cache_misses(&self) -> usize
+	{
+		self.session_cache_misses.load(Ordering::Relaxed)
+	}
+
+	#[inline(always)]
+	fn is_session_key(key: &[u8]) -> bool
+	{
+		const SessionKeyPrefix: &'static [u8] = b"session";
+
+		key.starts_with(SessionKeyPrefix)
+	}
+}
@@ -10,6 +10,11 @@ pub struct TlsClientStreamFactory
 
 	/// TLS session buffer size limit.
 	pub session_buffer_limit: usize,
+
+	/// Try to offload the encryption and decryption of records to the kernel (kTLS) after handshaking.
+	///
+	/// Needs a `tls_configuration` created with `with_kernel_tls_offload()`.
+	pub kernel_tls_offload: bool,
 }
 
 impl Debug for TlsClientStreamFactory
@@ -17,7 +22,47 @@ impl Debug for TlsClientStreamFactory
 	#[inline(always)]
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result
 	{
-		write!(f, "TlsClientStreamFactory {{ tls_configuration: _, session_buffer_limit: {:?} }}", self.session_buffer_limit)
+		write!(f, "TlsClientStreamFactory {{ tls_configuration: _, session_buffer_limit: {:?}, kernel_tls_offload: {:?} }}", self.session_buffer_limit, self.kernel_tls_offload)
+	}
+}
+
+impl TlsClientStreamFactory
+{
+	/// Creates a new instance which resumes sessions using `session_cache`.
+	///
+	/// `tls_configuration` and `session_cache` should not be shared with other threads.
+	#[inline(always)]
+	pub fn with_session_cache(mut tls_configuration: ClientConfig, session_buffer_limit: usize, session_cache: Arc<TlsClientSessionCache>) -> Self
+	{
+		tls_configuration.set_persistence(session_cache);
+
+		Self
+		{
+			tls_configuration: Arc::new(tls_configuration),
+			session_buffer_limit,
+			kernel_tls_offload: false,
+		}
+	}
+
+	/// Creates a new instance which tries to offload the encryption and decryption of records to the kernel (kTLS) after handshaking, so that, eg, `TlsClientStream::send_file()` uses `sendfile(2)`.
+	///
+	/// Offloading needs TLS 1.3 and a kernel which supports the negotiated cipher suite; otherwise, records continue to be encrypted and decrypted in userspace.
+	///
+	/// Any key log in `tls_configuration` is replaced.
+	/// Any session cache in `tls_configuration` (including the default one) is also replaced with one which stores nothing, so sessions are never resumed.
+	/// This is because new session tickets are received after handshaking, when the kernel, not rustls, decrypts records, and so are discarded; hence this can not be combined with `with_session_cache()` and a `TlsClientSessionCache`.
+	#[inline(always)]
+	pub fn with_kernel_tls_offload(mut tls_configuration: ClientConfig, session_buffer_limit: usize) -> Self
+	{
+		tls_configuration.key_log = Arc::new(TlsKernelOffloadKeyLog);
+		tls_configuration.set_persistence(Arc::new(NoClientSessionStorage {}));
+
+		Self
+		{
+			tls_configuration: Arc::new(tls_configuration),
+			session_buffer_limit,
+			kernel_tls_offload: true,
+		}
 	}
 }
 
@@ -38,7 +83,7 @@ impl<SD: SocketData> StreamFactory<SD> for TlsClientStreamFactory
 		let ascii_host_name = additional_arguments;
 
 		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
-		let stream = TlsClientStream::new(generic_stream, &self.tls_configuration, self.session_buffer_limit, ascii_host_name)?;
+		let stream = TlsClientStream::new(generic_stream, &self.tls_configuration, self.session_buffer_limit, ascii_host_name, self.kernel_tls_offload)?;
 
 		// Grotesque hack which extends lifetime from 'yielder to 'static.
 		let stream: Self::S = unsafe { transmute(stream) };
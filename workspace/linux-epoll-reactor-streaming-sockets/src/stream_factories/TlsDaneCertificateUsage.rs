@@ -0,0 +1,67 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// DNS-based Authentication of Named Entities (DANE) certificate usage of a `TLSA` record (RFC 6698 and RFC 7671).
+///
+/// Values are the same as those of the `TLSA` record (and those of `CertificateUsage` in the DNS protocol crate).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum TlsDaneCertificateUsage
+{
+	/// PKIX-TA.
+	///
+	/// A certificate in the server's chain must match and the chain must be valid using the public CA system.
+	PKIX_TA = 0,
+
+	/// PKIX-EE.
+	///
+	/// The server's (end entity) certificate must match and the chain must be valid using the public CA system.
+	PKIX_EE = 1,
+
+	/// DANE-TA.
+	///
+	/// A certificate in the server's chain (other than its own) must match and is used as the trust anchor to validate the chain, including the host name; the public CA system is not used.
+	DANE_TA = 2,
+
+	/// DANE-EE.
+	///
+	/// The server's (end entity) certificate must match; neither the chain, host name nor validity period are checked.
+	DANE_EE = 3,
+}
+
+impl TlsDaneCertificateUsage
+{
+	/// From a `TLSA` record's certificate usage field; `None` if unassigned or private.
+	#[inline(always)]
+	pub fn from_u8(certificate_usage: u8) -> Option<Self>
+	{
+		use self::TlsDaneCertificateUsage::*;
+
+		match certificate_usage
+		{
+			0 => Some(PKIX_TA),
+			1 => Some(PKIX_EE),
+			2 => Some(DANE_TA),
+			3 => Some(DANE_EE),
+			_ => None,
+		}
+	}
+}
+
+impl From<CertificateUsage> for TlsDaneCertificateUsage
+{
+	#[inline(always)]
+	fn from(certificate_usage: CertificateUsage) -> Self
+	{
+		use self::TlsDaneCertificateUsage::*;
+
+		match certificate_usage
+		{
+			CertificateUsage::PKIX_TA => PKIX_TA,
+			CertificateUsage::PKIX_EE => PKIX_EE,
+			CertificateUsage::DANE_TA => DANE_TA,
+			CertificateUsage::DANE_EE => DANE_EE,
+		}
+	}
+}
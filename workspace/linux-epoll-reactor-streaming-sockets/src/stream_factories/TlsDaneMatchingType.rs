@@ -0,0 +1,133 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// DNS-based Authentication of Named Entities (DANE) matching type and certificate association data of a `TLSA` record (RFC 6698).
+///
+/// The owned equivalent of `MatchingType` in the DNS protocol crate.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TlsDaneMatchingType
+{
+	/// The selected data must match exactly.
+	NoHashUsed(Vec<u8>),
+
+	/// The SHA2-256 digest (32 bytes) of the selected data must match.
+	Sha2_256(Vec<u8>),
+
+	/// The SHA2-512 digest (64 bytes) of the selected data must match.
+	Sha2_512(Vec<u8>),
+}
+
+impl TlsDaneMatchingType
+{
+	#[inline(always)]
+	pub(crate) fn matches(&self, selected_data: &[u8]) -> bool
+	{
+		use self::TlsDaneMatchingType::*;
+		use ::ring::digest::digest;
+		use ::ring::digest::SHA256;
+		use ::ring::digest::SHA512;
+
+		match self
+		{
+			&NoHashUsed(ref certificate_association_data) => selected_data == &certificate_association_data[..],
+
+			&Sha2_256(ref certificate_association_data) => digest(&SHA256, selected_data).as_ref() == &certificate_association_data[..],
+
+			&Sha2_512(ref certificate_association_data) => digest(&SHA512, selected_data).as_ref() == &certificate_association_data[..],
+		}
+	}
+}
+
+impl<'a> From<MatchingType<'a>> for TlsDaneMatchingType
+{
+	#[inline(always)]
+	fn from(matching_type: MatchingType<'a>) -> Self
+	{
+		use self::TlsDaneMatchingType::*;
+
+		match matching_type
+		{
+			MatchingType::NoHashUsed(certificate_association_data) => NoHashUsed(certificate_association_data.to_vec()),
+
+			MatchingType::Sha2_256(certificate_association_data) => Sha2_256(certificate_association_data.to_vec()),
+
+			MatchingType::Sha2_512(certificate_association_data) => Sha2_512(certificate_association_data.to_vec()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tls_dane_matching_type_tests
+{
+	use super::*;
+	use self::TlsDaneMatchingType::*;
+
+	const Sha2_256OfAbc: [u8; 32] = [0xBA, 0x78, 0x16, 0xBF, 0x8F, 0x01, 0xCF, 0xEA, 0x41, 0x41, 0x40, 0xDE, 0x5D, 0xAE, 0x22, 0x23, 0xB0, 0x03, 0x61, 0xA3, 0x96, 0x17, 0x7A, 0x9C, 0xB4, 0x10, 0xFF, 0x61, 0xF2, 0x00, 0x15, 0xAD];
+
+	const Sha2_512OfAbc: [u8; 64] = [0xDD, 0xAF, 0x35, 0xA1, 0x93, 0x61, 0x7A, 0xBA, 0xCC, 0x41, 0x73, 0x49, 0xAE, 0x20, 0x41, 0x31, 0x12, 0xE6, 0xFA, 0x4E, 0x89, 0xA9, 0x7E, 0xA2, 0x0A, 0x9E, 0xEE, 0xE6, 0x4B, 0x55, 0xD3, 0x9A, 0x21, 0x92, 0x99, 0x2A, 0x27, 0x4F, 0xC1, 0xA8, 0x36, 0xBA, 0x3C, 0x23, 0xA3, 0xFE, 0xEB, 0xBD, 0x45, 0x4D, 0x44, 0x23, 0x64, 0x3C, 0xE8, 0x0E, 0x2A, 0x9A, 0xC9, 0x4F, 0xA5, 0x4C, 0xA4, 0x9F];
+
+	#[test]
+	fn no_hash_used_matches_exactly()
+	{
+		let matching_type = NoHashUsed(b"abc".to_vec());
+
+		assert!(matching_type.matches(b"abc"));
+		assert!(!matching_type.matches(b"abd"));
+		assert!(!matching_type.matches(b"ab"));
+		assert!(!matching_type.matches(b"abcd"));
+		assert!(!matching_type.matches(b""));
+	}
+
+	#[test]
+	fn sha2_256_matches_digest()
+	{
+		assert!(Sha2_256(Sha2_256OfAbc.to_vec()).matches(b"abc"));
+		assert!(!Sha2_256(Sha2_256OfAbc.to_vec()).matches(b"abd"));
+	}
+
+	#[test]
+	fn sha2_512_matches_digest()
+	{
+		assert!(Sha2_512(Sha2_512OfAbc.to_vec()).matches(b"abc"));
+		assert!(!Sha2_512(Sha2_512OfAbc.to_vec()).matches(b"abd"));
+	}
+
+	#[test]
+	fn truncated_or_malformed_digest_does_not_match()
+	{
+		assert!(!Sha2_256(Sha2_256OfAbc[.. 31].to_vec()).matches(b"abc"));
+		assert!(!Sha2_256(Vec::new()).matches(b"abc"));
+		assert!(!Sha2_256(Sha2_512OfAbc.to_vec()).matches(b"abc"));
+		assert!(!Sha2_512(Sha2_512OfAbc[.. 63].to_vec()).matches(b"abc"));
+		assert!(!Sha2_512(Sha2_256OfAbc.to_vec()).matches(b"abc"));
+		assert!(!NoHashUsed(Sha2_256OfAbc.to_vec()).matches(b"abc"));
+	}
+
+	#[test]
+	fn from_dns_matching_type()
+	{
+		assert_eq!(TlsDaneMatchingType::from(MatchingType::NoHashUsed(b"abc")), NoHashUsed(b"abc".to_vec()));
+		assert_eq!(TlsDaneMatchingType::from(MatchingType::Sha2_256(&Sha2_256OfAbc)), Sha2_256(Sha2_256OfAbc.to_vec()));
+		assert_eq!(TlsDaneMatchingType::from(MatchingType::Sha2_512(&Sha2_512OfAbc)), Sha2_512(Sha2_512OfAbc.to_vec()));
+	}
+
+	#[test]
+	fn record_from_dns_record()
+	{
+		let record = DnsBasedAuthenticationOfNamedEntities
+		{
+			certificate_usage: CertificateUsage::DANE_EE,
+			selector: Selector::SubjectPublicKeyInformation,
+			matching_type: MatchingType::Sha2_256(&Sha2_256OfAbc),
+		};
+
+		let expected = TlsDaneRecord
+		{
+			certificate_usage: TlsDaneCertificateUsage::DANE_EE,
+			selector: TlsDaneSelector::SubjectPublicKeyInformation,
+			matching_type: Sha2_256(Sha2_256OfAbc.to_vec()),
+		};
+		assert_eq!(TlsDaneRecord::from(record), expected);
+	}
+}
@@ -0,0 +1,47 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A DNS-based Authentication of Named Entities (DANE) `TLSA` record for a server (RFC 6698 and RFC 7671).
+///
+/// Create using `From` from the `DnsBasedAuthenticationOfNamedEntities` records of a DNSSEC-validated `TLSA` query, eg for `_443._tcp.www.example.com`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TlsDaneRecord
+{
+	/// Certificate usage.
+	pub certificate_usage: TlsDaneCertificateUsage,
+
+	/// Selector.
+	pub selector: TlsDaneSelector,
+
+	/// Matching type and certificate association data.
+	pub matching_type: TlsDaneMatchingType,
+}
+
+impl TlsDaneRecord
+{
+	#[inline(always)]
+	pub(crate) fn matches(&self, certificate: &Certificate) -> bool
+	{
+		match self.selector.select(certificate)
+		{
+			None => false,
+
+			Some(selected_data) => self.matching_type.matches(selected_data),
+		}
+	}
+}
+
+impl<'a> From<DnsBasedAuthenticationOfNamedEntities<'a>> for TlsDaneRecord
+{
+	#[inline(always)]
+	fn from(record: DnsBasedAuthenticationOfNamedEntities<'a>) -> Self
+	{
+		Self
+		{
+			certificate_usage: record.certificate_usage.into(),
+			selector: record.selector.into(),
+			matching_type: record.matching_type.into(),
+		}
+	}
+}
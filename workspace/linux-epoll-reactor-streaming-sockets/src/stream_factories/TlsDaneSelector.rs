@@ -0,0 +1,62 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// DNS-based Authentication of Named Entities (DANE) selector of a `TLSA` record (RFC 6698).
+///
+/// Values are the same as those of the `TLSA` record (and those of `Selector` in the DNS protocol crate).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum TlsDaneSelector
+{
+	/// The full DER-encoded certificate.
+	FullCertificate = 0,
+
+	/// The DER-encoded `SubjectPublicKeyInfo` of the certificate.
+	SubjectPublicKeyInformation = 1,
+}
+
+impl TlsDaneSelector
+{
+	/// From a `TLSA` record's selector field; `None` if unassigned or private.
+	#[inline(always)]
+	pub fn from_u8(selector: u8) -> Option<Self>
+	{
+		use self::TlsDaneSelector::*;
+
+		match selector
+		{
+			0 => Some(FullCertificate),
+			1 => Some(SubjectPublicKeyInformation),
+			_ => None,
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn select<'a>(self, certificate: &'a Certificate) -> Option<&'a [u8]>
+	{
+		use self::TlsDaneSelector::*;
+
+		match self
+		{
+			FullCertificate => Some(&certificate.0[..]),
+
+			SubjectPublicKeyInformation => DerReader::certificate_subject_public_key_info(&certificate.0),
+		}
+	}
+}
+
+impl From<Selector> for TlsDaneSelector
+{
+	#[inline(always)]
+	fn from(selector: Selector) -> Self
+	{
+		use self::TlsDaneSelector::*;
+
+		match selector
+		{
+			Selector::FullCertificate => FullCertificate,
+			Selector::SubjectPublicKeyInformation => SubjectPublicKeyInformation,
+		}
+	}
+}
@@ -0,0 +1,170 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Produces stateless TLS session tickets, encrypted with ChaCha20-Poly1305 using a key which is rotated periodically.
+///
+/// Tickets encrypted with the previous key are still accepted for one rotation interval after it is rotated out, so a ticket is valid for between one and two rotation intervals.
+///
+/// Create one and share it (it is an `Arc`) between the `ServerConfig`s of all logical cores (threads) by setting `ServerConfig.ticketer`, so that a client can resume with any of them.
+pub struct TlsRotatingTicketer
+{
+	rotation_interval: Duration,
+	system_random: SystemRandom,
+	current_and_previous_keys: Mutex<TlsRotatingTicketerKeys>,
+	tickets_issued: AtomicUsize,
+	tickets_accepted: AtomicUsize,
+	tickets_rejected: AtomicUsize,
+}
+
+impl Debug for TlsRotatingTicketer
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "TlsRotatingTicketer {{ rotation_interval: {:?}, system_random: _, current_and_previous_keys: _, tickets_issued: {:?}, tickets_accepted: {:?}, tickets_rejected: {:?} }}", self.rotation_interval, self.tickets_issued, self.tickets_accepted, self.tickets_rejected)
+	}
+}
+
+impl ProducesTickets for TlsRotatingTicketer
+{
+	#[inline(always)]
+	fn enabled(&self) -> bool
+	{
+		true
+	}
+
+	#[inline(always)]
+	fn get_lifetime(&self) -> u32
+	{
+		self.rotation_interval.as_secs() as u32
+	}
+
+	fn encrypt(&self, plain: &[u8]) -> Option<Vec<u8>>
+	{
+		let (key_name, key) = self.current_key()?;
+
+		let mut nonce = [0u8; Self::NonceLength];
+		self.system_random.fill(&mut nonce).ok()?;
+
+		let sealing_key = SealingKey::new(&CHACHA20_POLY1305, &key).ok()?;
+		let tag_length = CHACHA20_POLY1305.tag_len();
+
+		let mut ticket = Vec::with_capacity(Self::HeaderLength + plain.len() + tag_length);
+		ticket.extend_from_slice(&key_name);
+		ticket.extend_from_slice(&nonce);
+		ticket.extend_from_slice(plain);
+		ticket.resize(Self::HeaderLength + plain.len() + tag_length, 0);
+
+		seal_in_place(&sealing_key, Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut ticket[Self::HeaderLength .. ], tag_length).ok()?;
+
+		self.tickets_issued.fetch_add(1, Ordering::Relaxed);
+		Some(ticket)
+	}
+
+	fn decrypt(&self, cipher: &[u8]) -> Option<Vec<u8>>
+	{
+		let plain = self.decrypt_ticket(cipher);
+
+		let counter = if plain.is_some()
+		{
+			&self.tickets_accepted
+		}
+		else
+		{
+			&self.tickets_rejected
+		};
+		counter.fetch_add(1, Ordering::Relaxed);
+
+		plain
+	}
+}
+
+impl TlsRotatingTicketer
+{
+	const NonceLength: usize = 12;
+
+	const HeaderLength: usize = TlsRotatingTicketerKeys::KeyNameLength + Self::NonceLength;
+
+	/// Creates a new instance.
+	///
+	/// Fails if random keys can not be generated.
+	#[inline(always)]
+	pub fn new(rotation_interval: Duration) -> Result<Arc<Self>, Unspecified>
+	{
+		let system_random = SystemRandom::new();
+		let current_and_previous_keys = TlsRotatingTicketerKeys::new(&system_random, Instant::now(), rotation_interval)?;
+
+		Ok
+		(
+			Arc::new
+			(
+				Self
+				{
+					rotation_interval,
+					current_and_previous_keys: Mutex::new(current_and_previous_keys),
+					system_random,
+					tickets_issued: AtomicUsize::new(0),
+					tickets_accepted: AtomicUsize::new(0),
+					tickets_rejected: AtomicUsize::new(0),
+				}
+			)
+		)
+	}
+
+	/// Number of tickets issued (after full and, depending on TLS version, resumed handshakes).
+	#[inline(always)]
+	pub fn tickets_issued(&self) -> usize
+	{
+		self.tickets_issued.load(Ordering::Relaxed)
+	}
+
+	/// Number of tickets presented by clients which could be decrypted; effectively, the number of resumed handshakes.
+	#[inline(always)]
+	pub fn tickets_accepted(&self) -> usize
+	{
+		self.tickets_accepted.load(Ordering::Relaxed)
+	}
+
+	/// Number of tickets presented by clients which could not be decrypted (eg because their key has been rotated out), causing full handshakes.
+	#[inline(always)]
+	pub fn tickets_rejected(&self) -> usize
+	{
+		self.tickets_rejected.load(Ordering::Relaxed)
+	}
+
+	#[inline(always)]
+	fn current_key(&self) -> Option<([u8; TlsRotatingTicketerKeys::KeyNameLength], [u8; TlsRotatingTicketerKeys::KeyLength])>
+	{
+		let mut current_and_previous_keys = self.current_and_previous_keys.lock().unwrap();
+		current_and_previous_keys.rotate_if_due(&self.system_random, Instant::now(), self.rotation_interval).ok()?;
+		Some(current_and_previous_keys.current_key())
+	}
+
+	#[inline(always)]
+	fn decrypt_ticket(&self, cipher: &[u8]) -> Option<Vec<u8>>
+	{
+		let tag_length = CHACHA20_POLY1305.tag_len();
+		if cipher.len() < Self::HeaderLength + tag_length
+		{
+			return None
+		}
+
+		let key_name = &cipher[.. TlsRotatingTicketerKeys::KeyNameLength];
+		let key =
+		{
+			let mut current_and_previous_keys = self.current_and_previous_keys.lock().unwrap();
+			current_and_previous_keys.rotate_if_due(&self.system_random, Instant::now(), self.rotation_interval).ok()?;
+			current_and_previous_keys.key(key_name)?
+		};
+
+		let mut nonce = [0u8; Self::NonceLength];
+		nonce.copy_from_slice(&cipher[TlsRotatingTicketerKeys::KeyNameLength .. Self::HeaderLength]);
+
+		let opening_key = OpeningKey::new(&CHACHA20_POLY1305, &key).ok()?;
+		let mut ciphertext_and_tag = cipher[Self::HeaderLength .. ].to_vec();
+		let plain_length = open_in_place(&opening_key, Nonce::assume_unique_for_key(nonce), Aad::empty(), 0, &mut ciphertext_and_tag).ok()?.len();
+		ciphertext_and_tag.truncate(plain_length);
+		Some(ciphertext_and_tag)
+	}
+}
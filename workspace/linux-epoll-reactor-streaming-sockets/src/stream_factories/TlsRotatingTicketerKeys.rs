@@ -0,0 +1,157 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// The current key and, for one rotation interval after it was rotated out, the previous key, of a `TlsRotatingTicketer`.
+pub(crate) struct TlsRotatingTicketerKeys
+{
+	current_key: ([u8; TlsRotatingTicketerKeys::KeyNameLength], [u8; TlsRotatingTicketerKeys::KeyLength]),
+	previous_key: Option<([u8; TlsRotatingTicketerKeys::KeyNameLength], [u8; TlsRotatingTicketerKeys::KeyLength])>,
+	rotate_at: Instant,
+}
+
+impl TlsRotatingTicketerKeys
+{
+	pub(crate) const KeyNameLength: usize = 16;
+
+	pub(crate) const KeyLength: usize = 32;
+
+	#[inline(always)]
+	pub(crate) fn new(system_random: &SystemRandom, now: Instant, rotation_interval: Duration) -> Result<Self, Unspecified>
+	{
+		Ok
+		(
+			Self
+			{
+				current_key: Self::new_key(system_random)?,
+				previous_key: None,
+				rotate_at: now + rotation_interval,
+			}
+		)
+	}
+
+	/// Rotates the current key if the rotation interval has elapsed; the previous key is discarded if it has been rotated out for at least one rotation interval.
+	///
+	/// Called when both encrypting and decrypting, so that a previous key does not outlive its rotation interval when no tickets are being issued.
+	#[inline(always)]
+	pub(crate) fn rotate_if_due(&mut self, system_random: &SystemRandom, now: Instant, rotation_interval: Duration) -> Result<(), Unspecified>
+	{
+		if likely!(now < self.rotate_at)
+		{
+			return Ok(())
+		}
+
+		let new_key = Self::new_key(system_random)?;
+		self.previous_key = if now >= self.rotate_at + rotation_interval
+		{
+			None
+		}
+		else
+		{
+			Some(self.current_key)
+		};
+		self.current_key = new_key;
+		self.rotate_at = now + rotation_interval;
+		Ok(())
+	}
+
+	#[inline(always)]
+	pub(crate) fn current_key(&self) -> ([u8; Self::KeyNameLength], [u8; Self::KeyLength])
+	{
+		self.current_key
+	}
+
+	/// Finds the current or previous key named `key_name`.
+	#[inline(always)]
+	pub(crate) fn key(&self, key_name: &[u8]) -> Option<[u8; Self::KeyLength]>
+	{
+		if &self.current_key.0[..] == key_name
+		{
+			return Some(self.current_key.1)
+		}
+
+		match self.previous_key
+		{
+			Some((ref previous_key_name, previous_key)) if &previous_key_name[..] == key_name => Some(previous_key),
+
+			_ => None,
+		}
+	}
+
+	#[inline(always)]
+	fn new_key(system_random: &SystemRandom) -> Result<([u8; Self::KeyNameLength], [u8; Self::KeyLength]), Unspecified>
+	{
+		let mut key_name = [0u8; Self::KeyNameLength];
+		system_random.fill(&mut key_name)?;
+
+		let mut key = [0u8; Self::KeyLength];
+		system_random.fill(&mut key)?;
+
+		Ok((key_name, key))
+	}
+}
+
+#[cfg(test)]
+mod tls_rotating_ticketer_keys_tests
+{
+	use super::*;
+
+	const RotationInterval: Duration = Duration::from_secs(3600);
+
+	#[test]
+	fn not_rotated_before_rotation_interval_has_elapsed()
+	{
+		let system_random = SystemRandom::new();
+		let now = Instant::now();
+		let mut keys = TlsRotatingTicketerKeys::new(&system_random, now, RotationInterval).unwrap();
+		let (key_name, key) = keys.current_key();
+
+		keys.rotate_if_due(&system_random, now + RotationInterval - Duration::from_secs(1), RotationInterval).unwrap();
+
+		assert_eq!(keys.current_key(), (key_name, key));
+		assert_eq!(keys.key(&key_name), Some(key));
+	}
+
+	#[test]
+	fn previous_key_is_accepted_for_one_rotation_interval()
+	{
+		let system_random = SystemRandom::new();
+		let now = Instant::now();
+		let mut keys = TlsRotatingTicketerKeys::new(&system_random, now, RotationInterval).unwrap();
+		let (previous_key_name, previous_key) = keys.current_key();
+
+		let rotated_at = now + RotationInterval;
+		keys.rotate_if_due(&system_random, rotated_at, RotationInterval).unwrap();
+		let (current_key_name, current_key) = keys.current_key();
+		assert_ne!(current_key_name, previous_key_name);
+		assert_eq!(keys.key(&current_key_name), Some(current_key));
+		assert_eq!(keys.key(&previous_key_name), Some(previous_key));
+
+		keys.rotate_if_due(&system_random, rotated_at + RotationInterval, RotationInterval).unwrap();
+		assert_eq!(keys.key(&previous_key_name), None);
+		assert_eq!(keys.key(&current_key_name), Some(current_key));
+	}
+
+	#[test]
+	fn previous_key_is_discarded_if_rotation_is_overdue_by_a_rotation_interval()
+	{
+		let system_random = SystemRandom::new();
+		let now = Instant::now();
+		let mut keys = TlsRotatingTicketerKeys::new(&system_random, now, RotationInterval).unwrap();
+		let (key_name, _) = keys.current_key();
+
+		keys.rotate_if_due(&system_random, now + RotationInterval + RotationInterval, RotationInterval).unwrap();
+
+		assert_eq!(keys.key(&key_name), None);
+	}
+
+	#[test]
+	fn unknown_key_name()
+	{
+		let system_random = SystemRandom::new();
+		let keys = TlsRotatingTicketerKeys::new(&system_random, Instant::now(), RotationInterval).unwrap();
+
+		assert_eq!(keys.key(&[0u8; TlsRotatingTicketerKeys::KeyNameLength]), None);
+		assert_eq!(keys.key(&[]), None);
+	}
+}
@@ -0,0 +1,43 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// How to authenticate a particular server other than by (just) the public CA system.
+///
+/// If both DANE records and public key pins are supplied, both must be satisfied.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TlsServerAuthentication
+{
+	/// DANE `TLSA` records; the server is authenticated if any one of them is satisfied.
+	///
+	/// These should only be used if they were obtained using DNSSEC.
+	pub dane_records: Vec<TlsDaneRecord>,
+
+	/// SHA-256 fingerprints of DER-encoded `SubjectPublicKeyInfo`s; the server is authenticated if any one of them matches the server's own certificate, or matches a certificate in the server's chain which is then used as the trust anchor to validate the chain, including the host name.
+	pub subject_public_key_info_sha256_pins: Vec<[u8; 32]>,
+}
+
+impl TlsServerAuthentication
+{
+	#[inline(always)]
+	pub(crate) fn pins_as_dane_records(&self) -> Vec<TlsDaneRecord>
+	{
+		let mut dane_records = Vec::with_capacity(self.subject_public_key_info_sha256_pins.len() * 2);
+		for pin in self.subject_public_key_info_sha256_pins.iter()
+		{
+			for &certificate_usage in &[TlsDaneCertificateUsage::DANE_EE, TlsDaneCertificateUsage::DANE_TA]
+			{
+				dane_records.push
+				(
+					TlsDaneRecord
+					{
+						certificate_usage,
+						selector: TlsDaneSelector::SubjectPublicKeyInformation,
+						matching_type: TlsDaneMatchingType::Sha2_256(pin.to_vec()),
+					}
+				)
+			}
+		}
+		dane_records
+	}
+}
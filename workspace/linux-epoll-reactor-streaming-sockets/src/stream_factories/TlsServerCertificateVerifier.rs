@@ -0,0 +1,170 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Verifies TLS servers' certificate chains using DNS-based Authentication of Named Entities (DANE) `TLSA` records and public key pins, falling back to the public CA system (using the `ClientConfig`'s root certificates) for servers without either.
+///
+/// Install in a `ClientConfig` using `TlsServerCertificateVerifier::install()`; it can be shared between threads, and servers' authentication can be changed at any time (eg as `TLSA` records are looked up or expire).
+pub struct TlsServerCertificateVerifier
+{
+	server_authentications: RwLock<HashMap<String, Arc<TlsServerAuthentication>>>,
+}
+
+impl Debug for TlsServerCertificateVerifier
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "TlsServerCertificateVerifier {{ server_authentications: {:?} }}", self.server_authentications.read().unwrap())
+	}
+}
+
+impl ServerCertVerifier for TlsServerCertificateVerifier
+{
+	fn verify_server_cert(&self, roots: &RootCertStore, presented_certs: &[Certificate], dns_name: DNSNameRef, _ocsp_response: &[u8]) -> Result<ServerCertVerified, TLSError>
+	{
+		let host_name: &str = dns_name.into();
+		let server_authentication = self.server_authentications.read().unwrap().get(&Self::normalize(host_name)).cloned();
+
+		let server_authentication = match server_authentication
+		{
+			None => return Self::verify_using_public_key_infrastructure(roots, presented_certs, dns_name),
+
+			Some(server_authentication) => server_authentication,
+		};
+
+		if presented_certs.is_empty()
+		{
+			return Err(TLSError::NoCertificatesPresented)
+		}
+
+		let has_dane_records = !server_authentication.dane_records.is_empty();
+		let has_pins = !server_authentication.subject_public_key_info_sha256_pins.is_empty();
+
+		if has_dane_records && !self.satisfies_any(&server_authentication.dane_records, roots, presented_certs, dns_name)
+		{
+			return Err(TLSError::General(format!("Server certificate chain for '{}' does not satisfy any DANE TLSA record", host_name)))
+		}
+
+		if has_pins && !self.satisfies_any(&server_authentication.pins_as_dane_records(), roots, presented_certs, dns_name)
+		{
+			return Err(TLSError::General(format!("Server certificate chain for '{}' does not match any public key pin", host_name)))
+		}
+
+		if !has_dane_records && !has_pins
+		{
+			return Self::verify_using_public_key_infrastructure(roots, presented_certs, dns_name)
+		}
+
+		Ok(ServerCertVerified::assertion())
+	}
+}
+
+impl TlsServerCertificateVerifier
+{
+	/// Creates a new instance.
+	#[inline(always)]
+	pub fn new() -> Arc<Self>
+	{
+		Arc::new
+		(
+			Self
+			{
+				server_authentications: RwLock::new(HashMap::default()),
+			}
+		)
+	}
+
+	/// Installs `this` as the verifier of server certificates in `tls_configuration`.
+	#[inline(always)]
+	pub fn install(this: &Arc<Self>, tls_configuration: &mut ClientConfig)
+	{
+		tls_configuration.dangerous().set_certificate_verifier(this.clone())
+	}
+
+	/// Sets (or replaces) how a server, identified by its host name, is authenticated.
+	#[inline(always)]
+	pub fn set_server_authentication(&self, host_name: &str, server_authentication: TlsServerAuthentication)
+	{
+		self.server_authentications.write().unwrap().insert(Self::normalize(host_name), Arc::new(server_authentication));
+	}
+
+	/// Removes how a server, identified by its host name, is authenticated, so that it is authenticated using the public CA system.
+	#[inline(always)]
+	pub fn remove_server_authentication(&self, host_name: &str)
+	{
+		self.server_authentications.write().unwrap().remove(&Self::normalize(host_name));
+	}
+
+	fn satisfies_any(&self, dane_records: &[TlsDaneRecord], roots: &RootCertStore, presented_certs: &[Certificate], dns_name: DNSNameRef) -> bool
+	{
+		use self::TlsDaneCertificateUsage::*;
+
+		let end_entity_certificate = &presented_certs[0];
+		let mut cached_public_key_infrastructure_validity = None;
+		let mut public_key_infrastructure_is_valid = ||
+		{
+			*cached_public_key_infrastructure_validity.get_or_insert_with(|| Self::verify_using_public_key_infrastructure(roots, presented_certs, dns_name).is_ok())
+		};
+
+		for dane_record in dane_records
+		{
+			let satisfied = match dane_record.certificate_usage
+			{
+				PKIX_TA => presented_certs.iter().any(|certificate| dane_record.matches(certificate)) && public_key_infrastructure_is_valid(),
+
+				PKIX_EE => dane_record.matches(end_entity_certificate) && public_key_infrastructure_is_valid(),
+
+				DANE_TA => presented_certs[1 .. ].iter().any(|certificate| dane_record.matches(certificate) && Self::is_valid_with_trust_anchor(certificate, presented_certs, dns_name)),
+
+				DANE_EE => dane_record.matches(end_entity_certificate),
+			};
+
+			if satisfied
+			{
+				return true
+			}
+		}
+
+		false
+	}
+
+	#[inline(always)]
+	fn is_valid_with_trust_anchor(trust_anchor: &Certificate, presented_certs: &[Certificate], dns_name: DNSNameRef) -> bool
+	{
+		let mut trust_anchors = RootCertStore::empty();
+		if trust_anchors.add(trust_anchor).is_err()
+		{
+			return false
+		}
+		Self::verify_using_public_key_infrastructure(&trust_anchors, presented_certs, dns_name).is_ok()
+	}
+
+	/// Verifies that `presented_certs` chain to one of `roots` and are valid for `dns_name`, as rustls' own (private) verifier does.
+	fn verify_using_public_key_infrastructure(roots: &RootCertStore, presented_certs: &[Certificate], dns_name: DNSNameRef) -> Result<ServerCertVerified, TLSError>
+	{
+		if presented_certs.is_empty()
+		{
+			return Err(TLSError::NoCertificatesPresented)
+		}
+
+		let end_entity_certificate = EndEntityCert::from(Input::from(&presented_certs[0].0)).map_err(TLSError::WebPKIError)?;
+		let intermediate_certificates: Vec<Input> = presented_certs[1 .. ].iter().map(|certificate| Input::from(&certificate.0)).collect();
+		let trust_anchors: Vec<TrustAnchor> = roots.roots.iter().map(|root| root.to_trust_anchor()).collect();
+		let now = Time::try_from(SystemTime::now()).map_err(|_| TLSError::FailedToGetCurrentTime)?;
+
+		// The same signature algorithms as rustls' own verifier.
+		let supported_signature_algorithms: [&SignatureAlgorithm; 11] = [&::webpki::ECDSA_P256_SHA256, &::webpki::ECDSA_P256_SHA384, &::webpki::ECDSA_P384_SHA256, &::webpki::ECDSA_P384_SHA384, &::webpki::RSA_PSS_2048_8192_SHA256_LEGACY_KEY, &::webpki::RSA_PSS_2048_8192_SHA384_LEGACY_KEY, &::webpki::RSA_PSS_2048_8192_SHA512_LEGACY_KEY, &::webpki::RSA_PKCS1_2048_8192_SHA256, &::webpki::RSA_PKCS1_2048_8192_SHA384, &::webpki::RSA_PKCS1_2048_8192_SHA512, &::webpki::RSA_PKCS1_3072_8192_SHA384];
+
+		end_entity_certificate.verify_is_valid_tls_server_cert(&supported_signature_algorithms[..], &TLSServerTrustAnchors(&trust_anchors), &intermediate_certificates, now).map_err(TLSError::WebPKIError)?;
+		end_entity_certificate.verify_is_valid_for_dns_name(dns_name).map_err(TLSError::WebPKIError)?;
+
+		Ok(ServerCertVerified::assertion())
+	}
+
+	#[inline(always)]
+	fn normalize(host_name: &str) -> String
+	{
+		host_name.trim_end_matches('.').to_ascii_lowercase()
+	}
+}
@@ -0,0 +1,188 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// TLS server configurations (certificate chain, client authentication policy, cipher suites, etc) selected by the Server Name Indication (SNI) host name sent by a client.
+///
+/// Host names are matched exactly first, then by wildcard (eg `*.example.com` matches `www.example.com` but neither `example.com` nor `a.b.example.com`), then the default is used.
+/// Host names are case-insensitive.
+pub struct TlsServerNameIndicationConfigurations
+{
+	exact: HashMap<String, Arc<ServerConfig>>,
+	wildcard: HashMap<String, Arc<ServerConfig>>,
+	default: Option<Arc<ServerConfig>>,
+}
+
+impl Debug for TlsServerNameIndicationConfigurations
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "TlsServerNameIndicationConfigurations {{ exact: {:?}, wildcard: {:?}, default: {:?} }}", self.exact.keys().collect::<Vec<_>>(), self.wildcard.keys().collect::<Vec<_>>(), self.default.is_some())
+	}
+}
+
+impl Default for TlsServerNameIndicationConfigurations
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			exact: HashMap::default(),
+			wildcard: HashMap::default(),
+			default: None,
+		}
+	}
+}
+
+impl TlsServerNameIndicationConfigurations
+{
+	/// Name (without the `.pem` extension) of the PEM file used for the default configuration by `load_from_directory()`.
+	pub const DefaultFileStem: &'static str = "default";
+
+	/// Loads from a directory of PEM files, each containing a certificate chain (leaf first) and a PKCS#8 or RSA private key.
+	///
+	/// Each file is named after its host name, eg `www.example.com.pem` or `*.example.com.pem`, or is `default.pem`; files without a `.pem` extension are ignored.
+	///
+	/// `tls_configuration_for_host_name` creates the configuration for a host name (`None` for the default), so setting, eg, its client authentication policy and cipher suites; its certificate chain and private key are then set from the file.
+	pub fn load_from_directory(directory_path: &Path, tls_configuration_for_host_name: impl Fn(Option<&str>) -> ServerConfig) -> Result<Self, TlsServerNameIndicationConfigurationsLoadError>
+	{
+		use self::TlsServerNameIndicationConfigurationsLoadError::*;
+		use ::std::fs::read;
+		use ::std::fs::read_dir;
+
+		let mut this = Self::default();
+
+		for directory_entry in read_dir(directory_path).map_err(DirectoryRead)?
+		{
+			let file_path = directory_entry.map_err(DirectoryRead)?.path();
+			if file_path.extension() != Some(OsStr::new("pem")) || !file_path.is_file()
+			{
+				continue
+			}
+
+			let host_name = match file_path.file_stem().and_then(OsStr::to_str)
+			{
+				Some(Self::DefaultFileStem) => None,
+
+				Some(host_name) if Self::is_valid_host_name(host_name) => Some(host_name.to_string()),
+
+				_ => return Err(InvalidHostName(file_path)),
+			};
+
+			let pem_file = match read(&file_path)
+			{
+				Err(error) => return Err(FileRead(file_path, error)),
+
+				Ok(pem_file) => pem_file,
+			};
+
+			let certificate_chain = match ::rustls::internal::pemfile::certs(&mut &pem_file[..])
+			{
+				Ok(ref certificate_chain) if certificate_chain.is_empty() => return Err(NoCertificates(file_path)),
+
+				Err(()) => return Err(NoCertificates(file_path)),
+
+				Ok(certificate_chain) => certificate_chain,
+			};
+
+			let private_key = match ::rustls::internal::pemfile::pkcs8_private_keys(&mut &pem_file[..]).ok().and_then(|mut private_keys| private_keys.pop())
+			{
+				Some(private_key) => private_key,
+
+				None => match ::rustls::internal::pemfile::rsa_private_keys(&mut &pem_file[..]).ok().and_then(|mut private_keys| private_keys.pop())
+				{
+					Some(private_key) => private_key,
+
+					None => return Err(NoPrivateKey(file_path)),
+				},
+			};
+
+			let mut tls_configuration = tls_configuration_for_host_name(host_name.as_ref().map(String::as_str));
+			if let Err(error) = tls_configuration.set_single_cert(certificate_chain, private_key)
+			{
+				return Err(InvalidCertificateChainOrPrivateKey(file_path, error))
+			}
+
+			let tls_configuration = Arc::new(tls_configuration);
+			match host_name
+			{
+				None => this.set_default(tls_configuration),
+
+				Some(host_name) => this.add(&host_name, tls_configuration),
+			}
+		}
+
+		Ok(this)
+	}
+
+	/// Adds (or replaces) a configuration for a host name, which may be a wildcard (eg `*.example.com`).
+	#[inline(always)]
+	pub fn add(&mut self, host_name: &str, tls_configuration: Arc<ServerConfig>)
+	{
+		let host_name = Self::normalize(host_name);
+
+		if host_name.starts_with("*.")
+		{
+			self.wildcard.insert(host_name[2 .. ].to_string(), tls_configuration);
+		}
+		else
+		{
+			self.exact.insert(host_name, tls_configuration);
+		}
+	}
+
+	/// Sets (or replaces) the configuration used when no host name was sent or no host name matched.
+	#[inline(always)]
+	pub fn set_default(&mut self, tls_configuration: Arc<ServerConfig>)
+	{
+		self.default = Some(tls_configuration)
+	}
+
+	/// Selects a configuration for a host name.
+	#[inline(always)]
+	pub fn select(&self, server_name_indication: Option<&str>) -> Option<&Arc<ServerConfig>>
+	{
+		if let Some(host_name) = server_name_indication
+		{
+			let host_name = Self::normalize(host_name);
+
+			if let Some(tls_configuration) = self.exact.get(&host_name)
+			{
+				return Some(tls_configuration)
+			}
+
+			if let Some(index) = host_name.find('.')
+			{
+				if let Some(tls_configuration) = self.wildcard.get(&host_name[index + 1 .. ])
+				{
+					return Some(tls_configuration)
+				}
+			}
+		}
+
+		self.default.as_ref()
+	}
+
+	#[inline(always)]
+	fn normalize(host_name: &str) -> String
+	{
+		host_name.trim_end_matches('.').to_ascii_lowercase()
+	}
+
+	#[inline(always)]
+	fn is_valid_host_name(host_name: &str) -> bool
+	{
+		let host_name = if host_name.starts_with("*.")
+		{
+			&host_name[2 .. ]
+		}
+		else
+		{
+			host_name
+		};
+
+		!host_name.is_empty() && host_name.len() <= 253 && host_name.split('.').all(|label| !label.is_empty() && label.len() <= 63 && label.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-'))
+	}
+}
@@ -0,0 +1,59 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// An error when loading `TlsServerNameIndicationConfigurations` from a directory.
+#[derive(Debug)]
+pub enum TlsServerNameIndicationConfigurationsLoadError
+{
+	/// The directory could not be read.
+	DirectoryRead(io::Error),
+
+	/// A PEM file could not be read.
+	FileRead(PathBuf, io::Error),
+
+	/// A PEM file's name is not a valid host name (or `default`).
+	InvalidHostName(PathBuf),
+
+	/// A PEM file did not contain any (valid) certificates.
+	NoCertificates(PathBuf),
+
+	/// A PEM file did not contain a (valid) PKCS#8 or RSA private key.
+	NoPrivateKey(PathBuf),
+
+	/// A PEM file's certificate chain and private key were not accepted.
+	InvalidCertificateChainOrPrivateKey(PathBuf, TLSError),
+}
+
+impl Display for TlsServerNameIndicationConfigurationsLoadError
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		Debug::fmt(self, f)
+	}
+}
+
+impl error::Error for TlsServerNameIndicationConfigurationsLoadError
+{
+	#[inline(always)]
+	fn source(&self) -> Option<&(error::Error + 'static)>
+	{
+		use self::TlsServerNameIndicationConfigurationsLoadError::*;
+
+		match self
+		{
+			&DirectoryRead(ref error) => Some(error),
+
+			&FileRead(_, ref error) => Some(error),
+
+			&InvalidHostName(_) => None,
+
+			&NoCertificates(_) => None,
+
+			&NoPrivateKey(_) => None,
+
+			&InvalidCertificateChainOrPrivateKey(_, ref error) => Some(error),
+		}
+	}
+}
@@ -0,0 +1,116 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A factory to create instances of `TlsServerStream` using a TLS server configuration selected by the Server Name Indication (SNI) host name sent by the client, so that one listener can terminate TLS for many virtual hosts.
+///
+/// The client's `ClientHello` is peeked at (not consumed) to find the host name before the TLS handshake starts; if it is fragmented across more than one TLS record, the records are reassembled.
+/// Connections for which no configuration can be selected are finished with an error.
+pub struct TlsServerNameIndicationStreamFactory
+{
+	configurations: RefCell<Arc<TlsServerNameIndicationConfigurations>>,
+
+	/// TLS session buffer size limit.
+	pub session_buffer_limit: usize,
+}
+
+impl Debug for TlsServerNameIndicationStreamFactory
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "TlsServerNameIndicationStreamFactory {{ configurations: {:?}, session_buffer_limit: {:?} }}", self.configurations.borrow(), self.session_buffer_limit)
+	}
+}
+
+impl<SD: SocketData> StreamFactory<SD> for TlsServerNameIndicationStreamFactory
+{
+	/// This is a lie; the lifetime is ***NOT*** `'static` but actually `'yielder` in `new_stream_and_handshake()`.
+	type S = TlsServerStream<'static, SD>;
+
+	type ProxyOrTunnelInformation = ();
+
+	type AdditionalArguments = ();
+
+	#[inline(always)]
+	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, _additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
+	{
+		let mut generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
+		let tls_configuration = self.select_tls_configuration(&mut generic_stream)?;
+		let stream = TlsServerStream::new(generic_stream, &tls_configuration, self.session_buffer_limit, false)?;
+
+		// Grotesque hack which extends lifetime from 'yielder to 'static.
+		let stream: Self::S = unsafe { transmute(stream) };
+		Ok((stream, ()))
+	}
+}
+
+impl TlsServerNameIndicationStreamFactory
+{
+	/// Creates a new instance.
+	#[inline(always)]
+	pub fn new(configurations: Arc<TlsServerNameIndicationConfigurations>, session_buffer_limit: usize) -> Self
+	{
+		Self
+		{
+			configurations: RefCell::new(configurations),
+			session_buffer_limit,
+		}
+	}
+
+	/// Replaces the configurations, eg after re-loading them using `TlsServerNameIndicationConfigurations::load_from_directory()`.
+	///
+	/// Connections already handshaking or established are unaffected.
+	/// As factories are per-thread, this needs to be done for each thread's factory; `configurations` can be loaded once and shared.
+	#[inline(always)]
+	pub fn reload(&self, configurations: Arc<TlsServerNameIndicationConfigurations>)
+	{
+		*self.configurations.borrow_mut() = configurations
+	}
+
+	#[inline(always)]
+	fn select_tls_configuration<'yielder, SD: SocketData>(&self, generic_stream: &mut GenericStream<'yielder, SD>) -> Result<Arc<ServerConfig>, CompleteError>
+	{
+		use self::TlsClientHelloServerName::*;
+
+		let mut records = vec![0u8; TlsClientHelloServerName::MaximumRecordLength];
+		let mut bytes_peeked = 0;
+		let server_name_indication = loop
+		{
+			bytes_peeked = generic_stream.peek_more_data(&mut records[..], bytes_peeked)?;
+			if unlikely!(bytes_peeked == 0)
+			{
+				return Err(CompleteError::RemotePeerClosedCleanly)
+			}
+
+			match TlsClientHelloServerName::parse(&records[.. bytes_peeked])
+			{
+				NeedMoreBytes => (),
+
+				Absent => break None,
+
+				Present(host_name) => break Some(host_name.into_owned()),
+			}
+
+			// A `ClientHello` fragmented across more than one record may not fit.
+			if bytes_peeked == records.len()
+			{
+				if unlikely!(records.len() == TlsClientHelloServerName::MaximumRecordsLength)
+				{
+					return Err(CompleteError::ProtocolViolation(format!("TLS ClientHello records exceed {} bytes", TlsClientHelloServerName::MaximumRecordsLength).into()))
+				}
+
+				let length = min(records.len() * 2, TlsClientHelloServerName::MaximumRecordsLength);
+				records.resize(length, 0);
+			}
+		};
+
+		let configurations = self.configurations.borrow().clone();
+		match configurations.select(server_name_indication.as_ref().map(String::as_str))
+		{
+			Some(tls_configuration) => Ok(tls_configuration.clone()),
+
+			None => Err(CompleteError::ProtocolViolation(format!("No TLS configuration for Server Name Indication host name {:?}", server_name_indication).into())),
+		}
+	}
+}
@@ -3,13 +3,22 @@
 
 
 /// A factory to create instances of `TlsServerStream`.
+///
+/// TLS 1.3 0-RTT early data is always rejected (rustls does not yet support accepting it), so clients fall back to sending it after the handshake; replay protection is therefore not required.
 pub struct TlsServerStreamFactory
 {
 	/// TLS server configuration.
+	///
+	/// Use `TlsClientAuthentication::server_configuration()` to create one which requires or permits client certificates (mutual TLS).
 	pub tls_configuration: Arc<ServerConfig>,
 
 	/// TLS session buffer size limit.
 	pub session_buffer_limit: usize,
+
+	/// Try to offload the encryption and decryption of records to the kernel (kTLS) after handshaking.
+	///
+	/// Needs a `tls_configuration` created with `with_kernel_tls_offload()`.
+	pub kernel_tls_offload: bool,
 }
 
 impl Debug for TlsServerStreamFactory
@@ -17,7 +26,48 @@ impl Debug for TlsServerStreamFactory
 	#[inline(always)]
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result
 	{
-		write!(f, "TlsServerStreamFactory {{ tls_configuration: _, session_buffer_limit: {:?} }}", self.session_buffer_limit)
+		write!(f, "TlsServerStreamFactory {{ tls_configuration: _, session_buffer_limit: {:?}, kernel_tls_offload: {:?} }}", self.session_buffer_limit, self.kernel_tls_offload)
+	}
+}
+
+impl TlsServerStreamFactory
+{
+	/// Creates a new instance which advertises, in preference order, the protocols of an `ApplicationLayerProtocolNegotiationStreamUser`, which should then be used as the `StreamUser`.
+	///
+	/// Any protocols previously set in `tls_configuration` are replaced.
+	/// If `kernel_tls_offload` is `true`, this tries to offload the encryption and decryption of records to the kernel (kTLS) after handshaking, as for `with_kernel_tls_offload()`.
+	#[inline(always)]
+	pub fn for_application_layer_protocol_negotiation<SD: SocketData, ProxyOrTunnelInformation>(mut tls_configuration: ServerConfig, session_buffer_limit: usize, stream_user: &ApplicationLayerProtocolNegotiationStreamUser<SD, ProxyOrTunnelInformation>, kernel_tls_offload: bool) -> Self
+	{
+		tls_configuration.set_protocols(stream_user.protocols());
+
+		Self::new(tls_configuration, session_buffer_limit, kernel_tls_offload)
+	}
+
+	/// Creates a new instance which tries to offload the encryption and decryption of records to the kernel (kTLS) after handshaking, so that, eg, `TlsServerStream::send_file()` uses `sendfile(2)`.
+	///
+	/// Offloading needs TLS 1.3 and a kernel which supports the negotiated cipher suite; otherwise, records continue to be encrypted and decrypted in userspace.
+	/// Any key log in `tls_configuration` is replaced.
+	#[inline(always)]
+	pub fn with_kernel_tls_offload(tls_configuration: ServerConfig, session_buffer_limit: usize) -> Self
+	{
+		Self::new(tls_configuration, session_buffer_limit, true)
+	}
+
+	#[inline(always)]
+	fn new(mut tls_configuration: ServerConfig, session_buffer_limit: usize, kernel_tls_offload: bool) -> Self
+	{
+		if kernel_tls_offload
+		{
+			tls_configuration.key_log = Arc::new(TlsKernelOffloadKeyLog);
+		}
+
+		Self
+		{
+			tls_configuration: Arc::new(tls_configuration),
+			session_buffer_limit,
+			kernel_tls_offload,
+		}
 	}
 }
 
@@ -35,7 +85,7 @@ impl<SD: SocketData> StreamFactory<SD> for TlsServerStreamFactory
 	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, _additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
 	{
 		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
-		let stream = TlsServerStream::new(generic_stream, &self.tls_configuration, self.session_buffer_limit)?;
+		let stream = TlsServerStream::new(generic_stream, &self.tls_configuration, self.session_buffer_limit, self.kernel_tls_offload)?;
 
 		// Grotesque hack which extends lifetime from 'yielder to 'static.
 		let stream: Self::S = unsafe { transmute(stream) };
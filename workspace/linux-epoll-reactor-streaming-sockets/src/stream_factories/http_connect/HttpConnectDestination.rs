@@ -0,0 +1,87 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Destination to ask a HTTP CONNECT proxy to open a tunnel to.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpConnectDestination
+{
+	/// Host name, Internet Protocol version 4 address or Internet Protocol version 6 address (without surrounding square brackets).
+	pub host: String,
+
+	/// Port.
+	pub port: u16,
+}
+
+impl HttpConnectDestination
+{
+	/// Authority, eg `server.example.com:443` or `[2001:db8::1]:443`.
+	///
+	/// Fails with `CompleteError::InvalidDataSupplied` if `host` is not an Internet Protocol version 6 address, an Internet Protocol version 4 address or a RFC 3986 `reg-name`.
+	#[inline(always)]
+	pub fn authority(&self) -> Result<String, CompleteError>
+	{
+		let host = &self.host[..];
+
+		if host.parse::<Ipv6Addr>().is_ok()
+		{
+			return Ok(format!("[{}]:{}", host, self.port))
+		}
+
+		if host.parse::<Ipv4Addr>().is_ok() || Self::is_registered_name(host.as_bytes())
+		{
+			Ok(format!("{}:{}", host, self.port))
+		}
+		else
+		{
+			Err(CompleteError::InvalidDataSupplied(format!("The host {:?} is not a registered name or an Internet Protocol address", host)))
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn connect_request(&self, proxy_authorization: Option<&str>) -> Result<String, CompleteError>
+	{
+		let authority = self.authority()?;
+
+		let connect_request = match proxy_authorization
+		{
+			None => format!("CONNECT {} HTTP/1.1\r\nHost: {}\r\n\r\n", authority, authority),
+
+			Some(proxy_authorization) => format!("CONNECT {} HTTP/1.1\r\nHost: {}\r\nProxy-Authorization: {}\r\n\r\n", authority, authority, proxy_authorization),
+		};
+		Ok(connect_request)
+	}
+
+	/// `reg-name = *( unreserved / pct-encoded / sub-delims )`, but not empty.
+	#[inline(always)]
+	fn is_registered_name(host: &[u8]) -> bool
+	{
+		if unlikely!(host.is_empty())
+		{
+			return false
+		}
+
+		let mut index = 0;
+		while index < host.len()
+		{
+			match host[index]
+			{
+				b'A' ..= b'Z' | b'a' ..= b'z' | b'0' ..= b'9' | b'-' | b'.' | b'_' | b'~' => index += 1,
+
+				b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=' => index += 1,
+
+				b'%' => if index + 2 < host.len() && (host[index + 1] as char).is_ascii_hexdigit() && (host[index + 2] as char).is_ascii_hexdigit()
+				{
+					index += 3
+				}
+				else
+				{
+					return false
+				},
+
+				_ => return false,
+			}
+		}
+		true
+	}
+}
@@ -0,0 +1,41 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// HTTP CONNECT failure error.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HttpConnectFailureError
+{
+	/// The response header was longer than the configured maximum.
+	ResponseHeaderTooLong,
+
+	/// The response status line was invalid.
+	StatusLineInvalid,
+
+	/// A response header line was invalid.
+	HeaderLineInvalid,
+
+	/// The proxy required authentication (`407`) but either no credentials were available for any of the challenges or the credentials supplied were rejected.
+	ProxyAuthenticationRequired,
+
+	/// The proxy required authentication (`407`) but the response can not be followed by a retry on the same connection (eg it had `Connection: close`).
+	///
+	/// Consider using pre-emptive authentication.
+	ProxyAuthenticationRequiredButRetryImpossible,
+
+	/// The proxy refused to create a tunnel (status code and reason phrase in tuple).
+	Refused(u16, String),
+}
+
+impl Display for HttpConnectFailureError
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		Debug::fmt(self, f)
+	}
+}
+
+impl error::Error for HttpConnectFailureError
+{
+}
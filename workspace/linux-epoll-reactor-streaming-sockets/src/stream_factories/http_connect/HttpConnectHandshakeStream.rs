@@ -0,0 +1,61 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A view of a stream to a HTTP CONNECT proxy used during a HTTP CONNECT handshake.
+///
+/// Received data can be peeked at, so that the proxy's response header can be read without reading any tunnelled data that follows it.
+pub(crate) trait HttpConnectHandshakeStream
+{
+	/// See `UnencryptedStream.peek_more_data()`.
+	fn peek_more_data(&mut self, peek_into_buffer: &mut [u8], already_peeked: usize) -> Result<usize, CompleteError>;
+
+	/// Reads exactly enough bytes to fill `buffer`.
+	fn read_exactly(&mut self, buffer: &mut [u8]) -> Result<(), CompleteError>;
+
+	/// Writes all of `buffer`.
+	fn write_all(&mut self, buffer: &[u8]) -> Result<(), CompleteError>;
+}
+
+impl<'yielder, SD: SocketData> HttpConnectHandshakeStream for UnencryptedStream<'yielder, SD>
+{
+	#[inline(always)]
+	fn peek_more_data(&mut self, peek_into_buffer: &mut [u8], already_peeked: usize) -> Result<usize, CompleteError>
+	{
+		UnencryptedStream::peek_more_data(self, peek_into_buffer, already_peeked)
+	}
+
+	#[inline(always)]
+	fn read_exactly(&mut self, buffer: &mut [u8]) -> Result<(), CompleteError>
+	{
+		read_exactly(self, buffer)
+	}
+
+	#[inline(always)]
+	fn write_all(&mut self, buffer: &[u8]) -> Result<(), CompleteError>
+	{
+		send_packet(self, buffer)
+	}
+}
+
+/// Only possible if the encryption and decryption of records has been offloaded to the kernel (kTLS).
+impl<'yielder, SD: SocketData> HttpConnectHandshakeStream for TlsClientStream<'yielder, SD>
+{
+	#[inline(always)]
+	fn peek_more_data(&mut self, peek_into_buffer: &mut [u8], already_peeked: usize) -> Result<usize, CompleteError>
+	{
+		TlsClientStream::peek_more_data(self, peek_into_buffer, already_peeked)
+	}
+
+	#[inline(always)]
+	fn read_exactly(&mut self, buffer: &mut [u8]) -> Result<(), CompleteError>
+	{
+		read_exactly(self, buffer)
+	}
+
+	#[inline(always)]
+	fn write_all(&mut self, buffer: &[u8]) -> Result<(), CompleteError>
+	{
+		send_packet(self, buffer)
+	}
+}
@@ -0,0 +1,85 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A factory to create instances of any stream tunnelled through a HTTP CONNECT proxy, connected to using TLS (sometimes called a HTTPS proxy), so that proxy credentials are not sent in the clear.
+///
+/// Only works if the encryption and decryption of the TLS session with the proxy can be offloaded to the kernel (kTLS); there is no fallback to userspace TLS.
+/// This is because the inner stream factory, eg `TlsClientStreamFactory`, creates its stream from a socket, which it can then use as if it were unencrypted.
+/// Connecting fails with `TlsInputOutputError::KernelOffloadImpossible` if the proxy does not negotiate TLS 1.3 or the kernel does not support the negotiated cipher suite.
+/// Once the tunnel has been established, the proxy must not send any post-handshake message or alert other than a final close notify alert.
+///
+/// The proxy's ASCII host name is supplied per connection as the first element of the `AdditionalArguments` tuple and the destination as the second; the third element is passed to the inner stream factory.
+///
+/// See also `HttpConnectStreamFactory`.
+#[derive(Debug)]
+pub struct HttpConnectOverKernelTlsStreamFactory<SF: StreamFactory<SD>, SD: SocketData, HPA: HttpProxyAuthentication = ()>
+{
+	/// Used to connect to the proxy; needs to have been created with `TlsClientStreamFactory::with_kernel_tls_offload()`.
+	pub tls_client_stream_factory: TlsClientStreamFactory,
+
+	/// Inner stream factory, eg `TlsClientStreamFactory`.
+	pub stream_factory: SF,
+
+	/// Proxy authentication.
+	pub authentication: HPA,
+
+	/// Maximum length of the proxy's response header.
+	pub maximum_response_header_length: usize,
+
+	marker: PhantomData<SD>,
+}
+
+impl<SF: StreamFactory<SD>, SD: SocketData, HPA: HttpProxyAuthentication> StreamFactory<SD> for HttpConnectOverKernelTlsStreamFactory<SF, SD, HPA>
+{
+	type S = SF::S;
+
+	/// Returns the proxy's response.
+	type ProxyOrTunnelInformation = (HttpConnectResponse, SF::ProxyOrTunnelInformation);
+
+	/// The proxy's ASCII host name, the destination and the inner stream factory's additional arguments.
+	type AdditionalArguments = (Rc<DNSName>, HttpConnectDestination, SF::AdditionalArguments);
+
+	#[inline(always)]
+	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
+	{
+		let (proxy_ascii_host_name, destination, additional_arguments) = additional_arguments;
+
+		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
+
+		let tls_client_stream_factory = &self.tls_client_stream_factory;
+		let mut tls_client_stream = TlsClientStream::new(generic_stream, &tls_client_stream_factory.tls_configuration, tls_client_stream_factory.session_buffer_limit, proxy_ascii_host_name, tls_client_stream_factory.kernel_tls_offload)?;
+
+		if unlikely!(!tls_client_stream.is_kernel_tls_offloaded())
+		{
+			return Err(CompleteError::from(TlsInputOutputError::KernelOffloadImpossible))
+		}
+
+		let http_connect_response = http_connect_handshake(&mut tls_client_stream, &destination, &self.authentication, self.maximum_response_header_length)?;
+
+		let (streaming_socket_file_descriptor, yielder) = tls_client_stream.unwrap_kernel_tls_offloaded()?;
+
+		let (stream, proxy_or_tunnel_information) = self.stream_factory.new_stream_and_handshake(streaming_socket_file_descriptor, yielder, additional_arguments)?;
+
+		Ok((stream, (http_connect_response, proxy_or_tunnel_information)))
+	}
+}
+
+impl<SF: StreamFactory<SD>, SD: SocketData, HPA: HttpProxyAuthentication> HttpConnectOverKernelTlsStreamFactory<SF, SD, HPA>
+{
+	/// New instance.
+	///
+	/// Any key log in `proxy_tls_configuration` is replaced.
+	#[inline(always)]
+	pub fn new(proxy_tls_configuration: ClientConfig, session_buffer_limit: usize, stream_factory: SF, authentication: HPA) -> Self
+	{
+		Self
+		{
+			tls_client_stream_factory: TlsClientStreamFactory::with_kernel_tls_offload(proxy_tls_configuration, session_buffer_limit),
+			stream_factory,
+			authentication,
+			maximum_response_header_length: HttpConnectResponse::DefaultMaximumHeaderLength,
+			marker: PhantomData,
+		}
+	}
+}
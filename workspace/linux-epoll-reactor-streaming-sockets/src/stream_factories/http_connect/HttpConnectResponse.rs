@@ -0,0 +1,213 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// The response of a HTTP CONNECT proxy to a CONNECT request.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpConnectResponse
+{
+	/// HTTP minor version; `0` for `HTTP/1.0` and `1` for `HTTP/1.1`.
+	pub minor_version: u8,
+
+	/// Status code, eg `200`.
+	pub status_code: u16,
+
+	/// Reason phrase, eg `Connection established`; may be empty.
+	pub reason_phrase: String,
+
+	/// Headers, as name and value, in the order received; names are not normalized.
+	pub headers: Vec<(String, String)>,
+}
+
+impl HttpConnectResponse
+{
+	/// Default maximum length of a response header.
+	pub const DefaultMaximumHeaderLength: usize = 16 * 1024;
+
+	/// Is the status code `2xx`?
+	#[inline(always)]
+	pub fn is_successful(&self) -> bool
+	{
+		self.status_code >= 200 && self.status_code <= 299
+	}
+
+	/// Values of all headers named `name`, compared case-insensitively.
+	#[inline(always)]
+	pub fn header_values<'a: 'b, 'b>(&'a self, name: &'b str) -> impl Iterator<Item=&'a str> + 'b
+	{
+		self.headers.iter().filter(move |&&(ref header_name, _)| header_name.eq_ignore_ascii_case(name)).map(|&(_, ref value)| value.as_str())
+	}
+
+	/// Value of the first header named `name`, compared case-insensitively.
+	#[inline(always)]
+	pub fn header_value(&self, name: &str) -> Option<&str>
+	{
+		self.header_values(name).next()
+	}
+
+	/// A `HTTP/1.1` connection is persistent unless the `Connection` header has a `close` option; a `HTTP/1.0` connection is only persistent if the `Connection` header has a `keep-alive` option (RFC 7230, Section 6.3).
+	#[inline(always)]
+	pub(crate) fn connection_will_close(&self) -> bool
+	{
+		let has_connection_option = |option: &str| self.header_values("Connection").any(|value| value.split(',').any(|token| token.trim().eq_ignore_ascii_case(option)));
+
+		if has_connection_option("close")
+		{
+			return true
+		}
+
+		self.minor_version == 0 && !has_connection_option("keep-alive")
+	}
+
+	/// `None` if the body is not delimited by a `Content-Length` (eg it is chunked or delimited by the connection closing).
+	#[inline(always)]
+	pub(crate) fn content_length(&self) -> Option<u64>
+	{
+		if self.header_value("Transfer-Encoding").is_some()
+		{
+			return None
+		}
+
+		self.header_value("Content-Length").and_then(|value| value.trim().parse().ok())
+	}
+
+	/// `response_header` excludes the final `\r\n\r\n`.
+	pub(crate) fn parse(response_header: &[u8]) -> Result<Self, HttpConnectFailureError>
+	{
+		use self::HttpConnectFailureError::*;
+
+		let response_header = from_utf8(response_header).map_err(|_| StatusLineInvalid)?;
+		let mut lines = response_header.split("\r\n");
+
+		let status_line = lines.next().ok_or(StatusLineInvalid)?;
+		let mut fields = status_line.splitn(3, ' ');
+
+		let minor_version = match fields.next()
+		{
+			Some("HTTP/1.1") => 1,
+			Some("HTTP/1.0") => 0,
+			_ => return Err(StatusLineInvalid),
+		};
+
+		let status_code = fields.next().ok_or(StatusLineInvalid)?;
+		if unlikely!(status_code.len() != 3 || !status_code.bytes().all(|byte| byte.is_ascii_digit()))
+		{
+			return Err(StatusLineInvalid)
+		}
+		let status_code = status_code.parse().map_err(|_| StatusLineInvalid)?;
+
+		let reason_phrase = fields.next().unwrap_or("").to_string();
+
+		let mut headers = Vec::new();
+		for line in lines
+		{
+			// Obsolete line folding (RFC 7230, Section 3.2.4) is rejected.
+			if unlikely!(line.starts_with(' ') || line.starts_with('\t'))
+			{
+				return Err(HeaderLineInvalid)
+			}
+
+			let colon_index = line.find(':').ok_or(HeaderLineInvalid)?;
+			let name = &line[.. colon_index];
+			if unlikely!(name.is_empty() || name.ends_with(' ') || name.ends_with('\t'))
+			{
+				return Err(HeaderLineInvalid)
+			}
+			let value = line[colon_index + 1 .. ].trim_matches(|character| character == ' ' || character == '\t');
+			headers.push((name.to_string(), value.to_string()));
+		}
+
+		Ok
+		(
+			Self
+			{
+				minor_version,
+				status_code,
+				reason_phrase,
+				headers,
+			}
+		)
+	}
+}
+
+#[cfg(test)]
+mod http_connect_response_tests
+{
+	use super::*;
+	use self::HttpConnectFailureError::*;
+
+	#[test]
+	fn parses_status_line_and_headers()
+	{
+		let response = HttpConnectResponse::parse(b"HTTP/1.1 200 Connection established\r\nProxy-Agent: example\r\nVia:\t1.1 proxy \r\nEmpty:").unwrap();
+
+		assert_eq!(response.minor_version, 1);
+		assert_eq!(response.status_code, 200);
+		assert_eq!(response.reason_phrase, "Connection established");
+		assert!(response.is_successful());
+		assert_eq!(response.header_value("proxy-agent"), Some("example"));
+		assert_eq!(response.header_value("VIA"), Some("1.1 proxy"));
+		assert_eq!(response.header_value("Empty"), Some(""));
+		assert_eq!(response.header_value("Absent"), None);
+	}
+
+	#[test]
+	fn parses_status_line_without_reason_phrase()
+	{
+		let response = HttpConnectResponse::parse(b"HTTP/1.0 407").unwrap();
+
+		assert_eq!(response.minor_version, 0);
+		assert_eq!(response.status_code, 407);
+		assert_eq!(response.reason_phrase, "");
+		assert!(!response.is_successful());
+		assert!(response.headers.is_empty());
+	}
+
+	#[test]
+	fn rejects_invalid_status_line()
+	{
+		assert_eq!(HttpConnectResponse::parse(b""), Err(StatusLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1"), Err(StatusLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 "), Err(StatusLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 20"), Err(StatusLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 2000 OK"), Err(StatusLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 +20 OK"), Err(StatusLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/2.0 200 OK"), Err(StatusLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"http/1.1 200 OK"), Err(StatusLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 200 \xFF"), Err(StatusLineInvalid));
+	}
+
+	#[test]
+	fn rejects_invalid_header_line()
+	{
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 200 OK\r\nNo-Colon"), Err(HeaderLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 200 OK\r\n: value"), Err(HeaderLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 200 OK\r\nName : value"), Err(HeaderLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 200 OK\r\nName: value\r\n folded"), Err(HeaderLineInvalid));
+		assert_eq!(HttpConnectResponse::parse(b"HTTP/1.1 200 OK\r\n"), Err(HeaderLineInvalid));
+	}
+
+	#[test]
+	fn connection_will_close()
+	{
+		let will_close = |response_header: &[u8]| HttpConnectResponse::parse(response_header).unwrap().connection_will_close();
+
+		assert!(!will_close(b"HTTP/1.1 407 Proxy Authentication Required"));
+		assert!(will_close(b"HTTP/1.1 407 Proxy Authentication Required\r\nConnection: Close"));
+		assert!(will_close(b"HTTP/1.1 407 Proxy Authentication Required\r\nConnection: upgrade, close"));
+		assert!(will_close(b"HTTP/1.0 407 Proxy Authentication Required"));
+		assert!(!will_close(b"HTTP/1.0 407 Proxy Authentication Required\r\nConnection: Keep-Alive"));
+		assert!(will_close(b"HTTP/1.0 407 Proxy Authentication Required\r\nConnection: keep-alive, close"));
+	}
+
+	#[test]
+	fn content_length()
+	{
+		let content_length = |response_header: &[u8]| HttpConnectResponse::parse(response_header).unwrap().content_length();
+
+		assert_eq!(content_length(b"HTTP/1.1 407 Proxy Authentication Required\r\nContent-Length: 12"), Some(12));
+		assert_eq!(content_length(b"HTTP/1.1 407 Proxy Authentication Required\r\nContent-Length: twelve"), None);
+		assert_eq!(content_length(b"HTTP/1.1 407 Proxy Authentication Required\r\nContent-Length: 12\r\nTransfer-Encoding: chunked"), None);
+		assert_eq!(content_length(b"HTTP/1.1 407 Proxy Authentication Required"), None);
+	}
+}
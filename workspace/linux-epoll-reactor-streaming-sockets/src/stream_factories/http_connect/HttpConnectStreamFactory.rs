@@ -0,0 +1,68 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A factory to create instances of any stream tunnelled through a HTTP CONNECT proxy, connected to unencrypted.
+///
+/// The destination is supplied per connection as the first element of the `AdditionalArguments` tuple; the second element is passed to the inner stream factory, eg `UnencryptedStreamFactory` or `TlsClientStreamFactory`.
+///
+/// See also `HttpConnectOverKernelTlsStreamFactory`.
+#[derive(Debug)]
+pub struct HttpConnectStreamFactory<SF: StreamFactory<SD>, SD: SocketData, HPA: HttpProxyAuthentication = ()>
+{
+	/// Inner stream factory, eg `TlsClientStreamFactory`.
+	pub stream_factory: SF,
+
+	/// Proxy authentication.
+	pub authentication: HPA,
+
+	/// Maximum length of the proxy's response header.
+	pub maximum_response_header_length: usize,
+
+	marker: PhantomData<SD>,
+}
+
+impl<SF: StreamFactory<SD>, SD: SocketData, HPA: HttpProxyAuthentication> StreamFactory<SD> for HttpConnectStreamFactory<SF, SD, HPA>
+{
+	type S = SF::S;
+
+	/// Returns the proxy's response.
+	type ProxyOrTunnelInformation = (HttpConnectResponse, SF::ProxyOrTunnelInformation);
+
+	/// The destination and the inner stream factory's additional arguments.
+	type AdditionalArguments = (HttpConnectDestination, SF::AdditionalArguments);
+
+	#[inline(always)]
+	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
+	{
+		let (destination, additional_arguments) = additional_arguments;
+
+		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
+
+		let mut unencrypted_stream = UnencryptedStream::new(generic_stream);
+
+		let http_connect_response = http_connect_handshake(&mut unencrypted_stream, &destination, &self.authentication, self.maximum_response_header_length)?;
+
+		let (streaming_socket_file_descriptor, yielder) = unencrypted_stream.unwrap();
+
+		let (stream, proxy_or_tunnel_information) = self.stream_factory.new_stream_and_handshake(streaming_socket_file_descriptor, yielder, additional_arguments)?;
+
+		Ok((stream, (http_connect_response, proxy_or_tunnel_information)))
+	}
+}
+
+impl<SF: StreamFactory<SD>, SD: SocketData, HPA: HttpProxyAuthentication> HttpConnectStreamFactory<SF, SD, HPA>
+{
+	/// New instance.
+	#[inline(always)]
+	pub fn new(stream_factory: SF, authentication: HPA) -> Self
+	{
+		Self
+		{
+			stream_factory,
+			authentication,
+			maximum_response_header_length: HttpConnectResponse::DefaultMaximumHeaderLength,
+			marker: PhantomData,
+		}
+	}
+}
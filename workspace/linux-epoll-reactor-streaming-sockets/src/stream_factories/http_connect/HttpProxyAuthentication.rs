@@ -0,0 +1,34 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Pluggable proxy authentication for HTTP CONNECT, supplying `Proxy-Authorization` header values.
+///
+/// Implemented for `()`, which never authenticates.
+pub trait HttpProxyAuthentication
+{
+	/// `Proxy-Authorization` header value to send with the first CONNECT request, if any.
+	///
+	/// Sending this avoids a round trip and is necessary for proxies which close the connection after a `407 Proxy Authentication Required`.
+	fn preemptive_proxy_authorization(&self, destination: &HttpConnectDestination) -> Option<String>;
+
+	/// `Proxy-Authorization` header value to send after a `407 Proxy Authentication Required` response, given the values of its `Proxy-Authenticate` headers.
+	///
+	/// Return `None` if none of the challenges are supported.
+	fn proxy_authorization_for_challenges(&self, destination: &HttpConnectDestination, proxy_authenticate_header_values: &[&str]) -> Option<String>;
+}
+
+impl HttpProxyAuthentication for ()
+{
+	#[inline(always)]
+	fn preemptive_proxy_authorization(&self, _destination: &HttpConnectDestination) -> Option<String>
+	{
+		None
+	}
+
+	#[inline(always)]
+	fn proxy_authorization_for_challenges(&self, _destination: &HttpConnectDestination, _proxy_authenticate_header_values: &[&str]) -> Option<String>
+	{
+		None
+	}
+}
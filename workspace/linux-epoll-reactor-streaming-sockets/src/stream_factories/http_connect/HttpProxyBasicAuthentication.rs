@@ -0,0 +1,114 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// `Basic` proxy authentication (RFC 7617).
+///
+/// Credentials are sent in the clear; use `HttpConnectOverKernelTlsStreamFactory` unless the proxy is on a trusted network.
+#[derive(Clone)]
+pub struct HttpProxyBasicAuthentication
+{
+	user_name: String,
+	password: String,
+	preemptive: bool,
+}
+
+impl Debug for HttpProxyBasicAuthentication
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "HttpProxyBasicAuthentication {{ user_name: {:?}, password: _, preemptive: {:?} }}", self.user_name, self.preemptive)
+	}
+}
+
+impl HttpProxyAuthentication for HttpProxyBasicAuthentication
+{
+	#[inline(always)]
+	fn preemptive_proxy_authorization(&self, _destination: &HttpConnectDestination) -> Option<String>
+	{
+		if self.preemptive
+		{
+			Some(self.proxy_authorization())
+		}
+		else
+		{
+			None
+		}
+	}
+
+	#[inline(always)]
+	fn proxy_authorization_for_challenges(&self, _destination: &HttpConnectDestination, proxy_authenticate_header_values: &[&str]) -> Option<String>
+	{
+		// A header value may contain several comma-separated challenges, each of which starts with an authentication scheme; parameters contain `=`.
+		let is_challenged = proxy_authenticate_header_values.iter().flat_map(|value| value.split(',')).filter_map(|challenge| challenge.split_whitespace().next()).any(|scheme| !scheme.contains('=') && scheme.eq_ignore_ascii_case("Basic"));
+
+		if is_challenged
+		{
+			Some(self.proxy_authorization())
+		}
+		else
+		{
+			None
+		}
+	}
+}
+
+impl HttpProxyBasicAuthentication
+{
+	/// Creates a new instance.
+	///
+	/// If `preemptive`, credentials are sent with the first CONNECT request rather than waiting to be challenged.
+	///
+	/// Fails with `CompleteError::InvalidDataSupplied` if `user_name` contains a colon or either `user_name` or `password` contains a control character, as RFC 7617 can not represent these.
+	#[inline(always)]
+	pub fn new(user_name: String, password: String, preemptive: bool) -> Result<Self, CompleteError>
+	{
+		if unlikely!(user_name.contains(':'))
+		{
+			return Err(CompleteError::InvalidDataSupplied("A Basic authentication user name can not contain a colon".to_string()))
+		}
+
+		if unlikely!(user_name.chars().chain(password.chars()).any(char::is_control))
+		{
+			return Err(CompleteError::InvalidDataSupplied("A Basic authentication user name or password can not contain a control character".to_string()))
+		}
+
+		Ok
+		(
+			Self
+			{
+				user_name,
+				password,
+				preemptive,
+			}
+		)
+	}
+
+	#[inline(always)]
+	fn proxy_authorization(&self) -> String
+	{
+		let credentials = format!("{}:{}", self.user_name, self.password);
+		format!("Basic {}", Self::base64_encode(credentials.as_bytes()))
+	}
+
+	fn base64_encode(bytes: &[u8]) -> String
+	{
+		const Alphabet: &'static [u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+		let mut encoded = String::with_capacity((bytes.len() + 2) / 3 * 4);
+		for chunk in bytes.chunks(3)
+		{
+			let byte0 = chunk[0] as u32;
+			let byte1 = chunk.get(1).cloned().unwrap_or(0) as u32;
+			let byte2 = chunk.get(2).cloned().unwrap_or(0) as u32;
+			let triple = (byte0 << 16) | (byte1 << 8) | byte2;
+
+			encoded.push(Alphabet[((triple >> 18) & 0x3F) as usize] as char);
+			encoded.push(Alphabet[((triple >> 12) & 0x3F) as usize] as char);
+			encoded.push(if chunk.len() > 1 { Alphabet[((triple >> 6) & 0x3F) as usize] as char } else { '=' });
+			encoded.push(if chunk.len() > 2 { Alphabet[(triple & 0x3F) as usize] as char } else { '=' });
+		}
+		encoded
+	}
+}
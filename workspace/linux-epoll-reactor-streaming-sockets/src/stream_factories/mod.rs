@@ -9,6 +9,10 @@ use super::*;
 pub mod http_connect;
 
 
+/// PROXY protocol (HAProxy) wrapping factories.
+pub mod proxy_protocol;
+
+
 /// SOCKS4a proxy wrapping factories.
 pub mod socks4a;
 
@@ -17,8 +21,24 @@ pub mod socks4a;
 pub mod socks5;
 
 
+include!("read_exactly.rs");
 include!("send_packet.rs");
 include!("StreamFactory.rs");
+include!("TlsClientAuthentication.rs");
+include!("TlsClientEarlyDataStreamFactory.rs");
+include!("TlsClientHelloServerName.rs");
+include!("TlsClientSessionCache.rs");
 include!("TlsClientStreamFactory.rs");
+include!("TlsDaneCertificateUsage.rs");
+include!("TlsDaneMatchingType.rs");
+include!("TlsDaneRecord.rs");
+include!("TlsDaneSelector.rs");
+include!("TlsRotatingTicketer.rs");
+include!("TlsRotatingTicketerKeys.rs");
+include!("TlsServerAuthentication.rs");
+include!("TlsServerCertificateVerifier.rs");
+include!("TlsServerNameIndicationConfigurations.rs");
+include!("TlsServerNameIndicationConfigurationsLoadError.rs");
+include!("TlsServerNameIndicationStreamFactory.rs");
 include!("TlsServerStreamFactory.rs");
 include!("UnencryptedStreamFactory.rs");
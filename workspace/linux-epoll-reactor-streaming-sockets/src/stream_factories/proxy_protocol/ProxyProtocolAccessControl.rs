@@ -0,0 +1,33 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Access control applied to the real source of a connection after the PROXY protocol header has been read.
+///
+/// Listener access control only sees the address of the proxy; use this to apply the same rules to the original client, eg by wrapping an `AccessControl` in a closure.
+///
+/// Implemented for `()`, which denies all, and for closures.
+pub trait ProxyProtocolAccessControl
+{
+	/// Is the connection described by `proxy_protocol_header` allowed?
+	fn is_allowed(&self, proxy_protocol_header: &ProxyProtocolHeader) -> bool;
+}
+
+/// Denies all connections.
+impl ProxyProtocolAccessControl for ()
+{
+	#[inline(always)]
+	fn is_allowed(&self, _proxy_protocol_header: &ProxyProtocolHeader) -> bool
+	{
+		false
+	}
+}
+
+impl<F: Fn(&ProxyProtocolHeader) -> bool> ProxyProtocolAccessControl for F
+{
+	#[inline(always)]
+	fn is_allowed(&self, proxy_protocol_header: &ProxyProtocolHeader) -> bool
+	{
+		self(proxy_protocol_header)
+	}
+}
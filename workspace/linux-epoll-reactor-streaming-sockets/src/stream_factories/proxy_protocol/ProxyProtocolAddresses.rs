@@ -0,0 +1,79 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// PROXY protocol source and destination addresses of the original connection.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ProxyProtocolAddresses
+{
+	/// Unknown or unspecified; always the case for a `Local` command.
+	Unspecified,
+
+	/// Internet Protocol version 4.
+	InternetProtocolVersion4
+	{
+		/// Source (client) address.
+		source: SocketAddrV4,
+
+		/// Destination (server) address.
+		destination: SocketAddrV4,
+	},
+
+	/// Internet Protocol version 6.
+	InternetProtocolVersion6
+	{
+		/// Source (client) address.
+		source: SocketAddrV6,
+
+		/// Destination (server) address.
+		destination: SocketAddrV6,
+	},
+
+	/// Unix domain socket; version 2 only.
+	///
+	/// Paths are as in `sun_path`, without trailing ASCII NULs; abstract addresses start with an ASCII NUL.
+	/// Paths are at most 108 bytes long.
+	UnixDomain
+	{
+		/// Source (client) path.
+		source: Vec<u8>,
+
+		/// Destination (server) path.
+		destination: Vec<u8>,
+	},
+}
+
+impl ProxyProtocolAddresses
+{
+	/// Source (client) Internet Protocol address and port, if any.
+	#[inline(always)]
+	pub fn source_internet_protocol_address(&self) -> Option<(IpAddr, u16)>
+	{
+		use self::ProxyProtocolAddresses::*;
+
+		match self
+		{
+			&InternetProtocolVersion4 { ref source, .. } => Some((IpAddr::V4(*source.ip()), source.port())),
+
+			&InternetProtocolVersion6 { ref source, .. } => Some((IpAddr::V6(*source.ip()), source.port())),
+
+			_ => None,
+		}
+	}
+
+	/// Destination (server) Internet Protocol address and port, if any.
+	#[inline(always)]
+	pub fn destination_internet_protocol_address(&self) -> Option<(IpAddr, u16)>
+	{
+		use self::ProxyProtocolAddresses::*;
+
+		match self
+		{
+			&InternetProtocolVersion4 { ref destination, .. } => Some((IpAddr::V4(*destination.ip()), destination.port())),
+
+			&InternetProtocolVersion6 { ref destination, .. } => Some((IpAddr::V6(*destination.ip()), destination.port())),
+
+			_ => None,
+		}
+	}
+}
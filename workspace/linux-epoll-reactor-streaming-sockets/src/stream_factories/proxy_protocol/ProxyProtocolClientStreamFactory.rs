@@ -0,0 +1,58 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A factory to create instances of any outbound stream which first sends a PROXY protocol version 1 or version 2 header, so that the remote peer (a backend) sees the original client's identity.
+///
+/// The header is supplied per connection as the first element of the `AdditionalArguments` tuple; the second element is passed to the inner stream factory, eg `UnencryptedStreamFactory` or `TlsClientStreamFactory`.
+///
+/// The header is sent before any TLS handshake.
+#[derive(Debug)]
+pub struct ProxyProtocolClientStreamFactory<SF: StreamFactory<SD>, SD: SocketData>
+{
+	/// Inner stream factory, eg `TlsClientStreamFactory`.
+	pub stream_factory: SF,
+
+	marker: PhantomData<SD>,
+}
+
+impl<SF: StreamFactory<SD>, SD: SocketData> StreamFactory<SD> for ProxyProtocolClientStreamFactory<SF, SD>
+{
+	type S = SF::S;
+
+	type ProxyOrTunnelInformation = SF::ProxyOrTunnelInformation;
+
+	/// The PROXY protocol header to send and the inner stream factory's additional arguments.
+	type AdditionalArguments = (ProxyProtocolHeader, SF::AdditionalArguments);
+
+	#[inline(always)]
+	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
+	{
+		let (proxy_protocol_header, additional_arguments) = additional_arguments;
+
+		let header = proxy_protocol_header.to_bytes().map_err(|error| CompleteError::InvalidDataSupplied(error.to_string()))?;
+
+		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
+
+		let mut unencrypted_stream = UnencryptedStream::new(generic_stream);
+		send_packet(&mut unencrypted_stream, &header[..])?;
+
+		let (streaming_socket_file_descriptor, yielder) = unencrypted_stream.unwrap();
+
+		self.stream_factory.new_stream_and_handshake(streaming_socket_file_descriptor, yielder, additional_arguments)
+	}
+}
+
+impl<SF: StreamFactory<SD>, SD: SocketData> ProxyProtocolClientStreamFactory<SF, SD>
+{
+	/// New instance.
+	#[inline(always)]
+	pub fn new(stream_factory: SF) -> Self
+	{
+		Self
+		{
+			stream_factory,
+			marker: PhantomData,
+		}
+	}
+}
@@ -0,0 +1,17 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// PROXY protocol command.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProxyProtocolCommand
+{
+	/// The connection was established on purpose by the proxy without being relayed, eg for health checks.
+	///
+	/// The addresses, if any, should be ignored and the real connection addresses used.
+	/// Version 1 `UNKNOWN` is treated as `Local`.
+	Local,
+
+	/// The connection was established on behalf of another node and the addresses are those of the original connection.
+	Proxy,
+}
@@ -0,0 +1,60 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// PROXY protocol failure error.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProxyProtocolFailureError
+{
+	/// Neither a version 1 nor a version 2 signature was present.
+	SignatureInvalid,
+
+	/// Version 2 version was not `2` (actual value in tuple).
+	VersionInvalid(u8),
+
+	/// Version 2 command was invalid (actual value in tuple).
+	CommandInvalid(u8),
+
+	/// Version 2 address family was invalid (actual value in tuple).
+	AddressFamilyInvalid(u8),
+
+	/// Version 2 transport protocol was invalid (actual value in tuple).
+	TransportProtocolInvalid(u8),
+
+	/// Version 2 address block was shorter than required for the address family.
+	AddressesTooShort,
+
+	/// Version 2 type-length-value (TLV) was truncated.
+	TypeLengthValueTruncated,
+
+	/// Version 1 header was longer than 107 bytes or did not end with `\r\n`.
+	Version1LineInvalid,
+
+	/// Version 1 header field was invalid (field in tuple).
+	Version1FieldInvalid(&'static str),
+
+	/// The real source was denied by access control.
+	SourceDenied,
+
+	/// Version 2 header to send would be longer than 65,535 bytes after the fixed 16 byte header.
+	Version2HeaderTooLong,
+
+	/// Version 2 header to send would have a Unix domain source or destination path longer than 108 bytes.
+	Version2UnixDomainPathTooLong,
+
+	/// Version 1 header to send would have type-length-values (TLVs), which version 1 can not represent.
+	Version1TypeLengthValuesUnsupported,
+}
+
+impl Display for ProxyProtocolFailureError
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		Debug::fmt(self, f)
+	}
+}
+
+impl error::Error for ProxyProtocolFailureError
+{
+}
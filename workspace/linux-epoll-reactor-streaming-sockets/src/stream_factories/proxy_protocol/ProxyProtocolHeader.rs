@@ -0,0 +1,340 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A PROXY protocol header, as defined by <https://www.haproxy.org/download/1.8/doc/proxy-protocol.txt>.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProxyProtocolHeader
+{
+	/// Version.
+	pub version: ProxyProtocolVersion,
+
+	/// Command.
+	pub command: ProxyProtocolCommand,
+
+	/// Transport protocol of the original connection.
+	pub transport_protocol: ProxyProtocolTransportProtocol,
+
+	/// Source and destination addresses of the original connection.
+	pub addresses: ProxyProtocolAddresses,
+
+	/// Type-Length-Values (TLVs); always empty for version 1.
+	pub type_length_values: Vec<ProxyProtocolTypeLengthValue>,
+}
+
+impl ProxyProtocolHeader
+{
+	/// Version 2 signature.
+	pub const Version2Signature: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+
+	/// Version 1 signature.
+	pub const Version1Signature: [u8; 6] = *b"PROXY ";
+
+	/// Maximum length of a version 1 header, including the trailing `\r\n`.
+	pub const Version1MaximumLength: usize = 107;
+
+	/// New instance for a `Proxy` command over a stream with no type-length-values (TLVs), suitable for sending.
+	#[inline(always)]
+	pub fn new_stream_proxy(version: ProxyProtocolVersion, addresses: ProxyProtocolAddresses) -> Self
+	{
+		Self
+		{
+			version,
+			command: ProxyProtocolCommand::Proxy,
+			transport_protocol: ProxyProtocolTransportProtocol::Stream,
+			addresses,
+			type_length_values: Vec::new(),
+		}
+	}
+
+	/// Adds an Application-Layer Protocol Negotiation (ALPN) protocol type-length-value (TLV); version 2 only.
+	#[inline(always)]
+	pub fn push_application_layer_protocol_negotiation_protocol(&mut self, protocol: impl Into<Vec<u8>>)
+	{
+		self.type_length_values.push(ProxyProtocolTypeLengthValue::new(ProxyProtocolTypeLengthValue::ApplicationLayerProtocolNegotiation, protocol))
+	}
+
+	/// Adds an authority (typically the TLS Server Name Indication host name) type-length-value (TLV); version 2 only.
+	#[inline(always)]
+	pub fn push_authority(&mut self, authority: impl Into<Vec<u8>>)
+	{
+		self.type_length_values.push(ProxyProtocolTypeLengthValue::new(ProxyProtocolTypeLengthValue::Authority, authority))
+	}
+
+	/// First value of a type-length-value (TLV) with type `type_`, if any.
+	#[inline(always)]
+	pub fn type_length_value(&self, type_: u8) -> Option<&[u8]>
+	{
+		self.type_length_values.iter().find(|type_length_value| type_length_value.type_ == type_).map(|type_length_value| &type_length_value.value[..])
+	}
+
+	/// Application-Layer Protocol Negotiation (ALPN) protocol, if any.
+	#[inline(always)]
+	pub fn application_layer_protocol_negotiation_protocol(&self) -> Option<&[u8]>
+	{
+		self.type_length_value(ProxyProtocolTypeLengthValue::ApplicationLayerProtocolNegotiation)
+	}
+
+	/// Authority (typically the TLS Server Name Indication host name), if any.
+	#[inline(always)]
+	pub fn authority(&self) -> Option<&[u8]>
+	{
+		self.type_length_value(ProxyProtocolTypeLengthValue::Authority)
+	}
+
+	/// Unique connection identifier, if any.
+	#[inline(always)]
+	pub fn unique_identifier(&self) -> Option<&[u8]>
+	{
+		self.type_length_value(ProxyProtocolTypeLengthValue::UniqueIdentifier)
+	}
+
+	/// Real source (client) Internet Protocol address and port for a `Proxy` command, if any.
+	#[inline(always)]
+	pub fn real_source_internet_protocol_address(&self) -> Option<(IpAddr, u16)>
+	{
+		match self.command
+		{
+			ProxyProtocolCommand::Local => None,
+
+			ProxyProtocolCommand::Proxy => self.addresses.source_internet_protocol_address(),
+		}
+	}
+
+	/// Serializes this header so it can be sent.
+	///
+	/// A version 1 header for a `Local` command or for Unix domain addresses is sent as `PROXY UNKNOWN\r\n`.
+	pub fn to_bytes(&self) -> Result<Vec<u8>, ProxyProtocolFailureError>
+	{
+		use self::ProxyProtocolVersion::*;
+
+		match self.version
+		{
+			One => self.to_version_1_bytes(),
+
+			Two => self.to_version_2_bytes(),
+		}
+	}
+
+	#[inline(always)]
+	fn to_version_1_bytes(&self) -> Result<Vec<u8>, ProxyProtocolFailureError>
+	{
+		use self::ProxyProtocolAddresses::*;
+
+		if unlikely!(!self.type_length_values.is_empty())
+		{
+			return Err(ProxyProtocolFailureError::Version1TypeLengthValuesUnsupported)
+		}
+
+		let line = match (self.command, &self.addresses)
+		{
+			(ProxyProtocolCommand::Proxy, &InternetProtocolVersion4 { ref source, ref destination }) => format!("PROXY TCP4 {} {} {} {}\r\n", source.ip(), destination.ip(), source.port(), destination.port()),
+
+			(ProxyProtocolCommand::Proxy, &InternetProtocolVersion6 { ref source, ref destination }) => format!("PROXY TCP6 {} {} {} {}\r\n", source.ip(), destination.ip(), source.port(), destination.port()),
+
+			_ => "PROXY UNKNOWN\r\n".to_string(),
+		};
+
+		debug_assert!(line.len() <= Self::Version1MaximumLength, "line is too long");
+
+		Ok(line.into_bytes())
+	}
+
+	#[inline(always)]
+	fn to_version_2_bytes(&self) -> Result<Vec<u8>, ProxyProtocolFailureError>
+	{
+		use self::ProxyProtocolAddresses::*;
+
+		#[inline(always)]
+		fn push_unix_domain_path(bytes: &mut Vec<u8>, path: &[u8]) -> Result<(), ProxyProtocolFailureError>
+		{
+			const Length: usize = 108;
+			let length = path.len();
+			if unlikely!(length > Length)
+			{
+				return Err(ProxyProtocolFailureError::Version2UnixDomainPathTooLong)
+			}
+			bytes.extend_from_slice(path);
+			bytes.resize(bytes.len() + (Length - length), 0);
+			Ok(())
+		}
+
+		let version_and_command = 0x20 | match self.command
+		{
+			ProxyProtocolCommand::Local => 0x0,
+			ProxyProtocolCommand::Proxy => 0x1,
+		};
+
+		let transport_protocol = match self.transport_protocol
+		{
+			ProxyProtocolTransportProtocol::Unspecified => 0x0,
+			ProxyProtocolTransportProtocol::Stream => 0x1,
+			ProxyProtocolTransportProtocol::Datagram => 0x2,
+		};
+
+		let mut addresses_and_type_length_values = Vec::with_capacity(216);
+		let address_family = match self.addresses
+		{
+			Unspecified => 0x0,
+
+			InternetProtocolVersion4 { ref source, ref destination } =>
+			{
+				addresses_and_type_length_values.extend_from_slice(&source.ip().octets());
+				addresses_and_type_length_values.extend_from_slice(&destination.ip().octets());
+				addresses_and_type_length_values.extend_from_slice(&source.port().to_be_bytes());
+				addresses_and_type_length_values.extend_from_slice(&destination.port().to_be_bytes());
+				0x1
+			}
+
+			InternetProtocolVersion6 { ref source, ref destination } =>
+			{
+				addresses_and_type_length_values.extend_from_slice(&source.ip().octets());
+				addresses_and_type_length_values.extend_from_slice(&destination.ip().octets());
+				addresses_and_type_length_values.extend_from_slice(&source.port().to_be_bytes());
+				addresses_and_type_length_values.extend_from_slice(&destination.port().to_be_bytes());
+				0x2
+			}
+
+			UnixDomain { ref source, ref destination } =>
+			{
+				push_unix_domain_path(&mut addresses_and_type_length_values, &source[..])?;
+				push_unix_domain_path(&mut addresses_and_type_length_values, &destination[..])?;
+				0x3
+			}
+		};
+
+		for type_length_value in self.type_length_values.iter()
+		{
+			let length = type_length_value.value.len();
+			if unlikely!(length > ::std::u16::MAX as usize)
+			{
+				return Err(ProxyProtocolFailureError::Version2HeaderTooLong)
+			}
+			addresses_and_type_length_values.push(type_length_value.type_);
+			addresses_and_type_length_values.extend_from_slice(&(length as u16).to_be_bytes());
+			addresses_and_type_length_values.extend_from_slice(&type_length_value.value[..]);
+		}
+
+		let length = addresses_and_type_length_values.len();
+		if unlikely!(length > ::std::u16::MAX as usize)
+		{
+			return Err(ProxyProtocolFailureError::Version2HeaderTooLong)
+		}
+
+		let mut bytes = Vec::with_capacity(16 + length);
+		bytes.extend_from_slice(&Self::Version2Signature);
+		bytes.push(version_and_command);
+		bytes.push((address_family << 4) | transport_protocol);
+		bytes.extend_from_slice(&(length as u16).to_be_bytes());
+		bytes.extend_from_slice(&addresses_and_type_length_values[..]);
+		Ok(bytes)
+	}
+}
+
+#[cfg(test)]
+mod proxy_protocol_header_tests
+{
+	use super::*;
+
+	fn round_trip(header: &ProxyProtocolHeader) -> ProxyProtocolHeader
+	{
+		let bytes = header.to_bytes().unwrap();
+		assert_eq!(ProxyProtocolHeaderReader::<sockaddr_in>::header_length(&bytes[..]), Ok(Some(bytes.len())));
+		ProxyProtocolHeaderReader::<sockaddr_in>::parse(&bytes[..]).unwrap()
+	}
+
+	fn internet_protocol_version_4_addresses() -> ProxyProtocolAddresses
+	{
+		ProxyProtocolAddresses::InternetProtocolVersion4 { source: SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 56324), destination: SocketAddrV4::new(Ipv4Addr::new(198, 51, 100, 1), 443) }
+	}
+
+	fn internet_protocol_version_6_addresses() -> ProxyProtocolAddresses
+	{
+		ProxyProtocolAddresses::InternetProtocolVersion6 { source: SocketAddrV6::new("2001:db8::1".parse().unwrap(), 56324, 0, 0), destination: SocketAddrV6::new("2001:db8::2".parse().unwrap(), 443, 0, 0) }
+	}
+
+	#[test]
+	fn version_1_round_trips()
+	{
+		for addresses in vec![internet_protocol_version_4_addresses(), internet_protocol_version_6_addresses()]
+		{
+			let header = ProxyProtocolHeader::new_stream_proxy(ProxyProtocolVersion::One, addresses);
+			assert_eq!(round_trip(&header), header);
+		}
+	}
+
+	#[test]
+	fn version_1_text()
+	{
+		let header = ProxyProtocolHeader::new_stream_proxy(ProxyProtocolVersion::One, internet_protocol_version_4_addresses());
+
+		assert_eq!(header.to_bytes().unwrap(), b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n".to_vec());
+	}
+
+	#[test]
+	fn version_1_without_internet_protocol_addresses_is_unknown()
+	{
+		let header = ProxyProtocolHeader::new_stream_proxy(ProxyProtocolVersion::One, ProxyProtocolAddresses::UnixDomain { source: b"/run/a.sock".to_vec(), destination: b"/run/b.sock".to_vec() });
+
+		assert_eq!(header.to_bytes().unwrap(), b"PROXY UNKNOWN\r\n".to_vec());
+		assert_eq!(round_trip(&header).command, ProxyProtocolCommand::Local);
+	}
+
+	#[test]
+	fn version_1_can_not_have_type_length_values()
+	{
+		let mut header = ProxyProtocolHeader::new_stream_proxy(ProxyProtocolVersion::One, internet_protocol_version_4_addresses());
+		header.push_authority("example.com");
+
+		assert_eq!(header.to_bytes(), Err(ProxyProtocolFailureError::Version1TypeLengthValuesUnsupported));
+	}
+
+	#[test]
+	fn version_2_round_trips()
+	{
+		let unix_domain_addresses = ProxyProtocolAddresses::UnixDomain { source: b"/run/a.sock".to_vec(), destination: b"\0abstract".to_vec() };
+		for addresses in vec![ProxyProtocolAddresses::Unspecified, internet_protocol_version_4_addresses(), internet_protocol_version_6_addresses(), unix_domain_addresses]
+		{
+			let mut header = ProxyProtocolHeader::new_stream_proxy(ProxyProtocolVersion::Two, addresses);
+			header.push_authority("example.com");
+			header.push_application_layer_protocol_negotiation_protocol("h2");
+			header.type_length_values.push(ProxyProtocolTypeLengthValue::new(ProxyProtocolTypeLengthValue::UniqueIdentifier, Vec::new()));
+
+			assert_eq!(round_trip(&header), header);
+		}
+	}
+
+	#[test]
+	fn version_2_unix_domain_path_of_maximum_length_round_trips()
+	{
+		let header = ProxyProtocolHeader::new_stream_proxy(ProxyProtocolVersion::Two, ProxyProtocolAddresses::UnixDomain { source: vec![b'a'; 108], destination: vec![b'b'; 1] });
+
+		assert_eq!(round_trip(&header), header);
+	}
+
+	#[test]
+	fn version_2_unix_domain_path_too_long_is_an_error()
+	{
+		let header = ProxyProtocolHeader::new_stream_proxy(ProxyProtocolVersion::Two, ProxyProtocolAddresses::UnixDomain { source: vec![b'a'; 109], destination: Vec::new() });
+
+		assert_eq!(header.to_bytes(), Err(ProxyProtocolFailureError::Version2UnixDomainPathTooLong));
+	}
+
+	#[test]
+	fn version_2_type_length_value_too_long_is_an_error()
+	{
+		let mut header = ProxyProtocolHeader::new_stream_proxy(ProxyProtocolVersion::Two, internet_protocol_version_4_addresses());
+		header.push_authority(vec![b'a'; 65_536]);
+
+		assert_eq!(header.to_bytes(), Err(ProxyProtocolFailureError::Version2HeaderTooLong));
+	}
+
+	#[test]
+	fn version_2_header_too_long_is_an_error()
+	{
+		let mut header = ProxyProtocolHeader::new_stream_proxy(ProxyProtocolVersion::Two, internet_protocol_version_4_addresses());
+		header.push_authority(vec![b'a'; 65_535]);
+
+		assert_eq!(header.to_bytes(), Err(ProxyProtocolFailureError::Version2HeaderTooLong));
+	}
+}
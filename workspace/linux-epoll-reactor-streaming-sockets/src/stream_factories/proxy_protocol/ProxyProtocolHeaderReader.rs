@@ -0,0 +1,478 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Reads a PROXY protocol header without reading any of the data that follows it.
+///
+/// Received data is peeked at (not consumed) until the length of the header is known, ie until the fixed 16 byte part of a version 2 header or the `\r\n` ending a version 1 header has been received; exactly that many bytes are then read.
+pub(crate) struct ProxyProtocolHeaderReader<'a, 'yielder: 'a, SD: SocketData + 'a>
+{
+	unencrypted_stream: &'a mut UnencryptedStream<'yielder, SD>,
+}
+
+impl<'a, 'yielder: 'a, SD: SocketData + 'a> ProxyProtocolHeaderReader<'a, 'yielder, SD>
+{
+	const Version2FixedHeaderLength: usize = 16;
+
+	#[inline(always)]
+	pub(crate) fn read_header(unencrypted_stream: &'a mut UnencryptedStream<'yielder, SD>) -> Result<ProxyProtocolHeader, CompleteError>
+	{
+		let this = Self
+		{
+			unencrypted_stream,
+		};
+
+		let mut peeked = [0u8; ProxyProtocolHeader::Version1MaximumLength];
+		let mut bytes_peeked = 0;
+		let header_length = loop
+		{
+			bytes_peeked = this.unencrypted_stream.peek_more_data(&mut peeked[..], bytes_peeked)?;
+			if unlikely!(bytes_peeked == 0)
+			{
+				return Err(CompleteError::RemotePeerClosedCleanly)
+			}
+
+			if let Some(header_length) = Self::header_length(&peeked[.. bytes_peeked]).map_err(Self::protocol_violation)?
+			{
+				break header_length
+			}
+		};
+
+		let mut header = vec![0; header_length];
+		read_exactly(this.unencrypted_stream, &mut header[..])?;
+
+		Self::parse(&header[..]).map_err(Self::protocol_violation)
+	}
+
+	/// Returns `Ok(None)` if more bytes need to be peeked at to know the length of the header.
+	#[inline(always)]
+	fn header_length(peeked: &[u8]) -> Result<Option<usize>, ProxyProtocolFailureError>
+	{
+		use self::ProxyProtocolFailureError::*;
+
+		#[inline(always)]
+		fn could_start_with(peeked: &[u8], signature: &[u8]) -> bool
+		{
+			let length = min(peeked.len(), signature.len());
+			peeked[.. length] == signature[.. length]
+		}
+
+		if could_start_with(peeked, &ProxyProtocolHeader::Version2Signature[..])
+		{
+			if peeked.len() < Self::Version2FixedHeaderLength
+			{
+				return Ok(None)
+			}
+
+			let length = u16::from_be_bytes([peeked[14], peeked[15]]) as usize;
+			Ok(Some(Self::Version2FixedHeaderLength + length))
+		}
+		else if could_start_with(peeked, &ProxyProtocolHeader::Version1Signature[..])
+		{
+			match peeked.windows(2).position(|window| window == b"\r\n")
+			{
+				Some(index) => Ok(Some(index + 2)),
+
+				None => if unlikely!(peeked.len() >= ProxyProtocolHeader::Version1MaximumLength)
+				{
+					Err(Version1LineInvalid)
+				}
+				else
+				{
+					Ok(None)
+				},
+			}
+		}
+		else
+		{
+			Err(SignatureInvalid)
+		}
+	}
+
+	/// `header` is a complete header, as measured by `header_length()`.
+	#[inline(always)]
+	fn parse(header: &[u8]) -> Result<ProxyProtocolHeader, ProxyProtocolFailureError>
+	{
+		if header.starts_with(&ProxyProtocolHeader::Version2Signature)
+		{
+			Self::parse_version_2(header[12], header[13], &header[Self::Version2FixedHeaderLength .. ])
+		}
+		else
+		{
+			Self::parse_version_1(&header[.. header.len() - 2])
+		}
+	}
+
+	#[inline(always)]
+	fn protocol_violation(error: ProxyProtocolFailureError) -> CompleteError
+	{
+		CompleteError::ProtocolViolation(Box::new(error))
+	}
+
+	#[inline(always)]
+	fn parse_version_2(version_and_command: u8, address_family_and_transport_protocol: u8, addresses_and_type_length_values: &[u8]) -> Result<ProxyProtocolHeader, ProxyProtocolFailureError>
+	{
+		use self::ProxyProtocolFailureError::*;
+
+		let version = version_and_command >> 4;
+		if unlikely!(version != 2)
+		{
+			return Err(VersionInvalid(version))
+		}
+
+		let command = match version_and_command & 0x0F
+		{
+			0x0 => ProxyProtocolCommand::Local,
+			0x1 => ProxyProtocolCommand::Proxy,
+			command @ _ => return Err(CommandInvalid(command)),
+		};
+
+		let transport_protocol = match address_family_and_transport_protocol & 0x0F
+		{
+			0x0 => ProxyProtocolTransportProtocol::Unspecified,
+			0x1 => ProxyProtocolTransportProtocol::Stream,
+			0x2 => ProxyProtocolTransportProtocol::Datagram,
+			transport_protocol @ _ => return Err(TransportProtocolInvalid(transport_protocol)),
+		};
+
+		#[inline(always)]
+		fn port(bytes: &[u8]) -> u16
+		{
+			u16::from_be_bytes([bytes[0], bytes[1]])
+		}
+
+		#[inline(always)]
+		fn without_trailing_nuls(bytes: &[u8]) -> Vec<u8>
+		{
+			let length = bytes.iter().rposition(|byte| *byte != 0).map(|index| index + 1).unwrap_or(0);
+			bytes[.. length].to_vec()
+		}
+
+		let (addresses, addresses_length) = match address_family_and_transport_protocol >> 4
+		{
+			0x0 => (ProxyProtocolAddresses::Unspecified, 0),
+
+			0x1 =>
+			{
+				const Length: usize = 12;
+				if unlikely!(addresses_and_type_length_values.len() < Length)
+				{
+					return Err(AddressesTooShort)
+				}
+				let bytes = addresses_and_type_length_values;
+				let source = SocketAddrV4::new(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]), port(&bytes[8 .. 10]));
+				let destination = SocketAddrV4::new(Ipv4Addr::new(bytes[4], bytes[5], bytes[6], bytes[7]), port(&bytes[10 .. 12]));
+				(ProxyProtocolAddresses::InternetProtocolVersion4 { source, destination }, Length)
+			}
+
+			0x2 =>
+			{
+				const Length: usize = 36;
+				if unlikely!(addresses_and_type_length_values.len() < Length)
+				{
+					return Err(AddressesTooShort)
+				}
+				let bytes = addresses_and_type_length_values;
+				let mut source_octets = [0u8; 16];
+				source_octets.copy_from_slice(&bytes[0 .. 16]);
+				let mut destination_octets = [0u8; 16];
+				destination_octets.copy_from_slice(&bytes[16 .. 32]);
+				let source = SocketAddrV6::new(Ipv6Addr::from(source_octets), port(&bytes[32 .. 34]), 0, 0);
+				let destination = SocketAddrV6::new(Ipv6Addr::from(destination_octets), port(&bytes[34 .. 36]), 0, 0);
+				(ProxyProtocolAddresses::InternetProtocolVersion6 { source, destination }, Length)
+			}
+
+			0x3 =>
+			{
+				const Length: usize = 216;
+				if unlikely!(addresses_and_type_length_values.len() < Length)
+				{
+					return Err(AddressesTooShort)
+				}
+				let bytes = addresses_and_type_length_values;
+				let source = without_trailing_nuls(&bytes[0 .. 108]);
+				let destination = without_trailing_nuls(&bytes[108 .. 216]);
+				(ProxyProtocolAddresses::UnixDomain { source, destination }, Length)
+			}
+
+			address_family @ _ => return Err(AddressFamilyInvalid(address_family)),
+		};
+
+		// For a `Local` command, the addresses must be ignored.
+		let addresses = match command
+		{
+			ProxyProtocolCommand::Local => ProxyProtocolAddresses::Unspecified,
+			ProxyProtocolCommand::Proxy => addresses,
+		};
+
+		let mut type_length_values = Vec::new();
+		let mut remaining = &addresses_and_type_length_values[addresses_length .. ];
+		while !remaining.is_empty()
+		{
+			if unlikely!(remaining.len() < 3)
+			{
+				return Err(TypeLengthValueTruncated)
+			}
+			let type_ = remaining[0];
+			let length = port(&remaining[1 .. 3]) as usize;
+			let end = 3 + length;
+			if unlikely!(remaining.len() < end)
+			{
+				return Err(TypeLengthValueTruncated)
+			}
+			if type_ != ProxyProtocolTypeLengthValue::NoOperation
+			{
+				type_length_values.push(ProxyProtocolTypeLengthValue::new(type_, &remaining[3 .. end]));
+			}
+			remaining = &remaining[end .. ];
+		}
+
+		Ok
+		(
+			ProxyProtocolHeader
+			{
+				version: ProxyProtocolVersion::Two,
+				command,
+				transport_protocol,
+				addresses,
+				type_length_values,
+			}
+		)
+	}
+
+	/// `line` excludes the trailing `\r\n`, eg `PROXY TCP4 192.0.2.1 198.51.100.1 56324 443`.
+	#[inline(always)]
+	fn parse_version_1(line: &[u8]) -> Result<ProxyProtocolHeader, ProxyProtocolFailureError>
+	{
+		use self::ProxyProtocolFailureError::*;
+
+		let line = from_utf8(line).map_err(|_| Version1LineInvalid)?;
+		let mut fields = line.split(' ').skip(1);
+
+		#[inline(always)]
+		fn field<'b, T: FromStr>(fields: &mut impl Iterator<Item=&'b str>, name: &'static str) -> Result<T, ProxyProtocolFailureError>
+		{
+			fields.next().ok_or(Version1FieldInvalid(name))?.parse().map_err(|_| Version1FieldInvalid(name))
+		}
+
+		let unknown = ProxyProtocolHeader
+		{
+			version: ProxyProtocolVersion::One,
+			command: ProxyProtocolCommand::Local,
+			transport_protocol: ProxyProtocolTransportProtocol::Unspecified,
+			addresses: ProxyProtocolAddresses::Unspecified,
+			type_length_values: Vec::new(),
+		};
+
+		let addresses = match fields.next()
+		{
+			// The rest of the line is to be ignored.
+			Some("UNKNOWN") => return Ok(unknown),
+
+			Some("TCP4") =>
+			{
+				let source_address: Ipv4Addr = field(&mut fields, "source address")?;
+				let destination_address: Ipv4Addr = field(&mut fields, "destination address")?;
+				let source_port: u16 = field(&mut fields, "source port")?;
+				let destination_port: u16 = field(&mut fields, "destination port")?;
+				ProxyProtocolAddresses::InternetProtocolVersion4 { source: SocketAddrV4::new(source_address, source_port), destination: SocketAddrV4::new(destination_address, destination_port) }
+			}
+
+			Some("TCP6") =>
+			{
+				let source_address: Ipv6Addr = field(&mut fields, "source address")?;
+				let destination_address: Ipv6Addr = field(&mut fields, "destination address")?;
+				let source_port: u16 = field(&mut fields, "source port")?;
+				let destination_port: u16 = field(&mut fields, "destination port")?;
+				ProxyProtocolAddresses::InternetProtocolVersion6 { source: SocketAddrV6::new(source_address, source_port, 0, 0), destination: SocketAddrV6::new(destination_address, destination_port, 0, 0) }
+			}
+
+			_ => return Err(Version1FieldInvalid("protocol")),
+		};
+
+		if unlikely!(fields.next().is_some())
+		{
+			return Err(Version1LineInvalid)
+		}
+
+		Ok
+		(
+			ProxyProtocolHeader
+			{
+				command: ProxyProtocolCommand::Proxy,
+				transport_protocol: ProxyProtocolTransportProtocol::Stream,
+				addresses,
+				.. unknown
+			}
+		)
+	}
+}
+
+#[cfg(test)]
+mod proxy_protocol_header_reader_tests
+{
+	use super::*;
+	use self::ProxyProtocolFailureError::*;
+
+	type Reader<'a, 'yielder> = ProxyProtocolHeaderReader<'a, 'yielder, sockaddr_in>;
+
+	fn version_2_header(version_and_command: u8, address_family_and_transport_protocol: u8, addresses_and_type_length_values: &[u8]) -> Vec<u8>
+	{
+		let mut header = ProxyProtocolHeader::Version2Signature.to_vec();
+		header.push(version_and_command);
+		header.push(address_family_and_transport_protocol);
+		header.extend_from_slice(&(addresses_and_type_length_values.len() as u16).to_be_bytes());
+		header.extend_from_slice(addresses_and_type_length_values);
+		header
+	}
+
+	const InternetProtocolVersion4Addresses: [u8; 12] = [192, 0, 2, 1, 198, 51, 100, 1, 0xDC, 0x04, 0x01, 0xBB];
+
+	#[test]
+	fn header_length_of_version_1_needs_line_ending()
+	{
+		assert_eq!(Reader::header_length(b"P"), Ok(None));
+		assert_eq!(Reader::header_length(b"PROXY TCP4 192.0.2.1"), Ok(None));
+		assert_eq!(Reader::header_length(b"PROXY TCP4 192.0.2.1\r"), Ok(None));
+		assert_eq!(Reader::header_length(b"PROXY UNKNOWN\r\nGET / HTTP/1.1\r\n"), Ok(Some(15)));
+	}
+
+	#[test]
+	fn header_length_of_version_1_longer_than_maximum_is_invalid()
+	{
+		let mut peeked = b"PROXY ".to_vec();
+		peeked.resize(ProxyProtocolHeader::Version1MaximumLength, b'A');
+
+		assert_eq!(Reader::header_length(&peeked[..]), Err(Version1LineInvalid));
+	}
+
+	#[test]
+	fn header_length_of_version_2_needs_fixed_header()
+	{
+		let header = version_2_header(0x21, 0x11, &InternetProtocolVersion4Addresses[..]);
+
+		assert_eq!(Reader::header_length(&header[.. 1]), Ok(None));
+		assert_eq!(Reader::header_length(&header[.. 15]), Ok(None));
+		assert_eq!(Reader::header_length(&header[.. 16]), Ok(Some(28)));
+		assert_eq!(Reader::header_length(&header[..]), Ok(Some(28)));
+	}
+
+	#[test]
+	fn header_length_without_signature_is_invalid()
+	{
+		assert_eq!(Reader::header_length(b"GET / HTTP/1.1\r\n"), Err(SignatureInvalid));
+		assert_eq!(Reader::header_length(b"PROXX"), Err(SignatureInvalid));
+		assert_eq!(Reader::header_length(b"\r\n\r\nX"), Err(SignatureInvalid));
+	}
+
+	#[test]
+	fn parses_version_1_internet_protocol_version_4()
+	{
+		let header = Reader::parse(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n").unwrap();
+
+		assert_eq!(header.version, ProxyProtocolVersion::One);
+		assert_eq!(header.command, ProxyProtocolCommand::Proxy);
+		assert_eq!(header.transport_protocol, ProxyProtocolTransportProtocol::Stream);
+		assert_eq!(header.addresses, ProxyProtocolAddresses::InternetProtocolVersion4 { source: SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 56324), destination: SocketAddrV4::new(Ipv4Addr::new(198, 51, 100, 1), 443) });
+		assert_eq!(header.real_source_internet_protocol_address(), Some((IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)), 56324)));
+	}
+
+	#[test]
+	fn parses_version_1_internet_protocol_version_6()
+	{
+		let header = Reader::parse(b"PROXY TCP6 2001:db8::1 2001:db8::2 56324 443\r\n").unwrap();
+
+		assert_eq!(header.addresses, ProxyProtocolAddresses::InternetProtocolVersion6 { source: SocketAddrV6::new("2001:db8::1".parse().unwrap(), 56324, 0, 0), destination: SocketAddrV6::new("2001:db8::2".parse().unwrap(), 443, 0, 0) });
+	}
+
+	#[test]
+	fn parses_version_1_unknown_as_local_ignoring_rest_of_line()
+	{
+		let header = Reader::parse(b"PROXY UNKNOWN 192.0.2.1 anything\r\n").unwrap();
+
+		assert_eq!(header.command, ProxyProtocolCommand::Local);
+		assert_eq!(header.addresses, ProxyProtocolAddresses::Unspecified);
+		assert_eq!(header.real_source_internet_protocol_address(), None);
+	}
+
+	#[test]
+	fn rejects_malformed_version_1()
+	{
+		assert_eq!(Reader::parse(b"PROXY UDP4 192.0.2.1 198.51.100.1 56324 443\r\n"), Err(Version1FieldInvalid("protocol")));
+		assert_eq!(Reader::parse(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324\r\n"), Err(Version1FieldInvalid("destination port")));
+		assert_eq!(Reader::parse(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 65536\r\n"), Err(Version1FieldInvalid("destination port")));
+		assert_eq!(Reader::parse(b"PROXY TCP4 2001:db8::1 198.51.100.1 56324 443\r\n"), Err(Version1FieldInvalid("source address")));
+		assert_eq!(Reader::parse(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443 extra\r\n"), Err(Version1LineInvalid));
+		assert_eq!(Reader::parse(b"PROXY TCP4 192.0.2.1  198.51.100.1 56324 443\r\n"), Err(Version1FieldInvalid("destination address")));
+		assert_eq!(Reader::parse(b"PROXY TCP4 \xFF\r\n"), Err(Version1LineInvalid));
+	}
+
+	#[test]
+	fn parses_version_2_internet_protocol_version_4_with_type_length_values()
+	{
+		let mut addresses_and_type_length_values = InternetProtocolVersion4Addresses.to_vec();
+		addresses_and_type_length_values.extend_from_slice(&[ProxyProtocolTypeLengthValue::Authority, 0x00, 0x0B]);
+		addresses_and_type_length_values.extend_from_slice(b"example.com");
+		addresses_and_type_length_values.extend_from_slice(&[ProxyProtocolTypeLengthValue::NoOperation, 0x00, 0x02, 0x00, 0x00]);
+		addresses_and_type_length_values.extend_from_slice(&[ProxyProtocolTypeLengthValue::ApplicationLayerProtocolNegotiation, 0x00, 0x02]);
+		addresses_and_type_length_values.extend_from_slice(b"h2");
+
+		let header = Reader::parse(&version_2_header(0x21, 0x11, &addresses_and_type_length_values[..])[..]).unwrap();
+
+		assert_eq!(header.version, ProxyProtocolVersion::Two);
+		assert_eq!(header.command, ProxyProtocolCommand::Proxy);
+		assert_eq!(header.transport_protocol, ProxyProtocolTransportProtocol::Stream);
+		assert_eq!(header.addresses, ProxyProtocolAddresses::InternetProtocolVersion4 { source: SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 56324), destination: SocketAddrV4::new(Ipv4Addr::new(198, 51, 100, 1), 443) });
+		assert_eq!(header.type_length_values.len(), 2);
+		assert_eq!(header.authority(), Some(&b"example.com"[..]));
+		assert_eq!(header.application_layer_protocol_negotiation_protocol(), Some(&b"h2"[..]));
+		assert_eq!(header.unique_identifier(), None);
+	}
+
+	#[test]
+	fn parses_version_2_unix_domain_without_trailing_nuls()
+	{
+		let mut addresses = vec![0u8; 216];
+		addresses[.. 9].copy_from_slice(b"/run/a.so");
+		addresses[108 .. 118].copy_from_slice(b"\0abstract\0");
+
+		let header = Reader::parse(&version_2_header(0x21, 0x31, &addresses[..])[..]).unwrap();
+
+		assert_eq!(header.addresses, ProxyProtocolAddresses::UnixDomain { source: b"/run/a.so".to_vec(), destination: b"\0abstract".to_vec() });
+	}
+
+	#[test]
+	fn parses_version_2_local_ignoring_addresses()
+	{
+		let header = Reader::parse(&version_2_header(0x20, 0x11, &InternetProtocolVersion4Addresses[..])[..]).unwrap();
+
+		assert_eq!(header.command, ProxyProtocolCommand::Local);
+		assert_eq!(header.addresses, ProxyProtocolAddresses::Unspecified);
+		assert_eq!(header.real_source_internet_protocol_address(), None);
+	}
+
+	#[test]
+	fn rejects_malformed_version_2()
+	{
+		assert_eq!(Reader::parse(&version_2_header(0x31, 0x11, &InternetProtocolVersion4Addresses[..])[..]), Err(VersionInvalid(3)));
+		assert_eq!(Reader::parse(&version_2_header(0x22, 0x11, &InternetProtocolVersion4Addresses[..])[..]), Err(CommandInvalid(2)));
+		assert_eq!(Reader::parse(&version_2_header(0x21, 0x41, &InternetProtocolVersion4Addresses[..])[..]), Err(AddressFamilyInvalid(4)));
+		assert_eq!(Reader::parse(&version_2_header(0x21, 0x13, &InternetProtocolVersion4Addresses[..])[..]), Err(TransportProtocolInvalid(3)));
+		assert_eq!(Reader::parse(&version_2_header(0x21, 0x11, &InternetProtocolVersion4Addresses[.. 11])[..]), Err(AddressesTooShort));
+		assert_eq!(Reader::parse(&version_2_header(0x21, 0x21, &InternetProtocolVersion4Addresses[..])[..]), Err(AddressesTooShort));
+		assert_eq!(Reader::parse(&version_2_header(0x21, 0x31, &[0u8; 215][..])[..]), Err(AddressesTooShort));
+	}
+
+	#[test]
+	fn rejects_truncated_version_2_type_length_values()
+	{
+		let mut addresses_and_type_length_values = InternetProtocolVersion4Addresses.to_vec();
+		addresses_and_type_length_values.extend_from_slice(&[ProxyProtocolTypeLengthValue::Authority, 0x00]);
+		assert_eq!(Reader::parse(&version_2_header(0x21, 0x11, &addresses_and_type_length_values[..])[..]), Err(TypeLengthValueTruncated));
+
+		let mut addresses_and_type_length_values = InternetProtocolVersion4Addresses.to_vec();
+		addresses_and_type_length_values.extend_from_slice(&[ProxyProtocolTypeLengthValue::Authority, 0x00, 0x0B]);
+		addresses_and_type_length_values.extend_from_slice(b"example");
+		assert_eq!(Reader::parse(&version_2_header(0x21, 0x11, &addresses_and_type_length_values[..])[..]), Err(TypeLengthValueTruncated));
+	}
+}
@@ -0,0 +1,67 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A factory to create instances of any stream where the remote peer (eg HAProxy or a cloud load balancer) first sends a PROXY protocol version 1 or version 2 header.
+///
+/// The header is exposed as `ProxyOrTunnelInformation`, giving the real source (client) and destination addresses.
+///
+/// The header is believed without question, so any remote peer able to connect can claim to be any source; this factory must only be used for listeners which only trusted proxies or load balancers can reach (eg by using listener access control).
+/// There is no default access control for the real source; `()` can be used, but denies all.
+#[derive(Debug)]
+pub struct ProxyProtocolStreamFactory<SF: StreamFactory<SD>, SD: SocketData, PPAC: ProxyProtocolAccessControl>
+{
+	/// Inner stream factory, eg `TlsServerStreamFactory`.
+	pub stream_factory: SF,
+
+	/// Access control re-run on the real source.
+	pub access_control: PPAC,
+
+	marker: PhantomData<SD>,
+}
+
+impl<SF: StreamFactory<SD>, SD: SocketData, PPAC: ProxyProtocolAccessControl> StreamFactory<SD> for ProxyProtocolStreamFactory<SF, SD, PPAC>
+{
+	type S = SF::S;
+
+	/// Returns the PROXY protocol header.
+	type ProxyOrTunnelInformation = (ProxyProtocolHeader, SF::ProxyOrTunnelInformation);
+
+	type AdditionalArguments = SF::AdditionalArguments;
+
+	#[inline(always)]
+	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
+	{
+		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
+
+		let mut unencrypted_stream = UnencryptedStream::new(generic_stream);
+
+		let proxy_protocol_header = ProxyProtocolHeaderReader::read_header(&mut unencrypted_stream)?;
+
+		if unlikely!(!self.access_control.is_allowed(&proxy_protocol_header))
+		{
+			return Err(CompleteError::ProtocolViolation(Box::new(ProxyProtocolFailureError::SourceDenied)))
+		}
+
+		let (streaming_socket_file_descriptor, yielder) = unencrypted_stream.unwrap();
+
+		let (stream, proxy_or_tunnel_information) = self.stream_factory.new_stream_and_handshake(streaming_socket_file_descriptor, yielder, additional_arguments)?;
+
+		Ok((stream, (proxy_protocol_header, proxy_or_tunnel_information)))
+	}
+}
+
+impl<SF: StreamFactory<SD>, SD: SocketData, PPAC: ProxyProtocolAccessControl> ProxyProtocolStreamFactory<SF, SD, PPAC>
+{
+	/// New instance which re-runs access control on the real source.
+	#[inline(always)]
+	pub fn new(stream_factory: SF, access_control: PPAC) -> Self
+	{
+		Self
+		{
+			stream_factory,
+			access_control,
+			marker: PhantomData,
+		}
+	}
+}
@@ -0,0 +1,54 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A PROXY protocol version 2 Type-Length-Value (TLV).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProxyProtocolTypeLengthValue
+{
+	/// Type; see the associated constants.
+	pub type_: u8,
+
+	/// Value; at most 65,535 bytes.
+	pub value: Vec<u8>,
+}
+
+impl ProxyProtocolTypeLengthValue
+{
+	/// Application-Layer Protocol Negotiation (ALPN) protocol, eg `h2`.
+	pub const ApplicationLayerProtocolNegotiation: u8 = 0x01;
+
+	/// Authority; typically the TLS Server Name Indication (SNI) host name.
+	pub const Authority: u8 = 0x02;
+
+	/// CRC-32c checksum of the header.
+	///
+	/// Not verified when received.
+	pub const Crc32c: u8 = 0x03;
+
+	/// Ignored padding.
+	pub const NoOperation: u8 = 0x04;
+
+	/// Opaque unique connection identifier of up to 128 bytes.
+	pub const UniqueIdentifier: u8 = 0x05;
+
+	/// TLS information, with sub-TLVs.
+	pub const Tls: u8 = 0x20;
+
+	/// Network namespace name.
+	pub const NetworkNamespace: u8 = 0x30;
+
+	/// Start of the range reserved for custom (private) use; the range ends at `0xEF`.
+	pub const CustomStart: u8 = 0xE0;
+
+	/// New instance.
+	#[inline(always)]
+	pub fn new(type_: u8, value: impl Into<Vec<u8>>) -> Self
+	{
+		Self
+		{
+			type_,
+			value: value.into(),
+		}
+	}
+}
@@ -0,0 +1,20 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Reads exactly enough bytes to fill `buffer`, so that no data following a handshake message is consumed.
+#[inline(always)]
+pub(crate) fn read_exactly<S: Stream>(stream: &mut S, buffer: &mut [u8]) -> Result<(), CompleteError>
+{
+	let mut bytes_read_so_far = 0;
+	while bytes_read_so_far < buffer.len()
+	{
+		let bytes_read = stream.read_data(&mut buffer[bytes_read_so_far .. ])?;
+		if unlikely!(bytes_read == 0)
+		{
+			return Err(CompleteError::RemotePeerClosedCleanly)
+		}
+		bytes_read_so_far += bytes_read;
+	}
+	Ok(())
+}
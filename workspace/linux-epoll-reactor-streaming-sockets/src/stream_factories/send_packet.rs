@@ -3,14 +3,14 @@
 
 
 #[inline(always)]
-pub(crate) fn send_packet<'yielder, SD: SocketData>(unencrypted_stream: UnencryptedStream<'yielder, SD>, packet: &[u8]) -> Result<UnencryptedStream<'yielder, SD>, CompleteError>
+pub(crate) fn send_packet<S: Stream>(stream: &mut S, packet: &[u8]) -> Result<(), CompleteError>
 {
 	let mut bytes_already_written = 0;
 	let mut bytes_remaining_to_write = packet.len();
 
 	while
 	{
-		let bytes_written = unencrypted_stream.write_data(&packet[bytes_already_written .. ]).map_err(|io_error| CompleteError::SocketRead(io_error))?;
+		let bytes_written = stream.write_data(&packet[bytes_already_written .. ])?;
 		bytes_remaining_to_write -= bytes_written;
 		bytes_already_written += bytes_written;
 		bytes_remaining_to_write != 0
@@ -18,5 +18,5 @@ pub(crate) fn send_packet<'yielder, SD: SocketData>(unencrypted_stream: Unencryp
 	{
 	}
 
-	Ok(unencrypted_stream)
+	Ok(())
 }
@@ -31,10 +31,11 @@ impl Socks4aConnect
 	const PacketMaximumSize: usize = 384;
 
 	#[inline(always)]
-	pub(crate) fn send_request<'yielder, SD: SocketData>(&self, unencrypted_stream: UnencryptedStream<'yielder, SD>) -> Result<UnencryptedStream<'yielder, SD>, CompleteError>
+	pub(crate) fn send_request<'yielder, SD: SocketData>(&self, mut unencrypted_stream: UnencryptedStream<'yielder, SD>) -> Result<UnencryptedStream<'yielder, SD>, CompleteError>
 	{
 		let (buffer, packet_length) = self.write_packet(&mut buffer).map_err(|additional_length_required| CompleteError::InvalidData(format!("Either the user identifer or host name were so large that they could not be written; an extra {:?} bytes over {:?} would be required", additional_length_required, buffer.len())))?;
-		send_packet(unencrypted_stream, &buffer[0 .. packet_length])
+		send_packet(&mut unencrypted_stream, &buffer[0 .. packet_length])?;
+		Ok(unencrypted_stream)
 	}
 
 	#[inline(always)]
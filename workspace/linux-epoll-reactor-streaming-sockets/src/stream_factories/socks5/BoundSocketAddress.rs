@@ -16,3 +16,20 @@ pub struct BoundSocketAddress
 	/// A port number.
 	pub port: u16,
 }
+
+impl BoundSocketAddress
+{
+	/// Is the address unspecified (`0.0.0.0` or `::`)?
+	///
+	/// SOCKS5 servers commonly reply to an UDP ASSOCIATE command with an unspecified address, meaning the relay is at the same address as the SOCKS5 server itself.
+	#[inline(always)]
+	pub fn is_unspecified(&self) -> bool
+	{
+		match self.address
+		{
+			InternetProtocolAddressOrHostName::InternetProtocolAddress(ref address) => address.is_unspecified(),
+
+			InternetProtocolAddressOrHostName::HostName(_) => false,
+		}
+	}
+}
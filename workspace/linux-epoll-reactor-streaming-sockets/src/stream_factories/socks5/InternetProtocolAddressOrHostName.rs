@@ -2,9 +2,9 @@
 // Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
 
 
-/// An Internet Protocol version 4 address (for SOCKS4) or host name to be resolved by the SOCKS proxy server (SOCKS4a).
+/// An Internet Protocol version 4 or version 6 address or host name to be resolved by the SOCKS5 proxy server.
 ///
-/// Note that a Host Name should never exceed 253 bytes (254 including trailing ASCII NUL).
+/// Note that a Host Name should never exceed 253 bytes (254 including prefixed length byte).
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum InternetProtocolAddressOrHostName
 {
@@ -16,3 +16,126 @@ pub enum InternetProtocolAddressOrHostName
 	/// Note that a Host Name should never exceed 253 bytes (254 including prefixed length byte) and should never be empty.
 	HostName(ArrayVec<[u8; 253]>),
 }
+
+impl InternetProtocolAddressOrHostName
+{
+	const MaximumSizeOfHostName: usize = 253;
+
+	const InternetProtocolVersion4AddressType: u8 = 0x01;
+
+	pub(crate) const HostNameAddressType: u8 = 0x03;
+
+	const InternetProtocolVersion6AddressType: u8 = 0x04;
+
+	/// Size of `ATYP` and the largest possible `ADDR`.
+	pub(crate) const MaximumSizeOfAddressTypeAndAddress: usize = 1 + 1 + Self::MaximumSizeOfHostName;
+
+	/// Writes `ATYP` followed by `ADDR`, returning the number of bytes written.
+	///
+	/// `buffer` must be at least `MaximumSizeOfAddressTypeAndAddress` bytes long.
+	#[inline(always)]
+	pub(crate) fn write_address_type_and_address(&self, buffer: &mut [u8]) -> Result<usize, CompleteError>
+	{
+		use self::InternetProtocolAddressOrHostName::*;
+
+		debug_assert!(buffer.len() >= Self::MaximumSizeOfAddressTypeAndAddress, "buffer is too small");
+
+		match self
+		{
+			&InternetProtocolAddress(IpAddr::V4(ref address)) =>
+			{
+				buffer[0] = Self::InternetProtocolVersion4AddressType;
+				buffer[1 .. 5].copy_from_slice(&address.octets());
+				Ok(1 + 4)
+			}
+
+			&InternetProtocolAddress(IpAddr::V6(ref address)) =>
+			{
+				buffer[0] = Self::InternetProtocolVersion6AddressType;
+				buffer[1 .. 17].copy_from_slice(&address.octets());
+				Ok(1 + 16)
+			}
+
+			&HostName(ref host_name) =>
+			{
+				let number_of_octets_in_host_name = host_name.len();
+
+				if unlikely!(number_of_octets_in_host_name == 0)
+				{
+					return Err(CompleteError::InvalidDataSupplied("The host name is empty".to_string()))
+				}
+
+				buffer[0] = Self::HostNameAddressType;
+				buffer[1] = number_of_octets_in_host_name as u8;
+				buffer[2 .. 2 + number_of_octets_in_host_name].copy_from_slice(&host_name[..]);
+				Ok(1 + 1 + number_of_octets_in_host_name)
+			}
+		}
+	}
+
+	/// Length of `ADDR` given `ATYP` and, for a host name, the first byte of `ADDR`.
+	#[inline(always)]
+	pub(crate) fn address_length(address_type: u8, first_byte_of_address: impl FnOnce() -> Result<u8, CompleteError>) -> Result<usize, CompleteError>
+	{
+		use self::Socks5ProtocolFailureError::*;
+
+		match address_type
+		{
+			Self::InternetProtocolVersion4AddressType => Ok(4),
+
+			Self::InternetProtocolVersion6AddressType => Ok(16),
+
+			Self::HostNameAddressType =>
+			{
+				let number_of_octets_in_host_name = first_byte_of_address()?;
+
+				if unlikely!(number_of_octets_in_host_name == 0)
+				{
+					return error(HostNameInReplyWasEmpty)
+				}
+
+				if unlikely!(number_of_octets_in_host_name as usize > Self::MaximumSizeOfHostName)
+				{
+					return error(HostNameInReplyWasTooLarge(number_of_octets_in_host_name))
+				}
+
+				Ok(1 + number_of_octets_in_host_name as usize)
+			}
+
+			_ => error(ReplyContainedAnUnrecognisedAddressType(address_type)),
+		}
+	}
+
+	/// Parses `ADDR`; `address` must be exactly `address_length()` bytes long.
+	#[inline(always)]
+	pub(crate) fn parse_address(address_type: u8, address: &[u8]) -> Self
+	{
+		use self::InternetProtocolAddressOrHostName::*;
+
+		match address_type
+		{
+			Self::InternetProtocolVersion4AddressType =>
+			{
+				let mut octets = [0u8; 4];
+				octets.copy_from_slice(address);
+				InternetProtocolAddress(IpAddr::from(octets))
+			}
+
+			Self::InternetProtocolVersion6AddressType =>
+			{
+				let mut octets = [0u8; 16];
+				octets.copy_from_slice(address);
+				InternetProtocolAddress(IpAddr::from(octets))
+			}
+
+			Self::HostNameAddressType =>
+			{
+				let mut host_name = ArrayVec::new();
+				host_name.extend(address[1 .. ].iter().cloned());
+				HostName(host_name)
+			}
+
+			_ => unreachable!("address type was validated by address_length()"),
+		}
+	}
+}
@@ -2,7 +2,7 @@
 // Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
 
 
-/// At this point there is no support for the GSS-API methods; however, GSS-API is only really effective when used with Kerberos.
+/// Built-in authentication methods; see also `Socks5GssApiAuthenticationMethod` and `Socks5AuthenticationMethod`.
 ///
 /// A potential source of draft RFCs for other authentication methods is listed as part of [Dante](https://www.inet.no/dante/doc/).
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -24,42 +24,52 @@ pub enum Socks5AuthenticationCredential
 	}
 }
 
-impl Socks5AuthenticationCredential
+impl Socks5AuthenticationMethod for Socks5AuthenticationCredential
 {
-	const NumberOfMembers: usize = 2;
+	#[inline(always)]
+	fn code(&self) -> Socks5AuthenticationCredentialCode
+	{
+		self.to_code()
+	}
 
 	#[inline(always)]
-	pub(crate) fn to_code(&self) -> Socks5AuthenticationCredentialCode
+	fn negotiate(&self, stream: &mut dyn Socks5HandshakeStream) -> Result<Option<Box<dyn Socks5MessageEncapsulation>>, CompleteError>
 	{
 		use self::Socks5AuthenticationCredential::*;
 
 		match self
 		{
-			&None => Socks5AuthenticationCredentialCode::None,
+			&None => (),
 
-			&UserNameAndPassword { .. } => Socks5AuthenticationCredentialCode::UserNameAndPassword,
+			&UserNameAndPassword { ref user_name, ref password } => Self::negotiate_user_name_and_password(stream, &user_name[..], &password[..])?,
 		}
+
+		Ok(Option::None)
 	}
+}
+
+impl Socks5AuthenticationCredential
+{
+	const UserNamePasswordVersion: u8 = 0x01;
 
 	#[inline(always)]
-	pub(crate) fn negotiate<'yielder, SD: SocketData>(&self, unencrypted_stream: UnencryptedStream<'yielder, SD>) -> Result<UnencryptedStream<'yielder, SD>, CompleteError>
+	pub(crate) fn to_code(&self) -> Socks5AuthenticationCredentialCode
 	{
 		use self::Socks5AuthenticationCredential::*;
-		use self::Socks5ProtocolFailureError::*;
 
-		let unencrypted_stream = match self
+		match self
 		{
-			&None => unencrypted_stream,
-
-			&UserNameAndPassword { ref user_name, ref password } => negotiate_user_name_and_password(unencrypted_stream, &user_name, &password)?,
-		};
+			&None => Socks5AuthenticationCredentialCode::None,
 
-		Ok(unencrypted_stream)
+			&UserNameAndPassword { .. } => Socks5AuthenticationCredentialCode::UserNameAndPassword,
+		}
 	}
 
 	#[inline(always)]
-	fn negotiate_user_name_and_password<'yielder, SD: SocketData>(unencrypted_stream: UnencryptedStream<'yielder, SD>, user_name: &[u8], password: &[u8]) -> Result<UnencryptedStream<'yielder, SD>, CompleteError>
+	fn negotiate_user_name_and_password(stream: &mut dyn Socks5HandshakeStream, user_name: &[u8], password: &[u8]) -> Result<(), CompleteError>
 	{
+		use self::Socks5ProtocolFailureError::*;
+
 		if unlikely!(user_name.is_empty())
 		{
 			return error(EmptyUserName)
@@ -86,7 +96,7 @@ impl Socks5AuthenticationCredential
 			let user_name_length = user_name.len();
 			let password_length = password.len();
 
-			*(unsafe { buffer.get_unchecked_mut(0) }) = Self::Version;
+			*(unsafe { buffer.get_unchecked_mut(0) }) = Socks5AuthenticationCredential::UserNamePasswordVersion;
 			*(unsafe { buffer.get_unchecked_mut(SizeOfVersion) }) = user_name_length as u8;
 			unsafe { copy_nonoverlapping(user_name.as_ptr(), buffer.get_unchecked_mut(SizeOfVersion + SizeOfUserLength), user_name_length) };
 
@@ -97,8 +107,8 @@ impl Socks5AuthenticationCredential
 		}
 
 		let (buffer, packet_length) = write_packet(user_name, password);
-		send_packet(unencrypted_stream, &buffer[0 .. packet_length])?;
+		stream.write_all(&buffer[0 .. packet_length])?;
 
-		Socks5UserNamePasswordReply::read_reply(unencrypted_stream)?
+		Socks5UserNamePasswordReply::read_reply(stream)
 	}
 }
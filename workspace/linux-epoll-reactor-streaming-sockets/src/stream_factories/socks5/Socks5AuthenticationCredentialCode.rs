@@ -2,7 +2,7 @@
 // Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
 
 
-/// A credential code.
+/// A credential (authentication method) code.
 ///
 /// Never `0xFF`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -19,7 +19,30 @@ impl Into<u8> for Socks5AuthenticationCredentialCode
 
 impl Socks5AuthenticationCredentialCode
 {
-	const None: Self = Self(0x00);
+	/// No authentication.
+	pub const None: Self = Socks5AuthenticationCredentialCode(0x00);
 
-	const UserNameAndPassword: Self = Self(0x02);
+	/// GSS-API (RFC 1961).
+	pub const GssApi: Self = Socks5AuthenticationCredentialCode(0x01);
+
+	/// User name and password (RFC 1929).
+	pub const UserNameAndPassword: Self = Socks5AuthenticationCredentialCode(0x02);
+
+	const PrivateMethodsStart: u8 = 0x80;
+
+	const PrivateMethodsEnd: u8 = 0xFE;
+
+	/// A method reserved for private use; `code` must be between `0x80` and `0xFE` inclusive.
+	#[inline(always)]
+	pub fn private(code: u8) -> Option<Self>
+	{
+		if code >= Self::PrivateMethodsStart && code <= Self::PrivateMethodsEnd
+		{
+			Some(Socks5AuthenticationCredentialCode(code))
+		}
+		else
+		{
+			None
+		}
+	}
 }
@@ -2,10 +2,11 @@
 // Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
 
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// SOCKS5 authentication methods offered to a SOCKS5 server, in order of preference.
+#[derive(Debug, Clone)]
 pub struct Socks5AuthenticationCredentials
 {
-	credentials: ArrayVec<[Socks5AuthenticationCredential; Socks5AuthenticationCredential::NumberOfMembers]>,
+	methods: Vec<Rc<dyn Socks5AuthenticationMethod>>,
 	presence: HashMap<Socks5AuthenticationCredentialCode, usize>,
 }
 
@@ -16,19 +17,20 @@ impl Default for Socks5AuthenticationCredentials
 	{
 		Self
 		{
-			credentials: ArrayVec::new(),
-			presence: HashSet::with_capacity(Socks5AuthenticationCredential::NumberOfMembers),
+			methods: Vec::new(),
+			presence: HashMap::default(),
 		}
 	}
 }
 
 impl Deref for Socks5AuthenticationCredentials
 {
-	type Target = ArrayVec<[Socks5AuthenticationCredential; Socks5AuthenticationCredential::NumberOfMembers]>;
+	type Target = [Rc<dyn Socks5AuthenticationMethod>];
 
+	#[inline(always)]
 	fn deref(&self) -> &Self::Target
 	{
-		&self.credentials
+		&self.methods[..]
 	}
 }
 
@@ -36,25 +38,29 @@ impl Socks5AuthenticationCredentials
 {
 	const Version: u8 = 5;
 
-	const PacketMaximumSize: usize = SizeOfVersion + SizeOfNumberOfMembers + Socks5AuthenticationCredential::NumberOfMembers;
+	const SizeOfVersion: usize = 1;
+
+	const SizeOfNumberOfMembers: usize = 1;
+
+	const MaximumNumberOfMembers: usize = 255;
+
+	const PacketMaximumSize: usize = Self::SizeOfVersion + Self::SizeOfNumberOfMembers + Self::MaximumNumberOfMembers;
 
 	#[inline(always)]
-	pub(crate) fn send_request<'yielder, SD: SocketData>(&self, unencrypted_stream: UnencryptedStream<'yielder, SD>) -> Result<UnencryptedStream<'yielder, SD>, CompleteError>
+	pub(crate) fn send_request(&self, stream: &mut dyn Socks5HandshakeStream) -> Result<(), CompleteError>
 	{
 		let (buffer, packet_length) = self.write_packet();
-		send_packet(unencrypted_stream, &buffer[0 .. packet_length])
+		stream.write_all(&buffer[0 .. packet_length])
 	}
 
 	#[inline(always)]
-	fn write_packet(&self) -> ([u8; Self:: VersionIdentifierMaximumMessageSize], usize)
+	fn write_packet(&self) -> ([u8; Self::PacketMaximumSize], usize)
 	{
 		let socks5_authentication_credentials_length = self.len();
 
-		const SizeOfVersion: usize = 1;
-		const SizeOfNumberOfMembers: usize = 1;
-		const OffsetToCredentialCodes: usize = SizeOfVersion + SizeOfNumberOfMembers;
+		const OffsetToCredentialCodes: usize = Socks5AuthenticationCredentials::SizeOfVersion + Socks5AuthenticationCredentials::SizeOfNumberOfMembers;
 
-		let mut authentication_credentials_buffer: [u8; VersionIdentifierMaximumMessageSize] = unsafe { uninitialized() };
+		let mut authentication_credentials_buffer: [u8; Self::PacketMaximumSize] = unsafe { uninitialized() };
 
 		*(unsafe { authentication_credentials_buffer.get_unchecked_mut(0) }) = Self::Version;
 
@@ -62,7 +68,7 @@ impl Socks5AuthenticationCredentials
 
 		for index in 0 .. socks5_authentication_credentials_length
 		{
-			let code = (unsafe { socks5_authentication_credentials.get_unchecked(index) }).to_code() as u8;
+			let code: u8 = (unsafe { self.methods.get_unchecked(index) }).code().into();
 			*(unsafe { authentication_credentials_buffer.get_unchecked_mut(OffsetToCredentialCodes + index) }) = code;
 		}
 
@@ -89,40 +95,48 @@ impl Socks5AuthenticationCredentials
 
 	/// Add `credential`.
 	///
-	/// Only one credential of each type of member of `Socks5AuthenticationCredential` is permitted.
-	///
-	/// Returns `true` if `credential` not previously added (this will cause the old credential to be dropped and this credential to be put last).
+	/// See `add_method()`.
 	#[inline(always)]
 	pub fn add(&mut self, credential: Socks5AuthenticationCredential) -> bool
 	{
-		let code = credential.to_code();
+		self.add_method(Rc::new(credential))
+	}
+
+	/// Add `method`.
+	///
+	/// Only one method for each code is permitted, and at most 255 methods may be added.
+	///
+	/// Returns `true` if a method with the same code was not previously added; otherwise the old method is replaced in its original position and `false` is returned.
+	#[inline(always)]
+	pub fn add_method(&mut self, method: Rc<dyn Socks5AuthenticationMethod>) -> bool
+	{
+		let code = method.code();
+		debug_assert_ne!(Into::<u8>::into(code), 0xFF, "code 0xFF is not permitted");
 
 		use self::Entry::*;
 
-		match self.credentials.entry(code)
+		match self.presence.entry(code)
 		{
 			Vacant(vacant) =>
 			{
-				self.credentials.push(credential);
-				vacant.insert(self.credentials.len());
-				false
+				assert!(self.methods.len() < Self::MaximumNumberOfMembers, "no more than 255 methods may be added");
+				vacant.insert(self.methods.len());
+				self.methods.push(method);
+				true
 			}
 
 			Occupied(occupied) =>
 			{
 				let index = *occupied.get();
-				self.credentials.remove(index);
-				self.credentials.push(credential);
-				*occupied.get_mut() = self.credentials.len();
-				true
+				self.methods[index] = method;
+				false
 			}
 		}
 	}
 
 	#[inline(always)]
-	fn get_from_code(&self, code: Socks5AuthenticationCredentialCode) -> Option<&Socks5AuthenticationCredential>
+	fn get_from_code(&self, code: Socks5AuthenticationCredentialCode) -> Option<&Rc<dyn Socks5AuthenticationMethod>>
 	{
-		self.presence.get(&code).map(|index| unsafe { self.credentials.get_unchecked(*index) } )
+		self.presence.get(&code).map(|index| unsafe { self.methods.get_unchecked(*index) } )
 	}
 }
-
@@ -0,0 +1,17 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A SOCKS5 authentication method which can be offered to a SOCKS5 server and, if selected, negotiated.
+///
+/// Implemented by `Socks5AuthenticationCredential` (no authentication and user name and password) and `Socks5GssApiAuthenticationMethod`; implement this to add private methods (`0x80` to `0xFE` inclusive).
+pub trait Socks5AuthenticationMethod: Debug
+{
+	/// Code sent to the server; must not be `0xFF`.
+	fn code(&self) -> Socks5AuthenticationCredentialCode;
+
+	/// Method-specific sub-negotiation, after this method has been selected by the SOCKS5 server.
+	///
+	/// Returns per-message encapsulation to use for the rest of the SOCKS5 handshake, if any.
+	fn negotiate(&self, stream: &mut dyn Socks5HandshakeStream) -> Result<Option<Box<dyn Socks5MessageEncapsulation>>, CompleteError>;
+}
@@ -0,0 +1,59 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A factory to create instances of any stream where a SOCKS5 server is asked, using the BIND command, to listen for and accept a single inbound connection from a remote peer (eg for FTP-style reverse connections).
+///
+/// The SOCKS5 server sends two replies: the first with the address it is listening on, which must be communicated to the remote peer by other means (eg a FTP `PORT` command), and the second once the remote peer has connected, with the remote peer's address.
+/// The first reply is passed to the `first_reply` callback supplied in `AdditionalArguments`, before waiting for the second.
+///
+/// Fails if the authentication method negotiates per-message encapsulation (eg GSS-API), as it can not be applied to the tunnelled data.
+#[derive(Debug)]
+pub struct Socks5BindStreamFactory<SF: StreamFactory<SD>, SD: SocketData>(pub SF, PhantomData<SD>);
+
+impl<SF: StreamFactory<SD>, SD: SocketData> StreamFactory<SD> for Socks5BindStreamFactory<SF, SD>
+{
+	type S = SF::S;
+
+	/// Returns the address the SOCKS5 server listened on (the first reply) and the address of the remote peer which connected (the second reply).
+	type ProxyOrTunnelInformation = (BoundSocketAddress, BoundSocketAddress, SF::ProxyOrTunnelInformation);
+
+	/// The expected address of the remote peer, the credentials, the `first_reply` callback and the inner stream factory's additional arguments.
+	type AdditionalArguments = (Socks5Request, Rc<Socks5AuthenticationCredentials>, Box<dyn FnOnce(&BoundSocketAddress) -> Result<(), CompleteError>>, SF::AdditionalArguments);
+
+	#[inline(always)]
+	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
+	{
+		let (socks5_request, socks5_authentication_credentials, first_reply, additional_arguments) = additional_arguments;
+
+		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
+
+		let mut unencrypted_stream = UnencryptedStream::new(generic_stream);
+
+		let (listening_socket, mut encapsulation) = socks5_handshake(&mut unencrypted_stream, &socks5_authentication_credentials, &socks5_request, Socks5Command::Bind)?;
+		if unlikely!(encapsulation.is_some())
+		{
+			return error(Socks5ProtocolFailureError::MessageEncapsulationNotSupportedForTunnelledData)
+		}
+
+		first_reply(&listening_socket)?;
+
+		let remote_peer_socket = Socks5Reply::read_reply(&mut unencrypted_stream, &mut encapsulation)?;
+
+		let (streaming_socket_file_descriptor, yielder) = unencrypted_stream.unwrap();
+
+		let (stream, proxy_or_tunnel_information) = self.0.new_stream_and_handshake(streaming_socket_file_descriptor, yielder, additional_arguments)?;
+
+		Ok((stream, (listening_socket, remote_peer_socket, proxy_or_tunnel_information)))
+	}
+}
+
+impl<SF: StreamFactory<SD>, SD: SocketData> Socks5BindStreamFactory<SF, SD>
+{
+	/// New instance.
+	#[inline(always)]
+	pub fn new(stream_factory: SF) -> Self
+	{
+		Socks5BindStreamFactory(stream_factory, PhantomData)
+	}
+}
@@ -0,0 +1,18 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A SOCKS5 command (`CMD`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Socks5Command
+{
+	/// Connect to a destination.
+	Connect = 0x01,
+
+	/// Ask the SOCKS5 server to listen for a single inbound connection, eg for FTP-style reverse connections.
+	Bind = 0x02,
+
+	/// Ask the SOCKS5 server to relay UDP datagrams.
+	UdpAssociate = 0x03,
+}
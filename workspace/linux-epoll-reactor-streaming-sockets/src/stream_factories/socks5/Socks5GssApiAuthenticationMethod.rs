@@ -0,0 +1,86 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// GSS-API authentication (RFC 1961), with per-message integrity or confidentiality protection of the rest of the SOCKS5 handshake and of relayed UDP datagrams.
+///
+/// This crate does not implement GSS-API itself; `new_security_context` should return a context wrapping a GSS-API (typically Kerberos) implementation.
+pub struct Socks5GssApiAuthenticationMethod<C: Socks5GssApiSecurityContext + 'static, F: Fn() -> Result<C, Box<dyn error::Error>>>
+{
+	/// Creates a new security context for each connection.
+	pub new_security_context: F,
+
+	/// Protection level to request; authentication fails if the server chooses a weaker one.
+	pub protection_level: Socks5GssApiProtectionLevel,
+}
+
+impl<C: Socks5GssApiSecurityContext + 'static, F: Fn() -> Result<C, Box<dyn error::Error>>> Debug for Socks5GssApiAuthenticationMethod<C, F>
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "Socks5GssApiAuthenticationMethod {{ new_security_context: _, protection_level: {:?} }}", self.protection_level)
+	}
+}
+
+impl<C: Socks5GssApiSecurityContext + 'static, F: Fn() -> Result<C, Box<dyn error::Error>>> Socks5AuthenticationMethod for Socks5GssApiAuthenticationMethod<C, F>
+{
+	#[inline(always)]
+	fn code(&self) -> Socks5AuthenticationCredentialCode
+	{
+		Socks5AuthenticationCredentialCode::GssApi
+	}
+
+	#[inline(always)]
+	fn negotiate(&self, stream: &mut dyn Socks5HandshakeStream) -> Result<Option<Box<dyn Socks5MessageEncapsulation>>, CompleteError>
+	{
+		let mut security_context = (self.new_security_context)().map_err(CompleteError::ProtocolViolation)?;
+
+		let mut input_token = None;
+		loop
+		{
+			let (output_token, is_established) = match security_context.initialize_security_context(input_token.as_ref().map(Vec::as_slice))
+			{
+				Ok(result) => result,
+
+				Err(error) =>
+				{
+					Socks5GssApiMessage::abort(stream);
+					return Err(CompleteError::ProtocolViolation(error))
+				}
+			};
+
+			if !output_token.is_empty()
+			{
+				Socks5GssApiMessage::send(stream, Socks5GssApiMessage::Authentication, &output_token[..])?;
+			}
+
+			if is_established
+			{
+				break
+			}
+
+			input_token = Some(Socks5GssApiMessage::receive(stream, Socks5GssApiMessage::Authentication)?);
+		}
+
+		// The protection level is itself sent and received protected with integrity only.
+		let protection_level = [self.protection_level as u8];
+		let token = security_context.wrap(&protection_level[..], false).map_err(CompleteError::ProtocolViolation)?;
+		Socks5GssApiMessage::send(stream, Socks5GssApiMessage::ProtectionLevelNegotiation, &token[..])?;
+
+		let token = Socks5GssApiMessage::receive(stream, Socks5GssApiMessage::ProtectionLevelNegotiation)?;
+		let chosen_protection_level = security_context.unwrap(&token[..]).map_err(CompleteError::ProtocolViolation)?;
+		if unlikely!(chosen_protection_level.len() != 1)
+		{
+			return error(Socks5ProtocolFailureError::GssApiProtectionLevelLengthInvalid(chosen_protection_level.len()))
+		}
+		let chosen_protection_level = Socks5GssApiProtectionLevel::parse(chosen_protection_level[0])?;
+		if unlikely!(!chosen_protection_level.is_at_least_as_strong_as(self.protection_level))
+		{
+			Socks5GssApiMessage::abort(stream);
+			return error(Socks5ProtocolFailureError::GssApiProtectionLevelWeakerThanRequested(chosen_protection_level))
+		}
+
+		Ok(Some(Box::new(Socks5GssApiEncapsulation::new(security_context, chosen_protection_level.confidentiality()))))
+	}
+}
@@ -0,0 +1,67 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// GSS-API per-message encapsulation (RFC 1961, Section 5).
+#[derive(Debug)]
+pub struct Socks5GssApiEncapsulation<C: Socks5GssApiSecurityContext>
+{
+	security_context: C,
+	confidentiality: bool,
+}
+
+impl<C: Socks5GssApiSecurityContext> Socks5MessageEncapsulation for Socks5GssApiEncapsulation<C>
+{
+	#[inline(always)]
+	fn send_message(&mut self, stream: &mut dyn Socks5HandshakeStream, message: &[u8]) -> Result<(), CompleteError>
+	{
+		let token = self.wrap(message)?;
+		Socks5GssApiMessage::send(stream, Socks5GssApiMessage::Encapsulation, &token[..])
+	}
+
+	#[inline(always)]
+	fn receive_message(&mut self, stream: &mut dyn Socks5HandshakeStream) -> Result<Vec<u8>, CompleteError>
+	{
+		let token = Socks5GssApiMessage::receive(stream, Socks5GssApiMessage::Encapsulation)?;
+		self.unwrap(&token[..])
+	}
+
+	#[inline(always)]
+	fn encapsulate_datagram(&mut self, datagram: &[u8]) -> Result<Vec<u8>, CompleteError>
+	{
+		let token = self.wrap(datagram)?;
+		Socks5GssApiMessage::frame(Socks5GssApiMessage::Encapsulation, &token[..])
+	}
+
+	#[inline(always)]
+	fn decapsulate_datagram(&mut self, mut encapsulated_datagram: &[u8]) -> Result<Vec<u8>, CompleteError>
+	{
+		let token = Socks5GssApiMessage::receive(&mut encapsulated_datagram, Socks5GssApiMessage::Encapsulation)?;
+		self.unwrap(&token[..])
+	}
+}
+
+impl<C: Socks5GssApiSecurityContext> Socks5GssApiEncapsulation<C>
+{
+	#[inline(always)]
+	pub(crate) fn new(security_context: C, confidentiality: bool) -> Self
+	{
+		Self
+		{
+			security_context,
+			confidentiality,
+		}
+	}
+
+	#[inline(always)]
+	fn wrap(&mut self, message: &[u8]) -> Result<Vec<u8>, CompleteError>
+	{
+		self.security_context.wrap(message, self.confidentiality).map_err(CompleteError::ProtocolViolation)
+	}
+
+	#[inline(always)]
+	fn unwrap(&mut self, token: &[u8]) -> Result<Vec<u8>, CompleteError>
+	{
+		self.security_context.unwrap(token).map_err(CompleteError::ProtocolViolation)
+	}
+}
@@ -0,0 +1,81 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// GSS-API message framing (RFC 1961, Section 3): `VER`, `MTYP`, `LEN` and `TOKEN`.
+struct Socks5GssApiMessage;
+
+impl Socks5GssApiMessage
+{
+	const Version: u8 = 0x01;
+
+	const Abort: u8 = 0xFF;
+
+	pub(crate) const Authentication: u8 = 0x01;
+
+	pub(crate) const ProtectionLevelNegotiation: u8 = 0x02;
+
+	pub(crate) const Encapsulation: u8 = 0x03;
+
+	#[inline(always)]
+	pub(crate) fn send(stream: &mut dyn Socks5HandshakeStream, message_type: u8, token: &[u8]) -> Result<(), CompleteError>
+	{
+		let message = Self::frame(message_type, token)?;
+		stream.write_all(&message[..])
+	}
+
+	/// Tells the server that context establishment has failed (RFC 1961, Section 3.3); failure to send is ignored as the connection is about to be closed.
+	#[inline(always)]
+	pub(crate) fn abort(stream: &mut dyn Socks5HandshakeStream)
+	{
+		let _ = stream.write_all(&[Self::Version, Self::Abort][..]);
+	}
+
+	#[inline(always)]
+	pub(crate) fn frame(message_type: u8, token: &[u8]) -> Result<Vec<u8>, CompleteError>
+	{
+		let length = token.len();
+		if unlikely!(length > ::std::u16::MAX as usize)
+		{
+			return Err(CompleteError::InvalidDataSupplied("A GSS-API token can not exceed 65,535 bytes".to_string()))
+		}
+
+		let mut message = Vec::with_capacity(4 + length);
+		message.push(Self::Version);
+		message.push(message_type);
+		message.extend_from_slice(&(length as u16).to_be_bytes());
+		message.extend_from_slice(token);
+		Ok(message)
+	}
+
+	#[inline(always)]
+	pub(crate) fn receive(stream: &mut dyn Socks5HandshakeStream, expected_message_type: u8) -> Result<Vec<u8>, CompleteError>
+	{
+		use self::Socks5ProtocolFailureError::*;
+
+		let mut header = [0u8; 2];
+		stream.read_exactly(&mut header[..])?;
+
+		let version = header[0];
+		if unlikely!(version != Self::Version)
+		{
+			return error(GssApiVersionInvalid(version))
+		}
+
+		let message_type = header[1];
+		if unlikely!(message_type == Self::Abort)
+		{
+			return error(GssApiAborted)
+		}
+		if unlikely!(message_type != expected_message_type)
+		{
+			return error(GssApiMessageTypeInvalid(message_type))
+		}
+
+		let mut length = [0u8; 2];
+		stream.read_exactly(&mut length[..])?;
+		let mut token = vec![0; u16::from_be_bytes(length) as usize];
+		stream.read_exactly(&mut token[..])?;
+		Ok(token)
+	}
+}
@@ -0,0 +1,79 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// GSS-API per-message protection level (RFC 1961, Section 4).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Socks5GssApiProtectionLevel
+{
+	/// Required per-message integrity.
+	Integrity = 0x01,
+
+	/// Required per-message integrity and confidentiality.
+	IntegrityAndConfidentiality = 0x02,
+
+	/// Selective per-message integrity or confidentiality, based on local client and server configurations.
+	///
+	/// When chosen, integrity only is used.
+	Selective = 0x03,
+}
+
+impl Socks5GssApiProtectionLevel
+{
+	#[inline(always)]
+	pub(crate) fn parse(protection_level: u8) -> Result<Self, CompleteError>
+	{
+		use self::Socks5GssApiProtectionLevel::*;
+
+		match protection_level
+		{
+			0x01 => Ok(Integrity),
+			0x02 => Ok(IntegrityAndConfidentiality),
+			0x03 => Ok(Selective),
+			_ => error(Socks5ProtocolFailureError::GssApiProtectionLevelInvalid(protection_level)),
+		}
+	}
+
+	/// `Selective` is as strong as `Integrity`, as integrity only is used when it is chosen.
+	#[inline(always)]
+	pub(crate) fn is_at_least_as_strong_as(self, other: Self) -> bool
+	{
+		self.confidentiality() || !other.confidentiality()
+	}
+
+	#[inline(always)]
+	pub(crate) fn confidentiality(self) -> bool
+	{
+		self == Socks5GssApiProtectionLevel::IntegrityAndConfidentiality
+	}
+}
+
+#[cfg(test)]
+mod socks5_gss_api_protection_level_tests
+{
+	use super::*;
+	use self::Socks5GssApiProtectionLevel::*;
+
+	#[test]
+	fn parse()
+	{
+		assert_eq!(Socks5GssApiProtectionLevel::parse(0x01).unwrap(), Integrity);
+		assert_eq!(Socks5GssApiProtectionLevel::parse(0x02).unwrap(), IntegrityAndConfidentiality);
+		assert_eq!(Socks5GssApiProtectionLevel::parse(0x03).unwrap(), Selective);
+		assert!(Socks5GssApiProtectionLevel::parse(0x00).is_err());
+		assert!(Socks5GssApiProtectionLevel::parse(0x04).is_err());
+	}
+
+	#[test]
+	fn is_at_least_as_strong_as()
+	{
+		assert!(IntegrityAndConfidentiality.is_at_least_as_strong_as(IntegrityAndConfidentiality));
+		assert!(IntegrityAndConfidentiality.is_at_least_as_strong_as(Integrity));
+		assert!(IntegrityAndConfidentiality.is_at_least_as_strong_as(Selective));
+		assert!(Integrity.is_at_least_as_strong_as(Selective));
+		assert!(Selective.is_at_least_as_strong_as(Integrity));
+		assert!(!Integrity.is_at_least_as_strong_as(IntegrityAndConfidentiality));
+		assert!(!Selective.is_at_least_as_strong_as(IntegrityAndConfidentiality));
+	}
+}
@@ -0,0 +1,20 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A GSS-API security context, typically wrapping a Kerberos implementation's `gss_init_sec_context()`, `gss_wrap()` and `gss_unwrap()`.
+///
+/// Used by `Socks5GssApiAuthenticationMethod`; one is created for each connection.
+pub trait Socks5GssApiSecurityContext: Debug
+{
+	/// Calls `gss_init_sec_context()`, with no input token on the first call.
+	///
+	/// Returns the output token (which may be empty) and whether the context is now established (`GSS_S_COMPLETE` rather than `GSS_S_CONTINUE_NEEDED`).
+	fn initialize_security_context(&mut self, input_token: Option<&[u8]>) -> Result<(Vec<u8>, bool), Box<dyn error::Error>>;
+
+	/// Calls `gss_wrap()`, requesting confidentiality if `confidentiality` is `true`.
+	fn wrap(&mut self, message: &[u8], confidentiality: bool) -> Result<Vec<u8>, Box<dyn error::Error>>;
+
+	/// Calls `gss_unwrap()`.
+	fn unwrap(&mut self, token: &[u8]) -> Result<Vec<u8>, Box<dyn error::Error>>;
+}
@@ -0,0 +1,52 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// An object-safe view of a stream used by `Socks5AuthenticationMethod` implementations during a SOCKS5 handshake.
+///
+/// Also implemented for `&[u8]`, so that replies received encapsulated can be parsed.
+pub trait Socks5HandshakeStream
+{
+	/// Reads exactly enough bytes to fill `buffer`.
+	fn read_exactly(&mut self, buffer: &mut [u8]) -> Result<(), CompleteError>;
+
+	/// Writes all of `buffer`.
+	fn write_all(&mut self, buffer: &[u8]) -> Result<(), CompleteError>;
+}
+
+impl<'yielder, SD: SocketData> Socks5HandshakeStream for UnencryptedStream<'yielder, SD>
+{
+	#[inline(always)]
+	fn read_exactly(&mut self, buffer: &mut [u8]) -> Result<(), CompleteError>
+	{
+		read_exactly(self, buffer)
+	}
+
+	#[inline(always)]
+	fn write_all(&mut self, buffer: &[u8]) -> Result<(), CompleteError>
+	{
+		send_packet(self, buffer)
+	}
+}
+
+impl<'a> Socks5HandshakeStream for &'a [u8]
+{
+	#[inline(always)]
+	fn read_exactly(&mut self, buffer: &mut [u8]) -> Result<(), CompleteError>
+	{
+		let length = buffer.len();
+		if unlikely!(self.len() < length)
+		{
+			return error(Socks5ProtocolFailureError::EncapsulatedMessageTruncated)
+		}
+		buffer.copy_from_slice(&self[.. length]);
+		*self = &self[length .. ];
+		Ok(())
+	}
+
+	#[inline(always)]
+	fn write_all(&mut self, _buffer: &[u8]) -> Result<(), CompleteError>
+	{
+		Err(CompleteError::InvalidDataSupplied("A received message can not be written to".to_string()))
+	}
+}
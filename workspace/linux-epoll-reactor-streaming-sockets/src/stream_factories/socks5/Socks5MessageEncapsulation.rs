@@ -0,0 +1,23 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Per-message encapsulation (eg GSS-API integrity or confidentiality protection) negotiated by a `Socks5AuthenticationMethod`.
+///
+/// Applies to the SOCKS5 request and replies and to UDP datagrams relayed after UDP ASSOCIATE.
+///
+/// It can not be applied to data tunnelled after CONNECT or BIND, so `Socks5StreamFactory` and `Socks5BindStreamFactory` fail with `Socks5ProtocolFailureError::MessageEncapsulationNotSupportedForTunnelledData` if it is negotiated.
+pub trait Socks5MessageEncapsulation: Debug
+{
+	/// Encapsulates and sends `message`.
+	fn send_message(&mut self, stream: &mut dyn Socks5HandshakeStream, message: &[u8]) -> Result<(), CompleteError>;
+
+	/// Receives and decapsulates a message.
+	fn receive_message(&mut self, stream: &mut dyn Socks5HandshakeStream) -> Result<Vec<u8>, CompleteError>;
+
+	/// Encapsulates an UDP datagram (including its `Socks5UdpHeader`) to send to the relay.
+	fn encapsulate_datagram(&mut self, datagram: &[u8]) -> Result<Vec<u8>, CompleteError>;
+
+	/// Decapsulates an UDP datagram received from the relay; the result starts with a `Socks5UdpHeader`.
+	fn decapsulate_datagram(&mut self, encapsulated_datagram: &[u8]) -> Result<Vec<u8>, CompleteError>;
+}
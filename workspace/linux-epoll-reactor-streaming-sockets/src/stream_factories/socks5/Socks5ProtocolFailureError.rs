@@ -65,4 +65,65 @@ pub enum Socks5ProtocolFailureError
 
 	/// The address type `ATYP` was for a host name that was too large (the actualy size is in tuple).
 	HostNameInReplyWasTooLarge(u8),
+
+	/// An UDP datagram was too short to contain a SOCKS5 UDP request header.
+	UdpHeaderTruncated,
+
+	/// The `RSV` field in an UDP request header was not 0x0000.
+	UdpHeaderRsvFieldWasNotZero,
+
+	/// An UDP datagram was a fragment (fragment number is in tuple); fragmentation is not supported.
+	UdpFragmentationNotSupported(u8),
+
+	/// A message received encapsulated was too short.
+	EncapsulatedMessageTruncated,
+
+	/// GSS-API message version was not `1` (actual value in tuple).
+	GssApiVersionInvalid(u8),
+
+	/// GSS-API message type was not that expected (actual value in tuple).
+	GssApiMessageTypeInvalid(u8),
+
+	/// GSS-API context establishment was aborted by the server.
+	GssApiAborted,
+
+	/// GSS-API protection level chosen by the server was invalid (actual value in tuple).
+	GssApiProtectionLevelInvalid(u8),
+
+	/// GSS-API protection level message from the server was not exactly one byte long (actual length in tuple).
+	GssApiProtectionLevelLengthInvalid(usize),
+
+	/// GSS-API protection level chosen by the server was weaker than that requested (chosen value in tuple).
+	GssApiProtectionLevelWeakerThanRequested(Socks5GssApiProtectionLevel),
+
+	/// Per-message encapsulation (eg GSS-API integrity or confidentiality protection) was negotiated, but it can not be applied to data tunnelled after CONNECT or BIND.
+	MessageEncapsulationNotSupportedForTunnelledData,
+
+	/// A client of a SOCKS server failed to authenticate.
+	AuthenticationFailed,
+
+	/// A client of a SOCKS server requested a destination denied by policy.
+	DestinationDenied,
+
+	/// A client of a SOCKS server sent an unrecognised `CMD` (actual value in tuple).
+	RequestContainedAnUnrecognisedCommand(u8),
+
+	/// The `RSV` field in a request to a SOCKS server was not 0x00 (actual value in tuple).
+	RequestRsvFieldWasNotZero(u8),
+
+	/// A SOCKS4 or SOCKS4a user identifier or host name exceeded 255 bytes.
+	Socks4StringTooLong,
+}
+
+impl Display for Socks5ProtocolFailureError
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		Debug::fmt(self, f)
+	}
+}
+
+impl error::Error for Socks5ProtocolFailureError
+{
 }
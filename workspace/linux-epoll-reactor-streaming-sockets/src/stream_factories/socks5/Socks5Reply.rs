@@ -0,0 +1,125 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+const SizeOfVER: usize = 1;
+
+const SizeOfREP: usize = 1;
+
+const SizeOfRSV: usize = 1;
+
+const SizeOfATYP: usize = 1;
+
+const SizeOfBNDPORT: usize = 2;
+
+const SizeOfFixedHeader: usize = SizeOfVER + SizeOfREP + SizeOfRSV + SizeOfATYP;
+
+struct Socks5Reply<'a>
+{
+	stream: &'a mut dyn Socks5HandshakeStream,
+	small_reply_packet_buffer: [u8; SizeOfFixedHeader + InternetProtocolAddressOrHostName::MaximumSizeOfAddressTypeAndAddress + SizeOfBNDPORT],
+	bytes_read_so_far: usize,
+}
+
+impl<'a> Socks5Reply<'a>
+{
+	/// Reads a reply to a CONNECT, BIND or UDP ASSOCIATE request, decapsulating it if necessary.
+	///
+	/// BIND requests receive two replies; call this twice.
+	#[inline(always)]
+	pub(crate) fn read_reply(stream: &mut dyn Socks5HandshakeStream, encapsulation: &mut Option<Box<dyn Socks5MessageEncapsulation>>) -> Result<BoundSocketAddress, CompleteError>
+	{
+		match encapsulation
+		{
+			&mut None => Socks5Reply::read_reply_unencapsulated(stream),
+
+			&mut Some(ref mut encapsulation) =>
+			{
+				let message = encapsulation.receive_message(stream)?;
+				let mut message = &message[..];
+				Socks5Reply::read_reply_unencapsulated(&mut message)
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn read_reply_unencapsulated(stream: &'a mut dyn Socks5HandshakeStream) -> Result<BoundSocketAddress, CompleteError>
+	{
+		use self::Socks5ProtocolFailureError::*;
+
+		let mut this = Self
+		{
+			stream,
+			small_reply_packet_buffer: unsafe { uninitialized() },
+			bytes_read_so_far: 0,
+		};
+
+		this.read_reply_bytes(SizeOfFixedHeader)?;
+
+		let version = this.small_reply_packet_buffer[0];
+		if unlikely!(version != Socks5Request::Version)
+		{
+			return error(VersionInvalid(version))
+		}
+
+		let rep = this.small_reply_packet_buffer[SizeOfVER];
+		match rep
+		{
+			0x00 => (),
+			0x01 => return error(GeneralSocksServerFailure),
+			0x02 => return error(ConnectionNotAllowedByRuleset),
+			0x03 => return error(NetworkUnreachable),
+			0x04 => return error(HostUnreachable),
+			0x05 => return error(ConnectionRefused),
+			0x06 => return error(TimeToLiveExpired),
+			0x07 => return error(CommandNotSupported),
+			0x08 => return error(AddressTypeNotSupported),
+			_ => return error(UnassignedError(rep)),
+		}
+
+		let rsv = this.small_reply_packet_buffer[SizeOfVER + SizeOfREP];
+		if unlikely!(rsv != 0x00)
+		{
+			return error(ReplyRsvFieldWasNotZero(rsv))
+		}
+
+		let atyp = this.small_reply_packet_buffer[SizeOfVER + SizeOfREP + SizeOfRSV];
+
+		let bnd_addr_length = InternetProtocolAddressOrHostName::address_length(atyp, ||
+		{
+			this.read_reply_bytes(SizeOfFixedHeader + 1)?;
+			Ok(this.small_reply_packet_buffer[SizeOfFixedHeader])
+		})?;
+
+		let offset_to_bndport = SizeOfFixedHeader + bnd_addr_length;
+		this.read_reply_bytes(offset_to_bndport + SizeOfBNDPORT)?;
+
+		let bind_address = InternetProtocolAddressOrHostName::parse_address(atyp, &this.small_reply_packet_buffer[SizeOfFixedHeader .. offset_to_bndport]);
+
+		// Uses copy_from_slice as might not be 16-bit aligned on 32-bit ARM platforms, which will cause a ?SIGBUS.
+		let mut bind_port_octets = [0u8; SizeOfBNDPORT];
+		bind_port_octets.copy_from_slice(&this.small_reply_packet_buffer[offset_to_bndport .. offset_to_bndport + SizeOfBNDPORT]);
+		let bind_port = u16::from_be_bytes(bind_port_octets);
+
+		Ok
+		(
+			BoundSocketAddress
+			{
+				address: bind_address,
+				port: bind_port,
+			}
+		)
+	}
+
+	/// Reads until `bytes_read_so_far` is `until` and no further, so that no tunnelled data is consumed.
+	#[inline(always)]
+	fn read_reply_bytes(&mut self, until: usize) -> Result<(), CompleteError>
+	{
+		if self.bytes_read_so_far < until
+		{
+			self.stream.read_exactly(&mut self.small_reply_packet_buffer[self.bytes_read_so_far .. until])?;
+			self.bytes_read_so_far = until;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,36 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A SOCKS5 reply code (`REP`) sent by a SOCKS5 server.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Socks5ReplyCode
+{
+	/// Succeeded.
+	Succeeded = 0x00,
+
+	/// General SOCKS server failure.
+	GeneralSocksServerFailure = 0x01,
+
+	/// Connection not allowed by ruleset.
+	ConnectionNotAllowedByRuleset = 0x02,
+
+	/// Network unreachable.
+	NetworkUnreachable = 0x03,
+
+	/// Host unreachable.
+	HostUnreachable = 0x04,
+
+	/// Connection refused.
+	ConnectionRefused = 0x05,
+
+	/// Time to Live (TTL) expired.
+	TimeToLiveExpired = 0x06,
+
+	/// Command not supported.
+	CommandNotSupported = 0x07,
+
+	/// Address type not supported.
+	AddressTypeNotSupported = 0x08,
+}
@@ -0,0 +1,65 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Data required to make a SOCKS5 client request (CONNECT, BIND or UDP ASSOCIATE).
+#[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
+pub struct Socks5Request
+{
+	/// Address or Host Name.
+	///
+	/// For CONNECT, the destination; for BIND, the address the remote peer is expected to connect from; for UDP ASSOCIATE, the address datagrams will be sent from (usually unspecified, eg `0.0.0.0`).
+	pub address_or_host_name: InternetProtocolAddressOrHostName,
+
+	/// Port.
+	///
+	/// For UDP ASSOCIATE, the port datagrams will be sent from (usually `0`).
+	pub port: u16,
+}
+
+impl Socks5Request
+{
+	const SizeOfVER: usize = 1;
+
+	const SizeOfCMD: usize = 1;
+
+	const SizeOfRSV: usize = 1;
+
+	const SizeOfDSTPORT: usize = 2;
+
+	pub(crate) const Version: u8 = 5;
+
+	const PacketMaximumSize: usize = Self::SizeOfVER + Self::SizeOfCMD + Self::SizeOfRSV + InternetProtocolAddressOrHostName::MaximumSizeOfAddressTypeAndAddress + Self::SizeOfDSTPORT;
+
+	#[inline(always)]
+	pub(crate) fn send_request(&self, stream: &mut dyn Socks5HandshakeStream, command: Socks5Command, encapsulation: &mut Option<Box<dyn Socks5MessageEncapsulation>>) -> Result<(), CompleteError>
+	{
+		let (buffer, packet_length) = self.write_packet(command)?;
+		let packet = &buffer[0 .. packet_length];
+
+		match encapsulation
+		{
+			&mut None => stream.write_all(packet),
+
+			&mut Some(ref mut encapsulation) => encapsulation.send_message(stream, packet),
+		}
+	}
+
+	#[inline(always)]
+	fn write_packet(&self, command: Socks5Command) -> Result<([u8; Self::PacketMaximumSize], usize), CompleteError>
+	{
+		let mut buffer: [u8; Self::PacketMaximumSize] = unsafe { uninitialized() };
+
+		buffer[0] = Self::Version;
+		buffer[Self::SizeOfVER] = command as u8;
+		buffer[Self::SizeOfVER + Self::SizeOfCMD] = 0x00;
+
+		let offset = Self::SizeOfVER + Self::SizeOfCMD + Self::SizeOfRSV;
+		let offset = offset + self.address_or_host_name.write_address_type_and_address(&mut buffer[offset .. ])?;
+
+		// NOTE: Uses copy_from_slice as the DST.PORT field may not be 16-bit aligned and a so a write will fail with ?SIGBUS on ARM 32-bit.
+		buffer[offset .. offset + Self::SizeOfDSTPORT].copy_from_slice(&self.port.to_be_bytes());
+
+		Ok((buffer, offset + Self::SizeOfDSTPORT))
+	}
+}
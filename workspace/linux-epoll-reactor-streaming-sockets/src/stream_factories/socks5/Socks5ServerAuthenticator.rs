@@ -0,0 +1,52 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Authenticates clients of `Socks5ServerStreamFactory`.
+///
+/// Implemented for `()`, which denies all clients.
+pub trait Socks5ServerAuthenticator
+{
+	/// Supported SOCKS5 authentication methods, in order of preference.
+	fn supported_methods(&self) -> &[Socks5AuthenticationCredentialCode];
+
+	/// Authenticates a SOCKS5 user name and password (RFC 1929).
+	///
+	/// Return `None` to deny.
+	fn authenticate_user_name_and_password(&self, user_name: &[u8], password: &[u8]) -> Option<Socks5ServerIdentity>;
+
+	/// Checks a SOCKS4 or SOCKS4a user identifier, which is sent unauthenticated.
+	///
+	/// Return `None` to deny.
+	fn authenticate_socks4_user_identifier(&self, user_identifier: &[u8]) -> Option<Socks5ServerIdentity>;
+
+	/// Negotiates a supported SOCKS5 authentication method other than no authentication and user name and password (eg a private method).
+	///
+	/// Return `Ok(None)` to deny.
+	#[inline(always)]
+	fn negotiate_other_method(&self, code: Socks5AuthenticationCredentialCode, _stream: &mut dyn Socks5HandshakeStream) -> Result<Option<Socks5ServerIdentity>, CompleteError>
+	{
+		Err(CompleteError::InvalidDataSupplied(format!("The authentication method {:?} is listed as supported but can not be negotiated", code)))
+	}
+}
+
+impl Socks5ServerAuthenticator for ()
+{
+	#[inline(always)]
+	fn supported_methods(&self) -> &[Socks5AuthenticationCredentialCode]
+	{
+		&[]
+	}
+
+	#[inline(always)]
+	fn authenticate_user_name_and_password(&self, _user_name: &[u8], _password: &[u8]) -> Option<Socks5ServerIdentity>
+	{
+		None
+	}
+
+	#[inline(always)]
+	fn authenticate_socks4_user_identifier(&self, _user_identifier: &[u8]) -> Option<Socks5ServerIdentity>
+	{
+		None
+	}
+}
@@ -0,0 +1,30 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Decides if an authenticated client of `Socks5ServerStreamFactory` may use a command with a destination.
+///
+/// Implemented for `()`, which denies all, and for closures.
+pub trait Socks5ServerDestinationPolicy
+{
+	/// Is the destination allowed?
+	fn is_destination_allowed(&self, identity: &Socks5ServerIdentity, command: Socks5Command, destination: &BoundSocketAddress) -> bool;
+}
+
+impl Socks5ServerDestinationPolicy for ()
+{
+	#[inline(always)]
+	fn is_destination_allowed(&self, _identity: &Socks5ServerIdentity, _command: Socks5Command, _destination: &BoundSocketAddress) -> bool
+	{
+		false
+	}
+}
+
+impl<F: Fn(&Socks5ServerIdentity, Socks5Command, &BoundSocketAddress) -> bool> Socks5ServerDestinationPolicy for F
+{
+	#[inline(always)]
+	fn is_destination_allowed(&self, identity: &Socks5ServerIdentity, command: Socks5Command, destination: &BoundSocketAddress) -> bool
+	{
+		self(identity, command, destination)
+	}
+}
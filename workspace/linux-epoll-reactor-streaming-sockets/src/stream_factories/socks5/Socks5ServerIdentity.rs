@@ -0,0 +1,20 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Identity of an authenticated client of `Socks5ServerStreamFactory`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Socks5ServerIdentity
+{
+	/// No authentication took place.
+	Anonymous,
+
+	/// Authenticated using a SOCKS5 user name and password; the user name is in the tuple.
+	UserName(Vec<u8>),
+
+	/// A SOCKS4 or SOCKS4a user identifier (`USERID`); this is not authenticated and may be empty.
+	Socks4UserIdentifier(Vec<u8>),
+
+	/// Authenticated using another (eg private) method; the method's code and an opaque identity are in the tuple.
+	Other(Socks5AuthenticationCredentialCode, Vec<u8>),
+}
@@ -0,0 +1,104 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A request received by `Socks5ServerStreamFactory`, which is yet to be replied to.
+///
+/// The `StreamUser` should try to satisfy the request (eg connect to the destination) and then call `reply_succeeded()` or `reply_failed()`; for BIND, `reply_succeeded()` is called twice, once when listening and once when the remote peer has connected.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Socks5ServerRequest
+{
+	/// Version of the SOCKS protocol used by the client.
+	pub version: SocksProtocolVersion,
+
+	/// Command; never `UdpAssociate` for SOCKS4 and SOCKS4a.
+	pub command: Socks5Command,
+
+	/// Destination.
+	///
+	/// For BIND, the address the remote peer is expected to connect from; for UDP ASSOCIATE, the address the client will send datagrams from.
+	pub destination: BoundSocketAddress,
+
+	/// Identity of the client.
+	pub identity: Socks5ServerIdentity,
+}
+
+impl Socks5ServerRequest
+{
+	const Socks4ReplyVersion: u8 = 0x00;
+
+	const Socks4RequestGranted: u8 = 90;
+
+	const Socks4RequestRejectedOrFailed: u8 = 91;
+
+	/// Replies that the request succeeded.
+	///
+	/// `bound` is the address the SOCKS server connected from (CONNECT), is listening on or was connected to from (BIND) or will relay from (UDP ASSOCIATE).
+	/// SOCKS4 and SOCKS4a replies can only contain an Internet Protocol version 4 address; other addresses are sent as `0.0.0.0`.
+	#[inline(always)]
+	pub fn reply_succeeded(&self, stream: &mut dyn Socks5HandshakeStream, bound: &BoundSocketAddress) -> Result<(), CompleteError>
+	{
+		match self.version
+		{
+			SocksProtocolVersion::Four => Self::reply_socks4(stream, Self::Socks4RequestGranted, bound),
+
+			SocksProtocolVersion::Five => Self::reply_socks5(stream, Socks5ReplyCode::Succeeded, bound),
+		}
+	}
+
+	/// Replies that the request failed.
+	///
+	/// SOCKS4 and SOCKS4a replies do not distinguish between reasons for failure.
+	#[inline(always)]
+	pub fn reply_failed(&self, stream: &mut dyn Socks5HandshakeStream, reply_code: Socks5ReplyCode) -> Result<(), CompleteError>
+	{
+		debug_assert_ne!(reply_code, Socks5ReplyCode::Succeeded, "use reply_succeeded()");
+
+		match self.version
+		{
+			SocksProtocolVersion::Four => Self::reply_socks4(stream, Self::Socks4RequestRejectedOrFailed, &Self::unspecified()),
+
+			SocksProtocolVersion::Five => Self::reply_socks5(stream, reply_code, &Self::unspecified()),
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn unspecified() -> BoundSocketAddress
+	{
+		BoundSocketAddress
+		{
+			address: InternetProtocolAddressOrHostName::InternetProtocolAddress(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+			port: 0,
+		}
+	}
+
+	#[inline(always)]
+	pub(crate) fn reply_socks5(stream: &mut dyn Socks5HandshakeStream, reply_code: Socks5ReplyCode, bound: &BoundSocketAddress) -> Result<(), CompleteError>
+	{
+		const OffsetToAddressType: usize = 3;
+		let mut buffer: [u8; OffsetToAddressType + InternetProtocolAddressOrHostName::MaximumSizeOfAddressTypeAndAddress + 2] = unsafe { uninitialized() };
+
+		buffer[0] = Socks5Request::Version;
+		buffer[1] = reply_code as u8;
+		buffer[2] = 0x00;
+		let offset = OffsetToAddressType + bound.address.write_address_type_and_address(&mut buffer[OffsetToAddressType .. ])?;
+		buffer[offset .. offset + 2].copy_from_slice(&bound.port.to_be_bytes());
+
+		stream.write_all(&buffer[.. offset + 2])
+	}
+
+	#[inline(always)]
+	pub(crate) fn reply_socks4(stream: &mut dyn Socks5HandshakeStream, reply_code: u8, bound: &BoundSocketAddress) -> Result<(), CompleteError>
+	{
+		let address = match bound.address
+		{
+			InternetProtocolAddressOrHostName::InternetProtocolAddress(IpAddr::V4(ref address)) => address.octets(),
+
+			_ => [0, 0, 0, 0],
+		};
+		let port = bound.port.to_be_bytes();
+
+		let buffer = [Self::Socks4ReplyVersion, reply_code, port[0], port[1], address[0], address[1], address[2], address[3]];
+		stream.write_all(&buffer[..])
+	}
+}
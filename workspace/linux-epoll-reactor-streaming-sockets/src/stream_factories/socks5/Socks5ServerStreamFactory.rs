@@ -0,0 +1,344 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A factory which, for an accepted stream, acts as a SOCKS5 server (also accepting SOCKS4 and SOCKS4a clients).
+///
+/// The greeting, authentication and request are parsed, the client is authenticated and the destination checked against policy; the request is then passed to a `StreamUser` as `ProxyOrTunnelInformation`, which should satisfy it (eg by connecting to the destination and relaying), replying using `Socks5ServerRequest::reply_succeeded()` or `Socks5ServerRequest::reply_failed()`.
+///
+/// There are no default policies, as a SOCKS server which permits any client to reach any destination is an open relay; `()` can be used for either the authenticator or the destination policy, but denies all.
+///
+/// GSS-API is not supported.
+#[derive(Debug)]
+pub struct Socks5ServerStreamFactory<SSA: Socks5ServerAuthenticator, SSDP: Socks5ServerDestinationPolicy>
+{
+	/// Authenticator.
+	pub authenticator: SSA,
+
+	/// Destination policy.
+	pub destination_policy: SSDP,
+}
+
+impl<SD: SocketData, SSA: Socks5ServerAuthenticator, SSDP: Socks5ServerDestinationPolicy> StreamFactory<SD> for Socks5ServerStreamFactory<SSA, SSDP>
+{
+	/// This is a lie; the lifetime is ***NOT*** `'static` but actually `'yielder` in `new_stream_and_handshake()`.
+	type S = UnencryptedStream<'static, SD>;
+
+	/// The request, yet to be replied to.
+	type ProxyOrTunnelInformation = Socks5ServerRequest;
+
+	type AdditionalArguments = ();
+
+	#[inline(always)]
+	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, _additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
+	{
+		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
+
+		let mut unencrypted_stream = UnencryptedStream::new(generic_stream);
+
+		let socks5_server_request = self.handshake(&mut unencrypted_stream)?;
+
+		// Grotesque hack which extends lifetime from 'yielder to 'static.
+		let stream: Self::S = unsafe { transmute(unencrypted_stream) };
+		Ok((stream, socks5_server_request))
+	}
+}
+
+impl<SSA: Socks5ServerAuthenticator, SSDP: Socks5ServerDestinationPolicy> Socks5ServerStreamFactory<SSA, SSDP>
+{
+	const Socks4Version: u8 = 4;
+
+	const MaximumSizeOfSocks4String: usize = 255;
+
+	/// New instance.
+	#[inline(always)]
+	pub fn new(authenticator: SSA, destination_policy: SSDP) -> Self
+	{
+		Self
+		{
+			authenticator,
+			destination_policy,
+		}
+	}
+
+	#[inline(always)]
+	fn handshake(&self, stream: &mut dyn Socks5HandshakeStream) -> Result<Socks5ServerRequest, CompleteError>
+	{
+		use self::Socks5ProtocolFailureError::*;
+
+		let version = Self::read_byte(stream)?;
+		let socks5_server_request = match version
+		{
+			Self::Socks4Version => self.read_socks4_request(stream)?,
+
+			Socks5Request::Version =>
+			{
+				let identity = self.negotiate_socks5_authentication(stream)?;
+				Self::read_socks5_request(stream, identity)?
+			}
+
+			_ => return error(VersionInvalid(version)),
+		};
+
+		if unlikely!(!self.destination_policy.is_destination_allowed(&socks5_server_request.identity, socks5_server_request.command, &socks5_server_request.destination))
+		{
+			socks5_server_request.reply_failed(stream, Socks5ReplyCode::ConnectionNotAllowedByRuleset)?;
+			return error(DestinationDenied)
+		}
+
+		Ok(socks5_server_request)
+	}
+
+	#[inline(always)]
+	fn negotiate_socks5_authentication(&self, stream: &mut dyn Socks5HandshakeStream) -> Result<Socks5ServerIdentity, CompleteError>
+	{
+		use self::Socks5ProtocolFailureError::*;
+
+		let number_of_methods = Self::read_byte(stream)? as usize;
+		let mut offered_methods = [0u8; 255];
+		let offered_methods = &mut offered_methods[.. number_of_methods];
+		stream.read_exactly(offered_methods)?;
+
+		let chosen_method = self.authenticator.supported_methods().iter().cloned().find(|supported_method| offered_methods.contains(&(*supported_method).into()));
+
+		let chosen_method = match chosen_method
+		{
+			None =>
+			{
+				stream.write_all(&[Socks5Request::Version, 0xFF])?;
+				return error(NoAcceptableAuthenticationMethodsSupplied)
+			}
+
+			Some(chosen_method) => chosen_method,
+		};
+
+		stream.write_all(&[Socks5Request::Version, chosen_method.into()])?;
+
+		let identity = match chosen_method
+		{
+			Socks5AuthenticationCredentialCode::None => Some(Socks5ServerIdentity::Anonymous),
+
+			Socks5AuthenticationCredentialCode::UserNameAndPassword => self.negotiate_user_name_and_password(stream)?,
+
+			_ => self.authenticator.negotiate_other_method(chosen_method, stream)?,
+		};
+
+		identity.map_or_else(|| error(AuthenticationFailed), Ok)
+	}
+
+	#[inline(always)]
+	fn negotiate_user_name_and_password(&self, stream: &mut dyn Socks5HandshakeStream) -> Result<Option<Socks5ServerIdentity>, CompleteError>
+	{
+		use self::Socks5ProtocolFailureError::*;
+
+		let version = Self::read_byte(stream)?;
+		if unlikely!(version != Socks5AuthenticationCredential::UserNamePasswordVersion)
+		{
+			return error(UserNamePasswordVersionInvalid(version))
+		}
+
+		let mut user_name = [0u8; 255];
+		let user_name_length = Self::read_byte(stream)? as usize;
+		let user_name = &mut user_name[.. user_name_length];
+		stream.read_exactly(user_name)?;
+
+		let mut password = [0u8; 255];
+		let password_length = Self::read_byte(stream)? as usize;
+		let password = &mut password[.. password_length];
+		stream.read_exactly(password)?;
+
+		let identity = self.authenticator.authenticate_user_name_and_password(user_name, password);
+
+		let status = if identity.is_some()
+		{
+			0x00
+		}
+		else
+		{
+			0x01
+		};
+		stream.write_all(&[Socks5AuthenticationCredential::UserNamePasswordVersion, status])?;
+
+		Ok(identity)
+	}
+
+	#[inline(always)]
+	fn read_socks5_request(stream: &mut dyn Socks5HandshakeStream, identity: Socks5ServerIdentity) -> Result<Socks5ServerRequest, CompleteError>
+	{
+		use self::Socks5ProtocolFailureError::*;
+
+		let mut fixed_header = [0u8; 4];
+		stream.read_exactly(&mut fixed_header[..])?;
+
+		let version = fixed_header[0];
+		if unlikely!(version != Socks5Request::Version)
+		{
+			return error(VersionInvalid(version))
+		}
+
+		let command = match fixed_header[1]
+		{
+			0x01 => Socks5Command::Connect,
+			0x02 => Socks5Command::Bind,
+			0x03 => Socks5Command::UdpAssociate,
+			command @ _ =>
+			{
+				Socks5ServerRequest::reply_socks5(stream, Socks5ReplyCode::CommandNotSupported, &Socks5ServerRequest::unspecified())?;
+				return error(RequestContainedAnUnrecognisedCommand(command))
+			}
+		};
+
+		let rsv = fixed_header[2];
+		if unlikely!(rsv != 0x00)
+		{
+			return error(RequestRsvFieldWasNotZero(rsv))
+		}
+
+		let atyp = fixed_header[3];
+		let mut address = [0u8; 1 + 253];
+		let address_length = match InternetProtocolAddressOrHostName::address_length(atyp, ||
+		{
+			let number_of_octets_in_host_name = Self::read_byte(stream)?;
+			address[0] = number_of_octets_in_host_name;
+			Ok(number_of_octets_in_host_name)
+		})
+		{
+			Ok(address_length) => address_length,
+
+			Err(complete_error) =>
+			{
+				Socks5ServerRequest::reply_socks5(stream, Socks5ReplyCode::AddressTypeNotSupported, &Socks5ServerRequest::unspecified())?;
+				return Err(complete_error)
+			}
+		};
+
+		let already_read = if atyp == InternetProtocolAddressOrHostName::HostNameAddressType
+		{
+			1
+		}
+		else
+		{
+			0
+		};
+		stream.read_exactly(&mut address[already_read .. address_length])?;
+
+		let mut port = [0u8; 2];
+		stream.read_exactly(&mut port[..])?;
+
+		Ok
+		(
+			Socks5ServerRequest
+			{
+				version: SocksProtocolVersion::Five,
+				command,
+				destination: BoundSocketAddress
+				{
+					address: InternetProtocolAddressOrHostName::parse_address(atyp, &address[.. address_length]),
+					port: u16::from_be_bytes(port),
+				},
+				identity,
+			}
+		)
+	}
+
+	/// The version byte has already been read.
+	#[inline(always)]
+	fn read_socks4_request(&self, stream: &mut dyn Socks5HandshakeStream) -> Result<Socks5ServerRequest, CompleteError>
+	{
+		use self::Socks5ProtocolFailureError::*;
+
+		let mut fixed_header = [0u8; 7];
+		stream.read_exactly(&mut fixed_header[..])?;
+
+		let command = match fixed_header[0]
+		{
+			0x01 => Socks5Command::Connect,
+			0x02 => Socks5Command::Bind,
+			command @ _ =>
+			{
+				Socks5ServerRequest::reply_socks4(stream, Socks5ServerRequest::Socks4RequestRejectedOrFailed, &Socks5ServerRequest::unspecified())?;
+				return error(RequestContainedAnUnrecognisedCommand(command))
+			}
+		};
+
+		let port = u16::from_be_bytes([fixed_header[1], fixed_header[2]]);
+		let address = [fixed_header[3], fixed_header[4], fixed_header[5], fixed_header[6]];
+
+		let user_identifier = Self::read_socks4_string(stream)?;
+
+		// SOCKS4a: an address of `0.0.0.x`, where `x` is not zero, is followed by a host name.
+		let address = if address[0] == 0 && address[1] == 0 && address[2] == 0 && address[3] != 0
+		{
+			let host_name = Self::read_socks4_string(stream)?;
+			if unlikely!(host_name.is_empty())
+			{
+				return error(HostNameInReplyWasEmpty)
+			}
+			let mut array_vec = ArrayVec::new();
+			if unlikely!(host_name.len() > array_vec.capacity())
+			{
+				return error(HostNameInReplyWasTooLarge(host_name.len() as u8))
+			}
+			array_vec.extend(host_name.iter().cloned());
+			InternetProtocolAddressOrHostName::HostName(array_vec)
+		}
+		else
+		{
+			InternetProtocolAddressOrHostName::InternetProtocolAddress(IpAddr::from(address))
+		};
+
+		let identity = match self.authenticator.authenticate_socks4_user_identifier(&user_identifier[..])
+		{
+			None =>
+			{
+				Socks5ServerRequest::reply_socks4(stream, Socks5ServerRequest::Socks4RequestRejectedOrFailed, &Socks5ServerRequest::unspecified())?;
+				return error(AuthenticationFailed)
+			}
+
+			Some(identity) => identity,
+		};
+
+		Ok
+		(
+			Socks5ServerRequest
+			{
+				version: SocksProtocolVersion::Four,
+				command,
+				destination: BoundSocketAddress
+				{
+					address,
+					port,
+				},
+				identity,
+			}
+		)
+	}
+
+	/// Reads an ASCII NUL terminated string, excluding the ASCII NUL.
+	#[inline(always)]
+	fn read_socks4_string(stream: &mut dyn Socks5HandshakeStream) -> Result<Vec<u8>, CompleteError>
+	{
+		let mut string = Vec::new();
+		loop
+		{
+			let byte = Self::read_byte(stream)?;
+			if byte == 0x00
+			{
+				return Ok(string)
+			}
+			if unlikely!(string.len() == Self::MaximumSizeOfSocks4String)
+			{
+				return error(Socks5ProtocolFailureError::Socks4StringTooLong)
+			}
+			string.push(byte);
+		}
+	}
+
+	#[inline(always)]
+	fn read_byte(stream: &mut dyn Socks5HandshakeStream) -> Result<u8, CompleteError>
+	{
+		let mut byte = [0u8; 1];
+		stream.read_exactly(&mut byte[..])?;
+		Ok(byte[0])
+	}
+}
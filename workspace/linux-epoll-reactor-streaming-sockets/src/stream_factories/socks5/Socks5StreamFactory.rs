@@ -2,44 +2,51 @@
 // Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
 
 
-/// A factory to create instances of any stream where there is an initial handshake with a SOCKS5 server.
+/// A factory to create instances of any stream where there is an initial handshake with a SOCKS5 server using the CONNECT command.
+///
+/// Fails if the authentication method negotiates per-message encapsulation (eg GSS-API), as it can not be applied to the tunnelled data.
 #[derive(Debug)]
-pub struct Socks5StreamFactory<SF: StreamFactory<SD>, SD: SocketData>(pub SF);
+pub struct Socks5StreamFactory<SF: StreamFactory<SD>, SD: SocketData>(pub SF, PhantomData<SD>);
 
 impl<SF: StreamFactory<SD>, SD: SocketData> StreamFactory<SD> for Socks5StreamFactory<SF, SD>
 {
-	/// This is a lie; the lifetime is ***NOT*** `'static` but actually `'yielder` in `new_stream_and_handshake()`.
-	/// Rust's lack of associated type constructors makes this impossible to express; `yielder` is ***NOT*** a parameter to put on `UnencryptedStreamFactory` (eg `UnencryptedStreamFactory<'yielder>`) because the lifetime has nothing to do with the lifetime of the factory (indeed, factories live far longer than the coroutines they are invovled in instantiating).
 	type S = SF::S;
 
 	/// Returns a bound socket address.
 	type ProxyOrTunnelInformation = (BoundSocketAddress, SF::ProxyOrTunnelInformation);
 
-	type AdditionalArguments = (Socks5Connect, Rc<Socks5AuthenticationCredentials>, SF::AdditionalArguments);
+	/// The destination, the credentials and the inner stream factory's additional arguments.
+	type AdditionalArguments = (Socks5Request, Rc<Socks5AuthenticationCredentials>, SF::AdditionalArguments);
 
 	#[inline(always)]
 	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
 	{
-		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
-
-		let unencrypted_stream = UnencryptedStream::new(generic_stream);
-
-		let socks5_authentication_credentials = additional_arguments.1;
+		let (socks5_request, socks5_authentication_credentials, additional_arguments) = additional_arguments;
 
-		let unencrypted_stream = socks5_authentication_credentials.send_request(unencrypted_stream)?;
-
-		let (unencrypted_stream, credential) = Socks5CredentialReply::read_reply(unencrypted_stream)?;
+		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
 
-		let unencrypted_stream = credential.negotiate(unencrypted_stream)?;
+		let mut unencrypted_stream = UnencryptedStream::new(generic_stream);
 
-		let socks5_connect = additional_arguments.0;
+		let (bound_socket, encapsulation) = socks5_handshake(&mut unencrypted_stream, &socks5_authentication_credentials, &socks5_request, Socks5Command::Connect)?;
+		if unlikely!(encapsulation.is_some())
+		{
+			return error(Socks5ProtocolFailureError::MessageEncapsulationNotSupportedForTunnelledData)
+		}
 
-		let unencrypted_stream = socks5_connect.send_request(unencrypted_stream)?;
+		let (streaming_socket_file_descriptor, yielder) = unencrypted_stream.unwrap();
 
-		let (unencrypted_stream, bound_socket) = Socks5ConnectReply::read_reply(unencrypted_stream)?;
+		let (stream, proxy_or_tunnel_information) = self.0.new_stream_and_handshake(streaming_socket_file_descriptor, yielder, additional_arguments)?;
 
-		let (streaming_socket_file_descriptor, yielder) = unencrypted_stream.unwrap();
+		Ok((stream, (bound_socket, proxy_or_tunnel_information)))
+	}
+}
 
-		let (stream, proxy_or_tunnel_information) = self.0.new_stream_and_handshake(streaming_socket_file_descriptor, yielder, additional_arguments.2);
+impl<SF: StreamFactory<SD>, SD: SocketData> Socks5StreamFactory<SF, SD>
+{
+	/// New instance.
+	#[inline(always)]
+	pub fn new(stream_factory: SF) -> Self
+	{
+		Socks5StreamFactory(stream_factory, PhantomData)
 	}
 }
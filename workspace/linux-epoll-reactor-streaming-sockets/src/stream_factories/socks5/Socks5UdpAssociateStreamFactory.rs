@@ -0,0 +1,40 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A factory to create a SOCKS5 UDP ASSOCIATE control connection.
+///
+/// The returned stream is the control connection; the UDP association lasts only as long as it remains open, so it should be kept open (and read from, to detect the SOCKS5 server closing it) for as long as datagrams are to be relayed.
+///
+/// Datagrams are sent to and received from the relay address returned as `ProxyOrTunnelInformation` (if unspecified, eg `0.0.0.0`, use the SOCKS5 server's address) and must be encapsulated using `Socks5UdpHeader`, eg in a `DatagramHandler`.
+/// If the authentication method negotiated per-message encapsulation (eg GSS-API), this is also returned and must then be applied to each datagram after `Socks5UdpHeader`.
+#[derive(Debug)]
+pub struct Socks5UdpAssociateStreamFactory;
+
+impl<SD: SocketData> StreamFactory<SD> for Socks5UdpAssociateStreamFactory
+{
+	/// This is a lie; the lifetime is ***NOT*** `'static` but actually `'yielder` in `new_stream_and_handshake()`.
+	type S = UnencryptedStream<'static, SD>;
+
+	/// Returns the relay address and any per-message encapsulation.
+	type ProxyOrTunnelInformation = (BoundSocketAddress, Option<Box<dyn Socks5MessageEncapsulation>>);
+
+	/// The address datagrams will be sent from (usually unspecified) and the credentials.
+	type AdditionalArguments = (Socks5Request, Rc<Socks5AuthenticationCredentials>);
+
+	#[inline(always)]
+	fn new_stream_and_handshake<'yielder>(&self, streaming_socket_file_descriptor: StreamingSocketFileDescriptor<SD>, yielder: Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>, additional_arguments: Self::AdditionalArguments) -> Result<(Self::S, Self::ProxyOrTunnelInformation), CompleteError>
+	{
+		let (socks5_request, socks5_authentication_credentials) = additional_arguments;
+
+		let generic_stream = GenericStream::wrap(streaming_socket_file_descriptor, yielder);
+
+		let mut unencrypted_stream = UnencryptedStream::new(generic_stream);
+
+		let relay = socks5_handshake(&mut unencrypted_stream, &socks5_authentication_credentials, &socks5_request, Socks5Command::UdpAssociate)?;
+
+		// Grotesque hack which extends lifetime from 'yielder to 'static.
+		let stream: Self::S = unsafe { transmute(unencrypted_stream) };
+		Ok((stream, relay))
+	}
+}
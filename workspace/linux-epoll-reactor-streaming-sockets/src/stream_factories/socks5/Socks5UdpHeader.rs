@@ -0,0 +1,206 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// SOCKS5 UDP request header, which encapsulates every datagram sent to or received from a SOCKS5 UDP relay (RFC 1928, Section 7).
+///
+/// Fragmentation is not supported; fragmented datagrams are rejected when decapsulating.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Socks5UdpHeader
+{
+	/// When sending, the destination; when receiving, the original source.
+	pub address: BoundSocketAddress,
+}
+
+impl Socks5UdpHeader
+{
+	const SizeOfRSV: usize = 2;
+
+	const SizeOfFRAG: usize = 1;
+
+	const SizeOfDSTPORT: usize = 2;
+
+	const OffsetToAddressType: usize = Self::SizeOfRSV + Self::SizeOfFRAG;
+
+	/// Maximum size of a header.
+	pub const MaximumSize: usize = Self::OffsetToAddressType + InternetProtocolAddressOrHostName::MaximumSizeOfAddressTypeAndAddress + Self::SizeOfDSTPORT;
+
+	/// Encapsulates `payload` with this header, ready to send to the relay.
+	#[inline(always)]
+	pub fn encapsulate(&self, payload: &[u8]) -> Result<Vec<u8>, CompleteError>
+	{
+		let mut header: [u8; Self::MaximumSize] = unsafe { uninitialized() };
+
+		header[0] = 0x00;
+		header[1] = 0x00;
+		header[Self::SizeOfRSV] = 0x00;
+
+		let offset = Self::OffsetToAddressType + self.address.address.write_address_type_and_address(&mut header[Self::OffsetToAddressType .. ])?;
+		header[offset .. offset + Self::SizeOfDSTPORT].copy_from_slice(&self.address.port.to_be_bytes());
+		let header_length = offset + Self::SizeOfDSTPORT;
+
+		let mut datagram = Vec::with_capacity(header_length + payload.len());
+		datagram.extend_from_slice(&header[.. header_length]);
+		datagram.extend_from_slice(payload);
+		Ok(datagram)
+	}
+
+	/// Decapsulates a datagram received from the relay, returning the header and the payload.
+	#[inline(always)]
+	pub fn decapsulate(datagram: &[u8]) -> Result<(Self, &[u8]), CompleteError>
+	{
+		use self::Socks5ProtocolFailureError::*;
+
+		if unlikely!(datagram.len() < Self::OffsetToAddressType + 1)
+		{
+			return error(UdpHeaderTruncated)
+		}
+
+		if unlikely!(datagram[0] != 0x00 || datagram[1] != 0x00)
+		{
+			return error(UdpHeaderRsvFieldWasNotZero)
+		}
+
+		let frag = datagram[Self::SizeOfRSV];
+		if unlikely!(frag != 0x00)
+		{
+			return error(UdpFragmentationNotSupported(frag))
+		}
+
+		let atyp = datagram[Self::OffsetToAddressType];
+		let offset_to_address = Self::OffsetToAddressType + 1;
+
+		let address_length = InternetProtocolAddressOrHostName::address_length(atyp, || datagram.get(offset_to_address).cloned().map_or_else(|| error(UdpHeaderTruncated), Ok))?;
+
+		let offset_to_port = offset_to_address + address_length;
+		let offset_to_payload = offset_to_port + Self::SizeOfDSTPORT;
+		if unlikely!(datagram.len() < offset_to_payload)
+		{
+			return error(UdpHeaderTruncated)
+		}
+
+		let address = InternetProtocolAddressOrHostName::parse_address(atyp, &datagram[offset_to_address .. offset_to_port]);
+		let port = u16::from_be_bytes([datagram[offset_to_port], datagram[offset_to_port + 1]]);
+
+		let header = Self
+		{
+			address: BoundSocketAddress
+			{
+				address,
+				port,
+			}
+		};
+		Ok((header, &datagram[offset_to_payload .. ]))
+	}
+}
+
+#[cfg(test)]
+mod socks5_udp_header_tests
+{
+	use super::*;
+	use self::InternetProtocolAddressOrHostName::*;
+	use self::Socks5ProtocolFailureError::*;
+
+	fn decapsulate_failure(datagram: &[u8]) -> Socks5ProtocolFailureError
+	{
+		match Socks5UdpHeader::decapsulate(datagram)
+		{
+			Err(CompleteError::ProtocolViolation(error)) => *error.downcast_ref::<Socks5ProtocolFailureError>().expect("not a Socks5ProtocolFailureError"),
+
+			other => panic!("expected a protocol violation, not {:?}", other),
+		}
+	}
+
+	fn header(address: InternetProtocolAddressOrHostName, port: u16) -> Socks5UdpHeader
+	{
+		Socks5UdpHeader
+		{
+			address: BoundSocketAddress
+			{
+				address,
+				port,
+			}
+		}
+	}
+
+	fn host_name(host_name: &[u8]) -> InternetProtocolAddressOrHostName
+	{
+		let mut array_vec = ArrayVec::new();
+		array_vec.extend(host_name.iter().cloned());
+		HostName(array_vec)
+	}
+
+	#[test]
+	fn encapsulates_internet_protocol_version_4()
+	{
+		let datagram = header(InternetProtocolAddress(IpAddr::from([192, 0, 2, 1])), 53).encapsulate(b"payload").unwrap();
+
+		assert_eq!(&datagram[..], &b"\x00\x00\x00\x01\xC0\x00\x02\x01\x00\x35payload"[..]);
+	}
+
+	#[test]
+	fn encapsulates_host_name()
+	{
+		let datagram = header(host_name(b"example.com"), 443).encapsulate(b"").unwrap();
+
+		assert_eq!(&datagram[..], &b"\x00\x00\x00\x03\x0Bexample.com\x01\xBB"[..]);
+	}
+
+	#[test]
+	fn encapsulating_empty_host_name_fails()
+	{
+		assert!(header(host_name(b""), 443).encapsulate(b"payload").is_err());
+	}
+
+	#[test]
+	fn round_trips()
+	{
+		let headers =
+		[
+			header(InternetProtocolAddress(IpAddr::from([192, 0, 2, 1])), 53),
+			header(InternetProtocolAddress(IpAddr::from([0x2001, 0xDB8, 0, 0, 0, 0, 0, 1])), 65535),
+			header(host_name(b"example.com"), 0),
+			header(host_name(&[b'a'; 253][..]), 8080),
+		];
+
+		for header in headers.iter()
+		{
+			let datagram = header.encapsulate(b"payload").unwrap();
+			let (decapsulated_header, payload) = Socks5UdpHeader::decapsulate(&datagram[..]).unwrap();
+			assert_eq!(&decapsulated_header, header);
+			assert_eq!(payload, b"payload");
+		}
+	}
+
+	#[test]
+	fn decapsulates_empty_payload()
+	{
+		let (_, payload) = Socks5UdpHeader::decapsulate(b"\x00\x00\x00\x01\xC0\x00\x02\x01\x00\x35").unwrap();
+
+		assert!(payload.is_empty());
+	}
+
+	#[test]
+	fn decapsulating_truncated_datagram_fails()
+	{
+		let datagram = header(host_name(b"example.com"), 443).encapsulate(b"").unwrap();
+
+		for length in 0 .. datagram.len()
+		{
+			assert_eq!(decapsulate_failure(&datagram[.. length]), UdpHeaderTruncated, "length {}", length);
+		}
+
+		assert_eq!(decapsulate_failure(b"\x00\x00\x00\x04\x20\x01\x0D\xB8"), UdpHeaderTruncated);
+	}
+
+	#[test]
+	fn decapsulating_malformed_datagram_fails()
+	{
+		assert_eq!(decapsulate_failure(b"\x00\x01\x00\x01\xC0\x00\x02\x01\x00\x35"), UdpHeaderRsvFieldWasNotZero);
+		assert_eq!(decapsulate_failure(b"\x01\x00\x00\x01\xC0\x00\x02\x01\x00\x35"), UdpHeaderRsvFieldWasNotZero);
+		assert_eq!(decapsulate_failure(b"\x00\x00\x01\x01\xC0\x00\x02\x01\x00\x35"), UdpFragmentationNotSupported(0x01));
+		assert_eq!(decapsulate_failure(b"\x00\x00\x00\x02\xC0\x00\x02\x01\x00\x35"), ReplyContainedAnUnrecognisedAddressType(0x02));
+		assert_eq!(decapsulate_failure(b"\x00\x00\x00\x03\x00\x00\x35"), HostNameInReplyWasEmpty);
+		assert_eq!(decapsulate_failure(b"\x00\x00\x00\x03\xFE"), HostNameInReplyWasTooLarge(0xFE));
+	}
+}
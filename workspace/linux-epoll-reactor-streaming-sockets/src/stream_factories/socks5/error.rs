@@ -3,7 +3,7 @@
 
 
 #[inline(always)]
-fn error(error: Socks5ProtocolFailureError) -> Result<(), CompleteError>
+fn error<T>(error: Socks5ProtocolFailureError) -> Result<T, CompleteError>
 {
 	Err(CompleteError::ProtocolViolation(Box::new(error)))
 }
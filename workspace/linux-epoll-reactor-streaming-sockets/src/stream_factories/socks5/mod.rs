@@ -8,11 +8,32 @@ use super::*;
 include!("BoundSocketAddress.rs");
 include!("error.rs");
 include!("InternetProtocolAddressOrHostName.rs");
+include!("socks5_handshake.rs");
 include!("Socks5AuthenticationCredential.rs");
 include!("Socks5AuthenticationCredentialCode.rs");
 include!("Socks5AuthenticationCredentials.rs");
-include!("Socks5ConnectReply.rs");
+include!("Socks5AuthenticationMethod.rs");
+include!("Socks5BindStreamFactory.rs");
+include!("Socks5Command.rs");
 include!("Socks5CredentialReply.rs");
+include!("Socks5GssApiAuthenticationMethod.rs");
+include!("Socks5GssApiEncapsulation.rs");
+include!("Socks5GssApiMessage.rs");
+include!("Socks5GssApiProtectionLevel.rs");
+include!("Socks5GssApiSecurityContext.rs");
+include!("Socks5HandshakeStream.rs");
+include!("Socks5MessageEncapsulation.rs");
 include!("Socks5ProtocolFailureError.rs");
+include!("Socks5Reply.rs");
+include!("Socks5ReplyCode.rs");
+include!("Socks5Request.rs");
+include!("Socks5ServerAuthenticator.rs");
+include!("Socks5ServerDestinationPolicy.rs");
+include!("Socks5ServerIdentity.rs");
+include!("Socks5ServerRequest.rs");
+include!("Socks5ServerStreamFactory.rs");
 include!("Socks5StreamFactory.rs");
+include!("Socks5UdpAssociateStreamFactory.rs");
+include!("Socks5UdpHeader.rs");
 include!("Socks5UserNamePasswordReply.rs");
+include!("SocksProtocolVersion.rs");
@@ -0,0 +1,22 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Negotiates authentication, sends a request and reads the (first) reply.
+///
+/// Returns the per-message encapsulation negotiated, if any, which must be used to read a BIND request's second reply.
+#[inline(always)]
+pub(crate) fn socks5_handshake(stream: &mut dyn Socks5HandshakeStream, socks5_authentication_credentials: &Socks5AuthenticationCredentials, socks5_request: &Socks5Request, command: Socks5Command) -> Result<(BoundSocketAddress, Option<Box<dyn Socks5MessageEncapsulation>>), CompleteError>
+{
+	socks5_authentication_credentials.send_request(stream)?;
+
+	let method = Socks5CredentialReply::read_reply(stream, socks5_authentication_credentials)?;
+
+	let mut encapsulation = method.negotiate(stream)?;
+
+	socks5_request.send_request(stream, command, &mut encapsulation)?;
+
+	let bound_socket = Socks5Reply::read_reply(stream, &mut encapsulation)?;
+
+	Ok((bound_socket, encapsulation))
+}
@@ -17,7 +17,7 @@ macro_rules! streaming_socket_reactor
 		{
 			type FileDescriptor = $file_descriptor_name;
 
-			type RegistrationData = (StreamingSocketFileDescriptor<$sockaddr_type>, Rc<SF>, SF::AdditionalArguments, Rc<SU>);
+			type RegistrationData = (StreamingSocketFileDescriptor<$sockaddr_type>, Rc<SF>, SF::AdditionalArguments, Rc<SU>, Option<ConnectionPermit<$sockaddr_type>>);
 
 			#[inline(always)]
 			fn do_initial_input_and_output_and_register_with_epoll_if_necesssary<A: Arena<Self>, EPR: EventPollRegister>(event_poll_register: &EPR, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, registration_data: Self::RegistrationData) -> Result<(), EventPollRegistrationError>
@@ -0,0 +1,97 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Dispatches a `TlsServerStream` to a `StreamUser` chosen by the protocol agreed using TLS Application-Layer Protocol Negotiation (ALPN, RFC 7301), so that a single port can serve multiple protocols (eg `h2`, `http/1.1` and `dot`).
+///
+/// Protocols are byte strings, as in RFC 7301.
+///
+/// Use with a `TlsServerStreamFactory` created using `TlsServerStreamFactory::for_application_layer_protocol_negotiation()`, so that the protocols are advertised.
+pub struct ApplicationLayerProtocolNegotiationStreamUser<SD: SocketData, ProxyOrTunnelInformation=()>
+{
+	protocols_in_preference_order: Vec<Vec<u8>>,
+	stream_users: HashMap<Vec<u8>, Box<dyn StreamUser<TlsServerStream<'static, SD>, ProxyOrTunnelInformation>>>,
+	no_agreed_protocol_stream_user: Option<Box<dyn StreamUser<TlsServerStream<'static, SD>, ProxyOrTunnelInformation>>>,
+}
+
+impl<SD: SocketData, ProxyOrTunnelInformation> Debug for ApplicationLayerProtocolNegotiationStreamUser<SD, ProxyOrTunnelInformation>
+{
+	#[inline(always)]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "ApplicationLayerProtocolNegotiationStreamUser {{ protocols_in_preference_order: {:?}, stream_users: _, no_agreed_protocol_stream_user: {:?} }}", self.protocols_in_preference_order, self.no_agreed_protocol_stream_user.is_some())
+	}
+}
+
+impl<SD: SocketData, ProxyOrTunnelInformation> Default for ApplicationLayerProtocolNegotiationStreamUser<SD, ProxyOrTunnelInformation>
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			protocols_in_preference_order: Vec::new(),
+			stream_users: HashMap::default(),
+			no_agreed_protocol_stream_user: None,
+		}
+	}
+}
+
+impl<SD: SocketData, ProxyOrTunnelInformation> StreamUser<TlsServerStream<'static, SD>, ProxyOrTunnelInformation> for ApplicationLayerProtocolNegotiationStreamUser<SD, ProxyOrTunnelInformation>
+{
+	#[inline(always)]
+	fn use_stream(&self, stream: TlsServerStream<'static, SD>, proxy_or_tunnel_information: ProxyOrTunnelInformation) -> Result<(), CompleteError>
+	{
+		let stream_user = match stream.application_layer_protocol_negotiation_protocol()
+		{
+			None => self.no_agreed_protocol_stream_user.as_ref(),
+
+			Some(protocol) => self.stream_users.get(protocol),
+		};
+
+		match stream_user
+		{
+			Some(stream_user) => stream_user.use_stream(stream, proxy_or_tunnel_information),
+
+			None => Err(CompleteError::ProtocolViolation(format!("No stream user for Application-Layer Protocol Negotiation protocol {:?}", stream.application_layer_protocol_negotiation_protocol()).into())),
+		}
+	}
+}
+
+impl<SD: SocketData, ProxyOrTunnelInformation> ApplicationLayerProtocolNegotiationStreamUser<SD, ProxyOrTunnelInformation>
+{
+	/// Adds a `stream_user` for `protocol` (eg `b"h2"`); protocols added first are preferred.
+	///
+	/// Replaces any stream user previously added for `protocol`, but does not change its preference.
+	///
+	/// Panics if `protocol` is empty or longer than 255 bytes.
+	#[inline(always)]
+	pub fn add<SU: 'static + StreamUser<TlsServerStream<'static, SD>, ProxyOrTunnelInformation>>(&mut self, protocol: &[u8], stream_user: SU) -> &mut Self
+	{
+		assert_ne!(protocol.len(), 0, "protocol can not be empty");
+		assert!(protocol.len() <= 255, "protocol can not exceed 255 bytes");
+
+		if self.stream_users.insert(protocol.to_vec(), Box::new(stream_user)).is_none()
+		{
+			self.protocols_in_preference_order.push(protocol.to_vec());
+		}
+		self
+	}
+
+	/// Sets a `stream_user` to use when no protocol was agreed (eg because the client did not use ALPN).
+	///
+	/// If not set, such streams are finished with an error.
+	#[inline(always)]
+	pub fn set_no_agreed_protocol_stream_user<SU: 'static + StreamUser<TlsServerStream<'static, SD>, ProxyOrTunnelInformation>>(&mut self, stream_user: SU) -> &mut Self
+	{
+		self.no_agreed_protocol_stream_user = Some(Box::new(stream_user));
+		self
+	}
+
+	/// Protocols, in preference order.
+	#[inline(always)]
+	pub fn protocols(&self) -> &[Vec<u8>]
+	{
+		&self.protocols_in_preference_order
+	}
+}
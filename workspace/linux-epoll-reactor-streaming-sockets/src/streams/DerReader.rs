@@ -0,0 +1,376 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A minimal reader of ASN.1 DER, sufficient to extract the fields of a X.509 certificate which identify its subject.
+#[derive(Debug, Clone)]
+pub(crate) struct DerReader<'a>(&'a [u8]);
+
+impl<'a> DerReader<'a>
+{
+	pub(crate) const Boolean: u8 = 0x01;
+
+	pub(crate) const Integer: u8 = 0x02;
+
+	pub(crate) const OctetString: u8 = 0x04;
+
+	pub(crate) const ObjectIdentifier: u8 = 0x06;
+
+	pub(crate) const Utf8String: u8 = 0x0C;
+
+	pub(crate) const PrintableString: u8 = 0x13;
+
+	pub(crate) const TeletexString: u8 = 0x14;
+
+	pub(crate) const Ia5String: u8 = 0x16;
+
+	pub(crate) const UniversalString: u8 = 0x1C;
+
+	pub(crate) const BmpString: u8 = 0x1E;
+
+	pub(crate) const Sequence: u8 = 0x30;
+
+	pub(crate) const Set: u8 = 0x31;
+
+	/// Context-specific tag, eg `[0]`, which is constructed (eg explicitly tagged).
+	#[inline(always)]
+	pub(crate) const fn context_specific_constructed(number: u8) -> u8
+	{
+		0xA0 | number
+	}
+
+	/// Context-specific tag, eg `[0]`, which is primitive (eg implicitly tagged).
+	#[inline(always)]
+	pub(crate) const fn context_specific_primitive(number: u8) -> u8
+	{
+		0x80 | number
+	}
+
+	#[inline(always)]
+	pub(crate) fn new(bytes: &'a [u8]) -> Self
+	{
+		DerReader(bytes)
+	}
+
+	#[inline(always)]
+	pub(crate) fn is_empty(&self) -> bool
+	{
+		self.0.is_empty()
+	}
+
+	/// Reads any value, returning its tag, its content and its entire encoding (including tag and length).
+	#[inline(always)]
+	pub(crate) fn read_any(&mut self) -> Option<(u8, &'a [u8], &'a [u8])>
+	{
+		let bytes = self.0;
+		if bytes.len() < 2
+		{
+			return None
+		}
+
+		let tag = bytes[0];
+		let first_length_byte = bytes[1];
+		let (header_length, length) = if first_length_byte < 0x80
+		{
+			(2, first_length_byte as usize)
+		}
+		else
+		{
+			let number_of_length_bytes = (first_length_byte & 0x7F) as usize;
+			if number_of_length_bytes == 0 || number_of_length_bytes > 4 || bytes.len() < 2 + number_of_length_bytes
+			{
+				return None
+			}
+			(2 + number_of_length_bytes, bytes[2 .. 2 + number_of_length_bytes].iter().fold(0, |length, &byte| (length << 8) | (byte as usize)))
+		};
+
+		let end = header_length.checked_add(length)?;
+		if bytes.len() < end
+		{
+			return None
+		}
+
+		self.0 = &bytes[end .. ];
+		Some((tag, &bytes[header_length .. end], &bytes[ .. end]))
+	}
+
+	/// Reads a value which must have the tag `tag`, returning its content.
+	#[inline(always)]
+	pub(crate) fn read(&mut self, tag: u8) -> Option<&'a [u8]>
+	{
+		match self.read_any()?
+		{
+			(actual_tag, content, _) if actual_tag == tag => Some(content),
+
+			_ => None,
+		}
+	}
+
+	/// Reads a value if it has the tag `tag`, returning its content; returns `None` if the data is invalid.
+	#[inline(always)]
+	pub(crate) fn read_optional(&mut self, tag: u8) -> Option<Option<&'a [u8]>>
+	{
+		match self.0.first()
+		{
+			Some(&actual_tag) if actual_tag == tag => self.read(tag).map(Some),
+
+			_ => Some(None),
+		}
+	}
+
+	/// Finds the DER-encoded `SubjectPublicKeyInfo` (including its tag and length) of a DER-encoded X.509 certificate.
+	#[inline(always)]
+	pub(crate) fn certificate_subject_public_key_info(certificate: &'a [u8]) -> Option<&'a [u8]>
+	{
+		Self::certificate_subject_and_subject_public_key_info(certificate).map(|(_, subject_public_key_info, _)| subject_public_key_info)
+	}
+
+	/// Reads a DER-encoded X.509 certificate's `TBSCertificate` up to and including its `SubjectPublicKeyInfo`.
+	///
+	/// Returns the content of the subject, the DER-encoded `SubjectPublicKeyInfo` (including its tag and length) and a reader of the remainder of the `TBSCertificate`.
+	pub(crate) fn certificate_subject_and_subject_public_key_info(certificate: &'a [u8]) -> Option<(&'a [u8], &'a [u8], Self)>
+	{
+		let mut certificate = Self::new(Self::new(certificate).read(Self::Sequence)?);
+		let mut to_be_signed_certificate = Self::new(certificate.read(Self::Sequence)?);
+
+		// Version.
+		to_be_signed_certificate.read_optional(Self::context_specific_constructed(0))?;
+
+		// Serial number, signature algorithm, issuer and validity.
+		to_be_signed_certificate.read(Self::Integer)?;
+		to_be_signed_certificate.read(Self::Sequence)?;
+		to_be_signed_certificate.read(Self::Sequence)?;
+		to_be_signed_certificate.read(Self::Sequence)?;
+
+		let subject = to_be_signed_certificate.read(Self::Sequence)?;
+
+		match to_be_signed_certificate.read_any()?
+		{
+			(Self::Sequence, _, subject_public_key_info) => Some((subject, subject_public_key_info, to_be_signed_certificate)),
+
+			_ => None,
+		}
+	}
+
+	/// Decodes an object identifier's content to dotted-decimal, eg `2.5.4.3`.
+	pub(crate) fn object_identifier_to_string(content: &[u8]) -> Option<String>
+	{
+		let mut components = Vec::new();
+		let mut component: u64 = 0;
+		for &byte in content
+		{
+			if component > (::std::u64::MAX >> 7)
+			{
+				return None
+			}
+			component = (component << 7) | ((byte & 0x7F) as u64);
+			if byte & 0x80 == 0
+			{
+				if components.is_empty()
+				{
+					let first = min(component / 40, 2);
+					components.push(first);
+					components.push(component - first * 40);
+				}
+				else
+				{
+					components.push(component);
+				}
+				component = 0;
+			}
+		}
+
+		if components.is_empty() || content.last().unwrap() & 0x80 != 0
+		{
+			return None
+		}
+
+		Some(components.iter().map(u64::to_string).collect::<Vec<_>>().join("."))
+	}
+
+	/// Decodes the content of a string type to a `String`; returns `None` if `tag` is not a string type or the content is invalid.
+	pub(crate) fn string_to_string(tag: u8, content: &[u8]) -> Option<String>
+	{
+		match tag
+		{
+			Self::Utf8String | Self::PrintableString | Self::Ia5String => from_utf8(content).ok().map(str::to_string),
+
+			// Treated as ISO-8859-1, as is common practice.
+			Self::TeletexString => Some(content.iter().map(|&byte| byte as char).collect()),
+
+			Self::BmpString if content.len() % 2 == 0 => String::from_utf16(&content.chunks(2).map(|pair| ((pair[0] as u16) << 8) | (pair[1] as u16)).collect::<Vec<_>>()).ok(),
+
+			Self::UniversalString if content.len() % 4 == 0 => content.chunks(4).map(|quad| ::std::char::from_u32(((quad[0] as u32) << 24) | ((quad[1] as u32) << 16) | ((quad[2] as u32) << 8) | (quad[3] as u32))).collect(),
+
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod der_reader_tests
+{
+	use super::*;
+
+	pub(crate) fn der(tag: u8, content: &[u8]) -> Vec<u8>
+	{
+		let mut encoding = vec![tag];
+		match content.len()
+		{
+			length @ 0 ..= 0x7F => encoding.push(length as u8),
+
+			length @ 0x80 ..= 0xFF => encoding.extend_from_slice(&[0x81, length as u8]),
+
+			length => encoding.extend_from_slice(&[0x82, (length >> 8) as u8, length as u8]),
+		}
+		encoding.extend_from_slice(content);
+		encoding
+	}
+
+	pub(crate) fn subject_public_key_info() -> Vec<u8>
+	{
+		let algorithm = der(DerReader::Sequence, &der(DerReader::ObjectIdentifier, &[0x2B, 0x65, 0x70]));
+		der(DerReader::Sequence, &[algorithm, der(0x03, &[0x00; 33])].concat())
+	}
+
+	/// A X.509 certificate with the subject `subject` (the content of a `Name`) and, optionally, an extension (object identifier and value).
+	pub(crate) fn certificate(subject: &[u8], extension: Option<(&[u8], &[u8])>) -> Vec<u8>
+	{
+		let signature_algorithm = der(DerReader::Sequence, &der(DerReader::ObjectIdentifier, &[0x2B, 0x65, 0x70]));
+
+		let mut to_be_signed_certificate = Vec::new();
+		to_be_signed_certificate.extend_from_slice(&der(DerReader::context_specific_constructed(0), &der(DerReader::Integer, &[0x02])));
+		to_be_signed_certificate.extend_from_slice(&der(DerReader::Integer, &[0x01]));
+		to_be_signed_certificate.extend_from_slice(&signature_algorithm);
+		to_be_signed_certificate.extend_from_slice(&der(DerReader::Sequence, &[]));
+		to_be_signed_certificate.extend_from_slice(&der(DerReader::Sequence, &[]));
+		to_be_signed_certificate.extend_from_slice(&der(DerReader::Sequence, subject));
+		to_be_signed_certificate.extend_from_slice(&subject_public_key_info());
+		if let Some((extension_object_identifier, extension_value)) = extension
+		{
+			let extension = der(DerReader::Sequence, &[der(DerReader::ObjectIdentifier, extension_object_identifier), der(DerReader::Boolean, &[0x00]), der(DerReader::OctetString, extension_value)].concat());
+			to_be_signed_certificate.extend_from_slice(&der(DerReader::context_specific_constructed(3), &der(DerReader::Sequence, &extension)));
+		}
+
+		der(DerReader::Sequence, &[der(DerReader::Sequence, &to_be_signed_certificate), signature_algorithm, der(0x03, &[0x00; 65])].concat())
+	}
+
+	#[test]
+	fn read_any_short_and_long_lengths()
+	{
+		let short = der(DerReader::OctetString, &[0xAA; 0x7F]);
+		let long = der(DerReader::OctetString, &[0xBB; 0x100]);
+		let encodings = [short.clone(), long.clone()].concat();
+
+		let mut reader = DerReader::new(&encodings);
+		assert_eq!(reader.read_any(), Some((DerReader::OctetString, &short[2 .. ], &short[..])));
+		assert_eq!(reader.read_any(), Some((DerReader::OctetString, &long[4 .. ], &long[..])));
+		assert!(reader.is_empty());
+		assert_eq!(reader.read_any(), None);
+	}
+
+	#[test]
+	fn truncated_is_none()
+	{
+		for content_length in [0, 1, 0x7F, 0x80, 0x100].iter()
+		{
+			let encoding = der(DerReader::OctetString, &vec![0xAA; *content_length]);
+			for length in 0 .. encoding.len()
+			{
+				assert_eq!(DerReader::new(&encoding[.. length]).read_any(), None, "content length {} length {}", content_length, length);
+			}
+		}
+	}
+
+	#[test]
+	fn malformed_length_is_none()
+	{
+		assert_eq!(DerReader::new(&[DerReader::OctetString, 0x80, 0x00]).read_any(), None);
+		assert_eq!(DerReader::new(&[DerReader::OctetString, 0x85, 0x00, 0x00, 0x00, 0x00, 0x01, 0xAA]).read_any(), None);
+		assert_eq!(DerReader::new(&[DerReader::OctetString, 0x84, 0xFF, 0xFF, 0xFF, 0xFF, 0xAA]).read_any(), None);
+	}
+
+	#[test]
+	fn read_requires_tag()
+	{
+		let encoding = der(DerReader::Integer, &[0x01]);
+
+		assert_eq!(DerReader::new(&encoding).read(DerReader::Sequence), None);
+		assert_eq!(DerReader::new(&encoding).read(DerReader::Integer), Some(&[0x01][..]));
+	}
+
+	#[test]
+	fn read_optional()
+	{
+		let encoding = der(DerReader::Integer, &[0x01]);
+
+		let mut reader = DerReader::new(&encoding);
+		assert_eq!(reader.read_optional(DerReader::Boolean), Some(None));
+		assert_eq!(reader.read_optional(DerReader::Integer), Some(Some(&[0x01][..])));
+		assert_eq!(reader.read_optional(DerReader::Integer), Some(None));
+
+		assert_eq!(DerReader::new(&encoding[.. 2]).read_optional(DerReader::Integer), None);
+	}
+
+	#[test]
+	fn object_identifier_to_string()
+	{
+		assert_eq!(DerReader::object_identifier_to_string(&[0x55, 0x04, 0x03]), Some("2.5.4.3".to_string()));
+		assert_eq!(DerReader::object_identifier_to_string(&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x01]), Some("1.2.840.113549.1.9.1".to_string()));
+		assert_eq!(DerReader::object_identifier_to_string(&[0x88, 0x37]), Some("2.999".to_string()));
+	}
+
+	#[test]
+	fn malformed_object_identifier_is_none()
+	{
+		assert_eq!(DerReader::object_identifier_to_string(&[]), None);
+		assert_eq!(DerReader::object_identifier_to_string(&[0x55, 0x84]), None);
+		assert_eq!(DerReader::object_identifier_to_string(&[0x55, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F]), None);
+	}
+
+	#[test]
+	fn string_to_string()
+	{
+		assert_eq!(DerReader::string_to_string(DerReader::Utf8String, "café".as_bytes()), Some("café".to_string()));
+		assert_eq!(DerReader::string_to_string(DerReader::PrintableString, b"Org"), Some("Org".to_string()));
+		assert_eq!(DerReader::string_to_string(DerReader::TeletexString, &[0x63, 0x61, 0x66, 0xE9]), Some("café".to_string()));
+		assert_eq!(DerReader::string_to_string(DerReader::BmpString, &[0x00, 0x63, 0x00, 0xE9]), Some("cé".to_string()));
+		assert_eq!(DerReader::string_to_string(DerReader::UniversalString, &[0x00, 0x00, 0x00, 0x63, 0x00, 0x00, 0x00, 0xE9]), Some("cé".to_string()));
+	}
+
+	#[test]
+	fn malformed_string_is_none()
+	{
+		assert_eq!(DerReader::string_to_string(DerReader::Utf8String, &[0xFF]), None);
+		assert_eq!(DerReader::string_to_string(DerReader::BmpString, &[0x00, 0x63, 0x00]), None);
+		assert_eq!(DerReader::string_to_string(DerReader::BmpString, &[0xD8, 0x00]), None);
+		assert_eq!(DerReader::string_to_string(DerReader::UniversalString, &[0x00, 0x00, 0x63]), None);
+		assert_eq!(DerReader::string_to_string(DerReader::UniversalString, &[0x00, 0x11, 0x00, 0x00]), None);
+		assert_eq!(DerReader::string_to_string(DerReader::OctetString, b"abc"), None);
+	}
+
+	#[test]
+	fn certificate_subject_public_key_info()
+	{
+		let certificate = certificate(&[], None);
+
+		assert_eq!(DerReader::certificate_subject_public_key_info(&certificate), Some(&subject_public_key_info()[..]));
+	}
+
+	#[test]
+	fn truncated_certificate_has_no_subject_public_key_info()
+	{
+		let certificate = certificate(&[], None);
+		for length in 0 .. certificate.len()
+		{
+			assert_eq!(DerReader::certificate_subject_public_key_info(&certificate[.. length]), None, "length {}", length);
+		}
+	}
+
+	#[test]
+	fn not_a_certificate_has_no_subject_public_key_info()
+	{
+		assert_eq!(DerReader::certificate_subject_public_key_info(&der(DerReader::Sequence, &der(DerReader::Sequence, &der(DerReader::Integer, &[0x01])))), None);
+		assert_eq!(DerReader::certificate_subject_public_key_info(&der(DerReader::OctetString, &[])), None);
+	}
+}
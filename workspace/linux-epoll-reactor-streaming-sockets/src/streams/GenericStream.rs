@@ -18,6 +18,12 @@ impl<'yielder, SD: SocketData> GenericStream<'yielder, SD>
 		tls_session.complete_handshaking::<SD>(&self.streaming_socket_file_descriptor, &mut self.input_output_yielder, &mut self.byte_counter)
 	}
 
+	#[inline(always)]
+	fn tls_handshake_for_kernel_offload(&mut self, tls_session: &mut impl SessionExt) -> Result<Option<TlsKernelOffload>, CompleteError>
+	{
+		tls_session.complete_handshaking_for_kernel_offload::<SD>(&self.streaming_socket_file_descriptor, &mut self.input_output_yielder, &mut self.byte_counter)
+	}
+
 	#[inline(always)]
 	fn tls_read(&mut self, tls_session: &mut impl SessionExt, read_into_buffer: &mut [u8]) -> Result<usize, CompleteError>
 	{
@@ -30,6 +36,18 @@ impl<'yielder, SD: SocketData> GenericStream<'yielder, SD>
 		tls_session.stream_write::<SD>(&self.streaming_socket_file_descriptor, &mut self.input_output_yielder, &mut self.byte_counter, write_from_buffer)
 	}
 
+	#[inline(always)]
+	fn tls_read_vectored(&mut self, tls_session: &mut impl SessionExt, read_into_buffers: &mut [IoSliceMut]) -> Result<usize, CompleteError>
+	{
+		tls_session.stream_read_vectored::<SD>(&self.streaming_socket_file_descriptor, &mut self.input_output_yielder, &mut self.byte_counter, read_into_buffers)
+	}
+
+	#[inline(always)]
+	fn tls_write_vectored(&mut self, tls_session: &mut impl SessionExt, write_from_buffers: &[IoSlice]) -> Result<usize, CompleteError>
+	{
+		tls_session.stream_write_vectored::<SD>(&self.streaming_socket_file_descriptor, &mut self.input_output_yielder, &mut self.byte_counter, write_from_buffers)
+	}
+
 	#[inline(always)]
 	fn tls_flush_written_data(&mut self, tls_session: &mut impl SessionExt) -> Result<(), CompleteError>
 	{
@@ -41,6 +59,67 @@ impl<'yielder, SD: SocketData> GenericStream<'yielder, SD>
 	{
 		tls_session.stream_close::<SD>(&self.streaming_socket_file_descriptor, &mut self.input_output_yielder, &mut self.byte_counter)
 	}
+
+	#[inline(always)]
+	fn kernel_tls_read_vectored(&mut self, kernel_offload: &mut TlsKernelOffload, read_into_buffers: &mut [IoSliceMut]) -> Result<usize, CompleteError>
+	{
+		loop
+		{
+			match kernel_offload.receive(self.streaming_socket_file_descriptor.as_raw_fd(), &mut self.byte_counter, read_into_buffers)?
+			{
+				None => await_further_input_or_output_to_become_available!(&mut self.input_output_yielder),
+
+				Some(bytes_read) => return Ok(bytes_read),
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn kernel_tls_peek_more_data(&mut self, kernel_offload: &mut TlsKernelOffload, peek_into_buffer: &mut [u8], already_peeked: usize) -> Result<usize, CompleteError>
+	{
+		loop
+		{
+			if let Some(total_bytes_peeked) = kernel_offload.peek(self.streaming_socket_file_descriptor.as_raw_fd(), &mut self.byte_counter, peek_into_buffer)?
+			{
+				if total_bytes_peeked == 0 || total_bytes_peeked > already_peeked || total_bytes_peeked == peek_into_buffer.len()
+				{
+					return Ok(total_bytes_peeked)
+				}
+			}
+
+			await_further_input_or_output_to_become_available!(&mut self.input_output_yielder)
+		}
+	}
+
+	#[inline(always)]
+	fn kernel_tls_write_vectored(&mut self, write_from_buffers: &[IoSlice]) -> Result<usize, CompleteError>
+	{
+		loop
+		{
+			match TlsKernelOffload::send(self.streaming_socket_file_descriptor.as_raw_fd(), &mut self.byte_counter, write_from_buffers)?
+			{
+				None => await_further_input_or_output_to_become_available!(&mut self.input_output_yielder),
+
+				Some(bytes_written) => return Ok(bytes_written),
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn kernel_tls_finish(&mut self, kernel_offload: &mut TlsKernelOffload) -> Result<(), CompleteError>
+	{
+		kernel_offload.queue_close_notify_alert();
+
+		loop
+		{
+			if kernel_offload.send_queued_close_notify_alert(self.streaming_socket_file_descriptor.as_raw_fd(), &mut self.byte_counter)?
+			{
+				return Ok(())
+			}
+
+			await_further_input_or_output_to_become_available!(&mut self.input_output_yielder)
+		}
+	}
 }
 
 impl<'yielder, SD: SocketData> GenericStream<'yielder, SD>
@@ -54,7 +133,91 @@ impl<'yielder, SD: SocketData> GenericStream<'yielder, SD>
 	#[inline(always)]
 	pub(crate) fn unwrap(self) -> (StreamingSocketFileDescriptor<SD>, Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>)
 	{
-		(self.streaming_socket_file_descriptor, self.input_output_yielder)
+		(self.streaming_socket_file_descriptor, self.input_output_yielder.0)
+	}
+
+	#[inline(always)]
+	pub(crate) fn stream_relay_endpoint<'a>(&'a mut self, transport: Option<&'a mut (dyn StreamRelayTransport + 'static)>) -> StreamRelayEndpoint<'a, 'yielder> where 'yielder: 'a
+	{
+		StreamRelayEndpoint::new(self.streaming_socket_file_descriptor.as_raw_fd(), transport, &mut self.byte_counter, &mut self.input_output_yielder)
+	}
+
+	/// Peeks at received data without consuming it, waiting until more than `already_peeked` bytes have been received (or `peek_into_buffer` is full).
+	///
+	/// Returns the total number of bytes peeked, which can be zero if the remote peer has closed its side of the connection.
+	#[inline(always)]
+	pub(crate) fn peek_more_data(&mut self, peek_into_buffer: &mut [u8], already_peeked: usize) -> Result<usize, CompleteError>
+	{
+		use ::linux_epoll::libc::MSG_PEEK;
+		use ::linux_epoll::libc::recv;
+
+		loop
+		{
+			let result = unsafe { recv(self.streaming_socket_file_descriptor.as_raw_fd(), peek_into_buffer.as_mut_ptr() as *mut c_void, peek_into_buffer.len(), MSG_PEEK) };
+
+			let total_bytes_peeked = if likely!(result >= 0)
+			{
+				result as usize
+			}
+			else
+			{
+				let io_error = io::Error::last_os_error();
+				read_loop_or_await_or_error!(io_error, &mut self.input_output_yielder, SocketRead)
+			};
+
+			if total_bytes_peeked == 0 || total_bytes_peeked > already_peeked || total_bytes_peeked == peek_into_buffer.len()
+			{
+				return Ok(total_bytes_peeked)
+			}
+
+			await_further_input_or_output_to_become_available!(&mut self.input_output_yielder)
+		}
+	}
+
+	/// Sends up to `length` bytes of `file`, starting at `offset`, using `sendfile(2)`, so that the bytes are not copied into userspace.
+	///
+	/// Waits (yields) until all `length` bytes have been sent or the end of `file` has been reached; returns the number of bytes sent.
+	#[inline(always)]
+	pub(crate) fn send_file(&mut self, file: &impl AsRawFd, offset: u64, length: usize) -> Result<usize, CompleteError>
+	{
+		use ::linux_epoll::libc::sendfile;
+
+		let socket_file_descriptor = self.streaming_socket_file_descriptor.as_raw_fd();
+		let file_descriptor = file.as_raw_fd();
+
+		let mut offset = Self::file_offset(offset)?;
+		let mut total_bytes_written = 0;
+		while total_bytes_written < length
+		{
+			let result = unsafe { sendfile(socket_file_descriptor, file_descriptor, &mut offset, length - total_bytes_written) };
+
+			let bytes_written = if likely!(result >= 0)
+			{
+				result as usize
+			}
+			else
+			{
+				let io_error = io::Error::last_os_error();
+				write_loop_or_await_or_error!(io_error, &mut self.input_output_yielder, SocketWrite)
+			};
+
+			if unlikely!(bytes_written == 0)
+			{
+				break
+			}
+
+			self.byte_counter.bytes_written(bytes_written);
+			total_bytes_written += bytes_written;
+		}
+
+		Ok(total_bytes_written)
+	}
+
+	/// Otherwise an `offset` larger than `off_t` can represent would wrap and become negative.
+	#[inline(always)]
+	pub(crate) fn file_offset(offset: u64) -> Result<off_t, CompleteError>
+	{
+		off_t::try_from(offset).map_err(|_| CompleteError::InvalidDataSupplied(format!("A file offset can not exceed {} bytes, not {}", off_t::max_value(), offset)))
 	}
 
 	#[inline(always)]
@@ -4,6 +4,9 @@
 
 trait SessionExt: Session
 {
+	/// Is this a client session?
+	const IsClient: bool;
+
 	/// Logic required to complete handshaking before progressing with a connection.
 	fn complete_handshaking<SD: SocketData>(&mut self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, yielder: &mut InputOutputYielder, byte_counter: &mut ByteCounter) -> Result<(), CompleteError>
 	{
@@ -20,6 +23,58 @@ trait SessionExt: Session
 		Ok(())
 	}
 
+	/// Logic required to complete handshaking and then offload the encryption and decryption of records to the kernel (kTLS).
+	///
+	/// The session's configuration must use a `TlsKernelOffloadKeyLog`, and no plaintext must have been written before handshaking.
+	/// Returns `None` if offloading is not possible, eg because TLS 1.3 was not negotiated or the kernel does not support kTLS, in which case this session continues to be used.
+	fn complete_handshaking_for_kernel_offload<SD: SocketData>(&mut self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, yielder: &mut InputOutputYielder, byte_counter: &mut ByteCounter) -> Result<Option<TlsKernelOffload>, CompleteError>
+	{
+		let mut traffic_secrets = TlsTrafficSecrets::default();
+		let mut bytes_remaining_in_record = 0;
+		while self.is_handshaking()
+		{
+			let is_end_of_file = self.complete_record_input_output(streaming_socket_file_descriptor, yielder, byte_counter, &mut traffic_secrets, &mut bytes_remaining_in_record)?;
+
+			if unlikely!(is_end_of_file)
+			{
+				return Err(CompleteError::from(TlsInputOutputError::EndOfFileWhilstHandshaking))
+			}
+		}
+
+		// Any part of a record or any plaintext received after handshaking could only be decrypted by this session.
+		if bytes_remaining_in_record != 0 || !self.wants_read() || self.get_protocol_version() != Some(ProtocolVersion::TLSv1_3)
+		{
+			return Ok(None)
+		}
+
+		let (cipher_suite, (transmit_traffic_secret, receive_traffic_secret)) = match (self.get_negotiated_ciphersuite(), traffic_secrets.transmit_and_receive(Self::IsClient))
+		{
+			(Some(cipher_suite), Some(traffic_secrets)) => (cipher_suite, traffic_secrets),
+
+			_ => return Ok(None),
+		};
+
+		let mut records = Vec::new();
+		while self.wants_write()
+		{
+			self.write_tls(&mut records).map_err(CompleteError::Undifferentiated)?;
+		}
+
+		let transmit_record_sequence_number = if Self::IsClient
+		{
+			// A client's `Finished` (and any `Certificate` and `CertificateVerify`) are encrypted using the handshake traffic secret.
+			0
+		}
+		else
+		{
+			// A server's new session tickets are encrypted using the transmit traffic secret.
+			TlsKernelOffload::number_of_records(&records[..])
+		};
+		self.write_all_underlying::<SD>(streaming_socket_file_descriptor, yielder, byte_counter, &records[..])?;
+
+		TlsKernelOffload::install(streaming_socket_file_descriptor.as_raw_fd(), cipher_suite, transmit_traffic_secret, receive_traffic_secret, transmit_record_sequence_number)
+	}
+
 	/// Logic required for an implementation of `io::Read.read()`.
 	///
 	/// Can legitimately return 0 bytes and ***NOT*** be end-of-file.
@@ -60,6 +115,73 @@ trait SessionExt: Session
 		Ok(len)
 	}
 
+	/// Logic required for an implementation of `io::Read.read_vectored()`.
+	///
+	/// Only waits for data to fill the first non-empty buffer; subsequent buffers are filled with data which has already been decrypted.
+	fn stream_read_vectored<SD: SocketData>(&mut self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, yielder: &mut InputOutputYielder, byte_counter: &mut ByteCounter, bufs: &mut [IoSliceMut]) -> Result<usize, CompleteError>
+	{
+		let mut bufs = bufs.iter_mut().filter(|buf| !buf.is_empty());
+
+		let first_buf = match bufs.next()
+		{
+			None => return Ok(0),
+
+			Some(first_buf) => first_buf,
+		};
+
+		let mut total_bytes_read = self.stream_read::<SD>(streaming_socket_file_descriptor, yielder, byte_counter, first_buf)?;
+		if total_bytes_read < first_buf.len()
+		{
+			return Ok(total_bytes_read)
+		}
+
+		for buf in bufs
+		{
+			match self.read(buf)
+			{
+				// A close notify alert received is reported by the next read.
+				Err(_) => break,
+
+				Ok(bytes_read) =>
+				{
+					total_bytes_read += bytes_read;
+					if bytes_read < buf.len()
+					{
+						break
+					}
+				}
+			}
+		}
+
+		Ok(total_bytes_read)
+	}
+
+	/// Logic required for an implementation of `io::Write.write_vectored()`.
+	///
+	/// Each buffer is written to the session before the resultant TLS records are sent, so that, eg, a small header and its body are sent together.
+	fn stream_write_vectored<SD: SocketData>(&mut self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, yielder: &mut InputOutputYielder, byte_counter: &mut ByteCounter, bufs: &[IoSlice]) -> Result<usize, CompleteError>
+	{
+		self.complete_prior_input_output::<SD>(streaming_socket_file_descriptor, yielder, byte_counter)?;
+
+		let mut total_len = 0;
+		for buf in bufs.iter().filter(|buf| !buf.is_empty())
+		{
+			let len = self.write(buf).expect("Internal implementation in self (ServerSession) imp (ServerSessionImpl) common (SessionCommon) send_some_plaintext() does not return errors");
+			total_len += len;
+
+			// The session buffer limit has been reached.
+			if len < buf.len()
+			{
+				break
+			}
+		}
+
+		// As for `stream_write()`, errors are reported on the next call.
+		let _ = self.process_input_output_after_handshaking::<SD>(streaming_socket_file_descriptor, yielder, byte_counter);
+
+		Ok(total_len)
+	}
+
 	/// Logic required to close a TLS stream by sending a close notify fatal alert.
 	#[inline(always)]
 	fn stream_close<SD: SocketData>(&mut self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, yielder: &mut InputOutputYielder, byte_counter: &mut ByteCounter) -> Result<(), CompleteError>
@@ -187,6 +309,146 @@ trait SessionExt: Session
 		}
 	}
 
+	#[doc(hidden)]
+	#[inline(always)]
+	fn complete_record_input_output<SD: SocketData>(&mut self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, yielder: &mut InputOutputYielder, byte_counter: &mut ByteCounter, traffic_secrets: &mut TlsTrafficSecrets, bytes_remaining_in_record: &mut usize) -> Result<bool, CompleteError>
+	{
+		use self::TlsInputOutputError::*;
+
+		while self.wants_write()
+		{
+			loop
+			{
+				match self.write_underlying_vectored(streaming_socket_file_descriptor)
+				{
+					Err(io_error) => write_loop_or_await_or_error!(io_error, yielder, SocketVectoredWrite),
+
+					#[cfg(debug_assertions)] Ok(0) => panic!("Writes should always write more than one byte"),
+
+					Ok(bytes_written) =>
+					{
+						byte_counter.bytes_written(bytes_written);
+						break
+					}
+				}
+			}
+		}
+
+		// During handshaking, `self.wants_read()` is always true.
+		let bytes_read = loop
+		{
+			let bytes_read = match self.read_underlying_record(streaming_socket_file_descriptor, bytes_remaining_in_record)
+			{
+				Err(io_error) => read_loop_or_await_or_error!(io_error, yielder, SocketRead),
+
+				Ok(bytes_read) => bytes_read,
+			};
+
+			if let Err(tls_error) = TlsKernelOffloadKeyLog::capture(traffic_secrets, || self.process_new_packets())
+			{
+				// In case we have a TLS alert message to send describing this error we do a final write.
+				loop
+				{
+					match self.write_underlying_vectored(streaming_socket_file_descriptor)
+					{
+						Err(io_error) => write_loop_or_await_or_error!(io_error, yielder, SocketVectoredWrite),
+
+						#[cfg(debug_assertions)] Ok(0) => panic!("Writes should always write more than one byte"),
+
+						Ok(bytes_written) =>
+						{
+							byte_counter.bytes_written(bytes_written);
+							break
+						}
+					}
+				}
+
+				return Err(CompleteError::from(ProcessNewPackets(tls_error)));
+			}
+
+			break bytes_read;
+		};
+		byte_counter.bytes_read(bytes_read);
+		let is_end_of_file = bytes_read == 0;
+		Ok(is_end_of_file)
+	}
+
+	#[doc(hidden)]
+	#[inline(always)]
+	fn write_all_underlying<SD: SocketData>(&mut self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, yielder: &mut InputOutputYielder, byte_counter: &mut ByteCounter, write_from_buffer: &[u8]) -> Result<(), CompleteError>
+	{
+		let mut remaining_bytes = write_from_buffer;
+		while !remaining_bytes.is_empty()
+		{
+			let bytes_written = match streaming_socket_file_descriptor.send_to(remaining_bytes)
+			{
+				Err(io_error) => write_loop_or_await_or_error!(io_error, yielder, SocketWrite),
+
+				Ok(bytes_written) => bytes_written,
+			};
+
+			byte_counter.bytes_written(bytes_written);
+			remaining_bytes = &remaining_bytes[bytes_written .. ];
+		}
+
+		Ok(())
+	}
+
+	/// Reads no further than the end of the current TLS record, so that records received after handshaking are left in the socket for the kernel to decrypt.
+	#[doc(hidden)]
+	#[inline(always)]
+	fn read_underlying_record<SD: SocketData>(&mut self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>, bytes_remaining_in_record: &mut usize) -> io::Result<usize>
+	{
+		struct RecordReadAdaptor<'a, SD: 'a + SocketData>(&'a StreamingSocketFileDescriptor<SD>, &'a mut usize);
+
+		impl<'a, SD: 'a + SocketData> Read for RecordReadAdaptor<'a, SD>
+		{
+			#[inline(always)]
+			fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+			{
+				use ::linux_epoll::libc::MSG_PEEK;
+				use ::linux_epoll::libc::recv;
+
+				if *self.1 == 0
+				{
+					let mut record_header = [0u8; TlsKernelOffload::RecordHeaderLength];
+					let result = unsafe { recv(self.0.as_raw_fd(), record_header.as_mut_ptr() as *mut c_void, record_header.len(), MSG_PEEK) };
+					if unlikely!(result < 0)
+					{
+						return Err(io::Error::last_os_error())
+					}
+
+					let bytes_peeked = result as usize;
+					if unlikely!(bytes_peeked == 0)
+					{
+						return Ok(0)
+					}
+
+					// Receiving the rest of the record header will cause another edge-triggered event.
+					if unlikely!(bytes_peeked < record_header.len())
+					{
+						return Err(io::Error::from(ErrorKind::WouldBlock))
+					}
+
+					*self.1 = record_header.len() + u16::from_be_bytes([record_header[3], record_header[4]]) as usize;
+				}
+
+				let length = min(buf.len(), *self.1);
+				let bytes_read = self.0.receive_from(&mut buf[.. length])?;
+				*self.1 -= bytes_read;
+				Ok(bytes_read)
+			}
+
+			#[inline(always)]
+			unsafe fn initializer(&self) -> Initializer
+			{
+				Initializer::nop()
+			}
+		}
+
+		self.read_tls(&mut RecordReadAdaptor(streaming_socket_file_descriptor, bytes_remaining_in_record))
+	}
+
 	#[doc(hidden)]
 	#[inline(always)]
 	fn read_underlying<SD: SocketData>(&mut self, streaming_socket_file_descriptor: &StreamingSocketFileDescriptor<SD>) -> io::Result<usize>
@@ -233,8 +495,10 @@ trait SessionExt: Session
 
 impl SessionExt for ClientSession
 {
+	const IsClient: bool = true;
 }
 
 impl SessionExt for ServerSession
 {
+	const IsClient: bool = false;
 }
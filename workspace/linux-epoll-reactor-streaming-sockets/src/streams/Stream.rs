@@ -16,7 +16,7 @@ pub trait Stream: Read + Write
 	///
 	/// Since a stream is only made available after handshaking has been successful, this method always succeeds.
 	///
-	/// For a unencrypted stream, nothing useful is available as no handshaking occurs, although if support for `STARTTLS` in LDAP, SMTP, POP3 and IMAP is added it may be slightly useful.
+	/// For a unencrypted stream, nothing useful is available as no handshaking occurs; once upgraded to TLS in place (eg for `STARTTLS` in LDAP, SMTP, POP3 and IMAP; see `TlsUpgradable`), use the information of the upgraded stream.
 	#[inline(always)]
 	fn post_handshake_information(&self) -> Self::PostHandshakeInformation;
 
@@ -30,6 +30,16 @@ pub trait Stream: Read + Write
 	/// Appears to the user of this API to be blocking, but in practice it uses a coroutine.
 	fn write_data(&mut self, write_from_buffer: &[u8]) -> Result<usize, CompleteError>;
 
+	/// Read data into several buffers, filling each in turn.
+	///
+	/// Appears to the user of this API to be blocking, but in practice it uses a coroutine.
+	fn read_vectored_data(&mut self, read_into_buffers: &mut [IoSliceMut]) -> Result<usize, CompleteError>;
+
+	/// Write data from several buffers, in turn, eg a protocol frame's header and body without copying them together.
+	///
+	/// Appears to the user of this API to be blocking, but in practice it uses a coroutine.
+	fn write_vectored_data(&mut self, write_from_buffers: &[IoSlice]) -> Result<usize, CompleteError>;
+
 	/// Flush written data.
 	///
 	/// Not particularly useful, and there is no need to use this before calling `read_data()`, `write_data()` or `finish()`, all of which are self-flushing.
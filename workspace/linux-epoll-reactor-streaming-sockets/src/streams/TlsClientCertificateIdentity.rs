@@ -0,0 +1,261 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// The identity of a TLS client, from the verified client certificate chain it presented (mutual TLS).
+///
+/// Use this in `StreamUser::use_stream()` to authorise by identity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsClientCertificateIdentity
+{
+	/// The verified client certificate chain, leaf (client) certificate first.
+	pub certificate_chain: Vec<Certificate>,
+
+	/// Attributes of the leaf certificate's subject distinguished name, in order, as (attribute type, value) pairs.
+	///
+	/// Well-known attribute types are given by their short name (eg `CN`, `O`, `OU`, `C`, `DC` or `emailAddress`); others in dotted-decimal (eg `2.5.4.97`).
+	/// Attributes whose values are not strings are omitted.
+	pub subject: Vec<(String, String)>,
+
+	/// Subject alternative names of the leaf certificate.
+	pub subject_alternative_names: Vec<TlsSubjectAlternativeName>,
+
+	/// SHA-256 fingerprint of the leaf certificate's DER-encoded `SubjectPublicKeyInfo` (as used for public key pinning).
+	pub subject_public_key_info_sha256_fingerprint: [u8; 32],
+}
+
+impl TlsClientCertificateIdentity
+{
+	const SubjectAlternativeNameObjectIdentifier: &'static [u8] = &[0x55, 0x1D, 0x11];
+
+	/// The last common name (`CN`) in the subject, if any.
+	#[inline(always)]
+	pub fn common_name(&self) -> Option<&str>
+	{
+		self.subject.iter().rev().find(|&&(ref attribute_type, _)| attribute_type == "CN").map(|&(_, ref value)| value.as_str())
+	}
+
+	/// Returns `None` if there are no certificates or the leaf certificate can not be parsed (which is unlikely as it has been verified).
+	pub(crate) fn from_certificate_chain(certificate_chain: Vec<Certificate>) -> Option<Self>
+	{
+		let (subject, subject_alternative_names, subject_public_key_info_sha256_fingerprint) = Self::parse_leaf_certificate(&certificate_chain.first()?.0)?;
+
+		Some
+		(
+			Self
+			{
+				certificate_chain,
+				subject,
+				subject_alternative_names,
+				subject_public_key_info_sha256_fingerprint,
+			}
+		)
+	}
+
+	fn parse_leaf_certificate(certificate: &[u8]) -> Option<(Vec<(String, String)>, Vec<TlsSubjectAlternativeName>, [u8; 32])>
+	{
+		let (subject, subject_public_key_info, mut to_be_signed_certificate) = DerReader::certificate_subject_and_subject_public_key_info(certificate)?;
+
+		let subject = Self::parse_name(subject)?;
+
+		let subject_public_key_info_sha256_fingerprint =
+		{
+			let digest = ::ring::digest::digest(&::ring::digest::SHA256, subject_public_key_info);
+			let mut fingerprint = [0u8; 32];
+			fingerprint.copy_from_slice(digest.as_ref());
+			fingerprint
+		};
+
+		// Issuer unique identifier and subject unique identifier.
+		to_be_signed_certificate.read_optional(DerReader::context_specific_primitive(1))?;
+		to_be_signed_certificate.read_optional(DerReader::context_specific_primitive(2))?;
+
+		let mut subject_alternative_names = Vec::new();
+		if let Some(extensions) = to_be_signed_certificate.read_optional(DerReader::context_specific_constructed(3))?
+		{
+			let mut extensions = DerReader::new(DerReader::new(extensions).read(DerReader::Sequence)?);
+			while !extensions.is_empty()
+			{
+				let mut extension = DerReader::new(extensions.read(DerReader::Sequence)?);
+				let object_identifier = extension.read(DerReader::ObjectIdentifier)?;
+				extension.read_optional(DerReader::Boolean)?;
+				let extension_value = extension.read(DerReader::OctetString)?;
+
+				if object_identifier == Self::SubjectAlternativeNameObjectIdentifier
+				{
+					subject_alternative_names = TlsSubjectAlternativeName::parse_general_names(extension_value)?;
+				}
+			}
+		}
+
+		Some((subject, subject_alternative_names, subject_public_key_info_sha256_fingerprint))
+	}
+
+	fn parse_name(name: &[u8]) -> Option<Vec<(String, String)>>
+	{
+		let mut relative_distinguished_names = DerReader::new(name);
+		let mut attributes = Vec::new();
+		while !relative_distinguished_names.is_empty()
+		{
+			let mut relative_distinguished_name = DerReader::new(relative_distinguished_names.read(DerReader::Set)?);
+			while !relative_distinguished_name.is_empty()
+			{
+				let mut attribute_type_and_value = DerReader::new(relative_distinguished_name.read(DerReader::Sequence)?);
+				let attribute_type = DerReader::object_identifier_to_string(attribute_type_and_value.read(DerReader::ObjectIdentifier)?)?;
+				let (tag, content, _) = attribute_type_and_value.read_any()?;
+
+				if let Some(value) = DerReader::string_to_string(tag, content)
+				{
+					attributes.push((Self::attribute_type_short_name(attribute_type), value));
+				}
+			}
+		}
+		Some(attributes)
+	}
+
+	#[inline(always)]
+	fn attribute_type_short_name(attribute_type: String) -> String
+	{
+		let short_name = match attribute_type.as_str()
+		{
+			"2.5.4.3" => "CN",
+			"2.5.4.5" => "serialNumber",
+			"2.5.4.6" => "C",
+			"2.5.4.7" => "L",
+			"2.5.4.8" => "ST",
+			"2.5.4.9" => "street",
+			"2.5.4.10" => "O",
+			"2.5.4.11" => "OU",
+			"0.9.2342.19200300.100.1.1" => "UID",
+			"0.9.2342.19200300.100.1.25" => "DC",
+			"1.2.840.113549.1.9.1" => "emailAddress",
+			_ => return attribute_type,
+		};
+		short_name.to_string()
+	}
+}
+
+#[cfg(test)]
+mod tls_client_certificate_identity_tests
+{
+	use super::*;
+	use super::der_reader_tests::*;
+
+	fn attribute(object_identifier: &[u8], tag: u8, value: &[u8]) -> Vec<u8>
+	{
+		der(DerReader::Set, &der(DerReader::Sequence, &[der(DerReader::ObjectIdentifier, object_identifier), der(tag, value)].concat()))
+	}
+
+	fn subject() -> Vec<u8>
+	{
+		[
+			attribute(&[0x55, 0x04, 0x0A], DerReader::PrintableString, b"Example"),
+			attribute(&[0x55, 0x04, 0x03], DerReader::Utf8String, b"first"),
+			attribute(&[0x2B, 0x06, 0x01], DerReader::OctetString, b"not a string"),
+			attribute(&[0x55, 0x04, 0x61], DerReader::Utf8String, b"other"),
+			attribute(&[0x55, 0x04, 0x03], DerReader::Utf8String, b"client"),
+		].concat()
+	}
+
+	fn subject_alternative_names() -> Vec<u8>
+	{
+		der
+		(
+			DerReader::Sequence,
+			&[
+				der(DerReader::context_specific_primitive(2), b"client.example.com"),
+				der(DerReader::context_specific_primitive(1), b"client@example.com"),
+				der(DerReader::context_specific_primitive(6), b"spiffe://example.com/client"),
+				der(DerReader::context_specific_primitive(7), &[192, 0, 2, 1]),
+				der(DerReader::context_specific_primitive(7), &[0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+				der(DerReader::context_specific_primitive(8), &[0x2B, 0x06]),
+			].concat()
+		)
+	}
+
+	#[test]
+	fn from_certificate_chain()
+	{
+		use self::TlsSubjectAlternativeName::*;
+
+		let certificate = certificate(&subject(), Some((TlsClientCertificateIdentity::SubjectAlternativeNameObjectIdentifier, &subject_alternative_names()[..])));
+		let identity = TlsClientCertificateIdentity::from_certificate_chain(vec![Certificate(certificate.clone())]).unwrap();
+
+		assert_eq!(identity.certificate_chain, vec![Certificate(certificate)]);
+		assert_eq!(identity.subject, vec![("O".to_string(), "Example".to_string()), ("CN".to_string(), "first".to_string()), ("2.5.4.97".to_string(), "other".to_string()), ("CN".to_string(), "client".to_string())]);
+		assert_eq!(identity.common_name(), Some("client"));
+		assert_eq!
+		(
+			identity.subject_alternative_names,
+			vec!
+			[
+				DnsName("client.example.com".to_string()),
+				EmailAddress("client@example.com".to_string()),
+				UniformResourceIdentifier("spiffe://example.com/client".to_string()),
+				InternetProtocolAddress(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1))),
+				InternetProtocolAddress(IpAddr::V6(Ipv6Addr::new(0x2001, 0x0DB8, 0, 0, 0, 0, 0, 1))),
+				Other(8, vec![0x2B, 0x06]),
+			]
+		);
+
+		let digest = ::ring::digest::digest(&::ring::digest::SHA256, &subject_public_key_info());
+		assert_eq!(&identity.subject_public_key_info_sha256_fingerprint[..], digest.as_ref());
+	}
+
+	#[test]
+	fn without_extensions()
+	{
+		let identity = TlsClientCertificateIdentity::from_certificate_chain(vec![Certificate(certificate(&[], None))]).unwrap();
+
+		assert_eq!(identity.subject, vec![]);
+		assert_eq!(identity.common_name(), None);
+		assert_eq!(identity.subject_alternative_names, vec![]);
+	}
+
+	#[test]
+	fn other_extensions_are_ignored()
+	{
+		let identity = TlsClientCertificateIdentity::from_certificate_chain(vec![Certificate(certificate(&[], Some((&[0x55, 0x1D, 0x13][..], &der(DerReader::Sequence, &[])[..]))))]).unwrap();
+
+		assert_eq!(identity.subject_alternative_names, vec![]);
+	}
+
+	#[test]
+	fn empty_certificate_chain_is_none()
+	{
+		assert_eq!(TlsClientCertificateIdentity::from_certificate_chain(vec![]), None);
+	}
+
+	#[test]
+	fn truncated_certificate_is_none()
+	{
+		let certificate = certificate(&subject(), Some((TlsClientCertificateIdentity::SubjectAlternativeNameObjectIdentifier, &subject_alternative_names()[..])));
+		for length in 0 .. certificate.len()
+		{
+			assert_eq!(TlsClientCertificateIdentity::from_certificate_chain(vec![Certificate(certificate[.. length].to_vec())]), None, "length {}", length);
+		}
+	}
+
+	#[test]
+	fn malformed_subject_is_none()
+	{
+		let not_an_object_identifier = der(DerReader::Set, &der(DerReader::Sequence, &[der(DerReader::Integer, &[0x01]), der(DerReader::Utf8String, b"client")].concat()));
+		assert_eq!(TlsClientCertificateIdentity::from_certificate_chain(vec![Certificate(certificate(&not_an_object_identifier, None))]), None);
+
+		let not_a_set = der(DerReader::Sequence, &der(DerReader::Sequence, &[]));
+		assert_eq!(TlsClientCertificateIdentity::from_certificate_chain(vec![Certificate(certificate(&not_a_set, None))]), None);
+	}
+
+	#[test]
+	fn malformed_subject_alternative_names_is_none()
+	{
+		let internet_protocol_address_of_invalid_length = der(DerReader::Sequence, &der(DerReader::context_specific_primitive(7), &[192, 0, 2, 1, 0]));
+		assert_eq!(TlsClientCertificateIdentity::from_certificate_chain(vec![Certificate(certificate(&[], Some((TlsClientCertificateIdentity::SubjectAlternativeNameObjectIdentifier, &internet_protocol_address_of_invalid_length[..]))))]), None);
+
+		let dns_name_not_utf_8 = der(DerReader::Sequence, &der(DerReader::context_specific_primitive(2), &[0xFF]));
+		assert_eq!(TlsClientCertificateIdentity::from_certificate_chain(vec![Certificate(certificate(&[], Some((TlsClientCertificateIdentity::SubjectAlternativeNameObjectIdentifier, &dns_name_not_utf_8[..]))))]), None);
+
+		let not_a_sequence = der(DerReader::Set, &[]);
+		assert_eq!(TlsClientCertificateIdentity::from_certificate_chain(vec![Certificate(certificate(&[], Some((TlsClientCertificateIdentity::SubjectAlternativeNameObjectIdentifier, &not_a_sequence[..]))))]), None);
+	}
+}
@@ -34,6 +34,18 @@ impl<'yielder, SD: SocketData> Stream for TlsClientStream<'yielder, SD>
 		self.tls_generic_stream.write_data(write_from_buffer)
 	}
 
+	#[inline(always)]
+	fn read_vectored_data(&mut self, read_into_buffers: &mut [IoSliceMut]) -> Result<usize, CompleteError>
+	{
+		self.tls_generic_stream.read_vectored_data(read_into_buffers)
+	}
+
+	#[inline(always)]
+	fn write_vectored_data(&mut self, write_from_buffers: &[IoSlice]) -> Result<usize, CompleteError>
+	{
+		self.tls_generic_stream.write_vectored_data(write_from_buffers)
+	}
+
 	#[inline(always)]
 	fn flush_written_data(&mut self) -> Result<(), CompleteError>
 	{
@@ -47,10 +59,19 @@ impl<'yielder, SD: SocketData> Stream for TlsClientStream<'yielder, SD>
 	}
 }
 
+impl<'yielder, SD: SocketData> StreamRelayable<'yielder> for TlsClientStream<'yielder, SD>
+{
+	#[inline(always)]
+	fn stream_relay_endpoint<'a>(&'a mut self) -> StreamRelayEndpoint<'a, 'yielder> where 'yielder: 'a
+	{
+		self.tls_generic_stream.stream_relay_endpoint()
+	}
+}
+
 impl<'yielder, SD: SocketData> TlsClientStream<'yielder, SD>
 {
 	#[inline(always)]
-	pub(crate) fn new(generic_stream: GenericStream<'yielder, SD>, tls_configuration: &Arc<ClientConfig>, session_buffer_limit: usize, ascii_host_name: Rc<DNSName>) -> Result<Self, CompleteError>
+	pub(crate) fn new(generic_stream: GenericStream<'yielder, SD>, tls_configuration: &Arc<ClientConfig>, session_buffer_limit: usize, ascii_host_name: Rc<DNSName>, kernel_tls_offload: bool) -> Result<Self, CompleteError>
 	{
 		let tls_session = ClientSession::new(tls_configuration, ascii_host_name.deref().as_ref());
 
@@ -58,8 +79,86 @@ impl<'yielder, SD: SocketData> TlsClientStream<'yielder, SD>
 		(
 			Self
 			{
-				tls_generic_stream: TlsGenericStream::configure_and_handshake(generic_stream, tls_session, session_buffer_limit)?,
+				tls_generic_stream: TlsGenericStream::configure_and_handshake(generic_stream, tls_session, session_buffer_limit, kernel_tls_offload)?,
 			}
 		)
 	}
+
+	/// Sends as much of `early_data` as possible as TLS 1.3 0-RTT early data with the `ClientHello` when resuming; anything not sent as accepted early data is written after the handshake.
+	///
+	/// `tls_configuration` needs `enable_early_data` to be `true` and should have a session cache (see `TlsClientSessionCache`).
+	#[inline(always)]
+	pub(crate) fn new_with_early_data(generic_stream: GenericStream<'yielder, SD>, tls_configuration: &Arc<ClientConfig>, session_buffer_limit: usize, ascii_host_name: Rc<DNSName>, early_data: &[u8]) -> Result<(Self, TlsEarlyDataOutcome), CompleteError>
+	{
+		use self::TlsEarlyDataOutcome::*;
+
+		let mut tls_session = ClientSession::new(tls_configuration, ascii_host_name.deref().as_ref());
+
+		let early_data_bytes_sent = match tls_session.early_data()
+		{
+			None => None,
+
+			Some(mut write_early_data) => Some(write_early_data.write(early_data).map_err(CompleteError::Undifferentiated)?),
+		};
+
+		let mut this = Self
+		{
+			tls_generic_stream: TlsGenericStream::configure_and_handshake(generic_stream, tls_session, session_buffer_limit, false)?,
+		};
+
+		let (outcome, remaining_data) = match early_data_bytes_sent
+		{
+			None => (NotOffered, early_data),
+
+			Some(early_data_bytes_sent) => if this.tls_generic_stream.tls_session.is_early_data_accepted()
+			{
+				(Accepted { early_data_bytes_sent }, &early_data[early_data_bytes_sent .. ])
+			}
+			else
+			{
+				(Rejected, early_data)
+			},
+		};
+
+		let mut remaining_data = remaining_data;
+		while !remaining_data.is_empty()
+		{
+			let bytes_written = this.tls_generic_stream.write_data(remaining_data)?;
+			remaining_data = &remaining_data[bytes_written .. ];
+		}
+
+		Ok((this, outcome))
+	}
+
+	/// Is the encryption and decryption of records done by the kernel (kTLS)?
+	#[inline(always)]
+	pub fn is_kernel_tls_offloaded(&self) -> bool
+	{
+		self.tls_generic_stream.is_kernel_tls_offloaded()
+	}
+
+	/// Sends up to `length` bytes of `file`, starting at `offset`.
+	///
+	/// If the encryption of records is done by the kernel, this uses `sendfile(2)`, so that the bytes are not copied into userspace.
+	///
+	/// Waits (yields) until all `length` bytes have been sent or the end of `file` has been reached; returns the number of bytes sent.
+	#[inline(always)]
+	pub fn send_file(&mut self, file: &impl AsRawFd, offset: u64, length: usize) -> Result<usize, CompleteError>
+	{
+		self.tls_generic_stream.send_file(file, offset, length)
+	}
+
+	/// Only possible if the decryption of records has been offloaded to the kernel.
+	#[inline(always)]
+	pub(crate) fn peek_more_data(&mut self, peek_into_buffer: &mut [u8], already_peeked: usize) -> Result<usize, CompleteError>
+	{
+		self.tls_generic_stream.peek_more_data(peek_into_buffer, already_peeked)
+	}
+
+	/// Only possible if the encryption and decryption of records has been offloaded to the kernel; the socket can then be used as if unencrypted.
+	#[inline(always)]
+	pub(crate) fn unwrap_kernel_tls_offloaded(self) -> Result<(StreamingSocketFileDescriptor<SD>, Yielder<'yielder, ReactEdgeTriggeredStatus, (), Result<(), CompleteError>>), CompleteError>
+	{
+		Ok(self.tls_generic_stream.unwrap_kernel_tls_offloaded()?.unwrap())
+	}
 }
@@ -0,0 +1,25 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// How data supplied to be sent as TLS 1.3 0-RTT early data was sent.
+///
+/// In all cases, all of the data has been sent by the time the stream is available.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TlsEarlyDataOutcome
+{
+	/// Early data could not be offered (eg there was no session to resume, or the server does not permit early data); the data was sent after the handshake.
+	NotOffered,
+
+	/// The server accepted early data; any data beyond `early_data_bytes_sent` (limited by the server's maximum early data size) was sent after the handshake.
+	///
+	/// Early data can be replayed by an attacker, so should only be used for idempotent requests.
+	Accepted
+	{
+		/// Number of bytes sent as early data.
+		early_data_bytes_sent: usize,
+	},
+
+	/// The server rejected early data; the data was sent again after the handshake.
+	Rejected,
+}
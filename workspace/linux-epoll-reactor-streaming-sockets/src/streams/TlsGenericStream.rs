@@ -7,6 +7,7 @@ struct TlsGenericStream<'yielder, SD: SocketData, S: SessionExt>
 {
 	generic_stream: GenericStream<'yielder, SD>,
 	tls_session: S,
+	kernel_offload: Option<TlsKernelOffload>,
 }
 
 impl<'yielder, SD: SocketData> TlsGenericStream<'yielder, SD, ServerSession>
@@ -20,21 +21,92 @@ impl<'yielder, SD: SocketData> TlsGenericStream<'yielder, SD, ServerSession>
 
 impl<'yielder, SD: SocketData, S: SessionExt> TlsGenericStream<'yielder, SD, S>
 {
+	/// If `kernel_tls_offload` is true, the session's configuration must use a `TlsKernelOffloadKeyLog`; if record encryption and decryption can not be offloaded to the kernel, they continue to be done by the session.
 	#[inline(always)]
-	fn configure_and_handshake(mut generic_stream: GenericStream<'yielder, SD>, mut tls_session: S, session_buffer_limit: usize) -> Result<Self, CompleteError>
+	fn configure_and_handshake(mut generic_stream: GenericStream<'yielder, SD>, mut tls_session: S, session_buffer_limit: usize, kernel_tls_offload: bool) -> Result<Self, CompleteError>
 	{
 		tls_session.set_buffer_limit(session_buffer_limit);
-		generic_stream.tls_handshake(&mut tls_session)?;
+
+		let kernel_offload = if kernel_tls_offload
+		{
+			generic_stream.tls_handshake_for_kernel_offload(&mut tls_session)?
+		}
+		else
+		{
+			generic_stream.tls_handshake(&mut tls_session)?;
+			None
+		};
+
 		Ok
 		(
 			Self
 			{
 				generic_stream,
 				tls_session,
+				kernel_offload,
 			}
 		)
 	}
 
+	#[inline(always)]
+	fn is_kernel_tls_offloaded(&self) -> bool
+	{
+		self.kernel_offload.is_some()
+	}
+
+	/// Only possible if the decryption of records has been offloaded to the kernel, as a session can not peek at decrypted data.
+	#[inline(always)]
+	fn peek_more_data(&mut self, peek_into_buffer: &mut [u8], already_peeked: usize) -> Result<usize, CompleteError>
+	{
+		match self.kernel_offload
+		{
+			None => Err(CompleteError::from(TlsInputOutputError::KernelOffloadImpossible)),
+
+			Some(ref mut kernel_offload) => self.generic_stream.kernel_tls_peek_more_data(kernel_offload, peek_into_buffer, already_peeked),
+		}
+	}
+
+	/// Hands over a socket whose records are encrypted and decrypted by the kernel, so that it can be used as if unencrypted, eg by another stream factory.
+	///
+	/// Only application data must be received afterwards, as any other record causes reads of the socket to fail.
+	#[inline(always)]
+	fn unwrap_kernel_tls_offloaded(self) -> Result<GenericStream<'yielder, SD>, CompleteError>
+	{
+		match self.kernel_offload
+		{
+			None => Err(CompleteError::from(TlsInputOutputError::KernelOffloadImpossible)),
+
+			Some(kernel_offload) =>
+			{
+				kernel_offload.hand_over()?;
+				Ok(self.generic_stream)
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn stream_relay_endpoint<'a>(&'a mut self) -> StreamRelayEndpoint<'a, 'yielder> where 'yielder: 'a, S: 'static
+	{
+		match self.kernel_offload
+		{
+			None => self.generic_stream.stream_relay_endpoint(Some(&mut self.tls_session)),
+
+			Some(ref mut kernel_offload) => self.generic_stream.stream_relay_endpoint(Some(kernel_offload)),
+		}
+	}
+
+	#[inline(always)]
+	fn peer_certificates(&self) -> Option<Vec<Certificate>>
+	{
+		self.tls_session.get_peer_certificates()
+	}
+
+	#[inline(always)]
+	fn application_layer_protocol_negotiation_protocol(&self) -> Option<&[u8]>
+	{
+		self.tls_session.get_alpn_protocol()
+	}
+
 	/// This is a lie (ie the lifetime is ***NOT*** `'static`); the actual lifetime is ***LESS THAN*** `'yielder` and is the same as the lifetime of the underlying TLS `S: SessionExt`.
 	#[inline(always)]
 	fn common_tls_post_handshake_information(&self) -> CommonTlsPostHandshakeInformation<'static>
@@ -48,24 +120,120 @@ impl<'yielder, SD: SocketData, S: SessionExt> TlsGenericStream<'yielder, SD, S>
 	#[inline(always)]
 	fn read_data(&mut self, read_into_buffer: &mut [u8]) -> Result<usize, CompleteError>
 	{
-		self.generic_stream.tls_read(&mut self.tls_session, read_into_buffer)
+		match self.kernel_offload
+		{
+			None => self.generic_stream.tls_read(&mut self.tls_session, read_into_buffer),
+
+			Some(ref mut kernel_offload) => self.generic_stream.kernel_tls_read_vectored(kernel_offload, &mut [IoSliceMut::new(read_into_buffer)]),
+		}
 	}
 
 	#[inline(always)]
 	fn write_data(&mut self, write_from_buffer: &[u8]) -> Result<usize, CompleteError>
 	{
-		self.generic_stream.tls_write(&mut self.tls_session, write_from_buffer)
+		match self.kernel_offload
+		{
+			None => self.generic_stream.tls_write(&mut self.tls_session, write_from_buffer),
+
+			Some(_) => self.generic_stream.kernel_tls_write_vectored(&[IoSlice::new(write_from_buffer)]),
+		}
+	}
+
+	#[inline(always)]
+	fn read_vectored_data(&mut self, read_into_buffers: &mut [IoSliceMut]) -> Result<usize, CompleteError>
+	{
+		match self.kernel_offload
+		{
+			None => self.generic_stream.tls_read_vectored(&mut self.tls_session, read_into_buffers),
+
+			Some(ref mut kernel_offload) => self.generic_stream.kernel_tls_read_vectored(kernel_offload, read_into_buffers),
+		}
+	}
+
+	#[inline(always)]
+	fn write_vectored_data(&mut self, write_from_buffers: &[IoSlice]) -> Result<usize, CompleteError>
+	{
+		match self.kernel_offload
+		{
+			None => self.generic_stream.tls_write_vectored(&mut self.tls_session, write_from_buffers),
+
+			Some(_) => self.generic_stream.kernel_tls_write_vectored(write_from_buffers),
+		}
 	}
 
 	#[inline(always)]
 	fn flush_written_data(&mut self) -> Result<(), CompleteError>
 	{
-		self.generic_stream.tls_flush_written_data(&mut self.tls_session)
+		match self.kernel_offload
+		{
+			None => self.generic_stream.tls_flush_written_data(&mut self.tls_session),
+
+			// The kernel sends records as they are written.
+			Some(_) => Ok(()),
+		}
 	}
 
 	#[inline(always)]
 	fn finish(mut self) -> Result<(), CompleteError>
 	{
-		self.generic_stream.tls_finish(&mut self.tls_session)
+		match self.kernel_offload
+		{
+			None => self.generic_stream.tls_finish(&mut self.tls_session),
+
+			Some(ref mut kernel_offload) => self.generic_stream.kernel_tls_finish(kernel_offload),
+		}
+	}
+
+	/// Uses `sendfile()` if record encryption has been offloaded to the kernel; otherwise, `file` is read into userspace and encrypted by the session.
+	#[inline(always)]
+	fn send_file(&mut self, file: &impl AsRawFd, offset: u64, length: usize) -> Result<usize, CompleteError>
+	{
+		use ::linux_epoll::libc::pread;
+
+		if self.is_kernel_tls_offloaded()
+		{
+			return self.generic_stream.send_file(file, offset, length)
+		}
+
+		let file_descriptor = file.as_raw_fd();
+
+		let mut buffer: [u8; 16 * 1024] = unsafe { uninitialized() };
+		let mut offset = GenericStream::<SD>::file_offset(offset)?;
+		let mut total_bytes_written = 0;
+		while total_bytes_written < length
+		{
+			let result = unsafe { pread(file_descriptor, buffer.as_mut_ptr() as *mut c_void, min(buffer.len(), length - total_bytes_written), offset) };
+
+			let bytes_read = if likely!(result >= 0)
+			{
+				result as usize
+			}
+			else
+			{
+				let io_error = io::Error::last_os_error();
+				match io_error.kind()
+				{
+					ErrorKind::Interrupted => continue,
+
+					_ => return Err(CompleteError::Undifferentiated(io_error)),
+				}
+			};
+
+			if unlikely!(bytes_read == 0)
+			{
+				break
+			}
+			offset += bytes_read as off_t;
+
+			let mut remaining_bytes = &buffer[.. bytes_read];
+			while !remaining_bytes.is_empty()
+			{
+				let bytes_written = self.write_data(remaining_bytes)?;
+				remaining_bytes = &remaining_bytes[bytes_written .. ];
+			}
+			total_bytes_written += bytes_read;
+		}
+
+		Ok(total_bytes_written)
 	}
 }
@@ -0,0 +1,828 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A TLS 1.3 session whose records are encrypted and decrypted by the Linux kernel (kTLS) after rustls has done the handshake.
+///
+/// Application data is sent and received with ordinary socket system calls (and can be sent with `sendfile()`); the kernel passes any other records received to here.
+/// New session tickets received are discarded (so can not be used to resume a session) and any other post-handshake message, such as a key update, is an error, as the kernel can not change keys.
+///
+/// When relaying, it is not spliceable, as `splice()` can not receive records other than application data.
+#[derive(Debug, Default)]
+pub(crate) struct TlsKernelOffload
+{
+	received_control_record_type: u8,
+	received_control_record_bytes: Vec<u8>,
+	close_notify_alert_received: bool,
+	close_notify_alert_queued: bool,
+}
+
+impl StreamRelayTransport for TlsKernelOffload
+{
+	#[inline(always)]
+	fn relay_read(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, read_into_buffer: &mut [u8]) -> Result<Option<usize>, CompleteError>
+	{
+		match self.receive(raw_file_descriptor, byte_counter, &mut [IoSliceMut::new(read_into_buffer)])
+		{
+			Err(CompleteError::Tls(TlsInputOutputError::BufferReadCloseNotifyAlertReceived)) => Ok(Some(0)),
+
+			result => result,
+		}
+	}
+
+	#[inline(always)]
+	fn relay_write(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, write_from_buffer: &[u8]) -> Result<Option<usize>, CompleteError>
+	{
+		Self::send(raw_file_descriptor, byte_counter, &[IoSlice::new(write_from_buffer)])
+	}
+
+	#[inline(always)]
+	fn relay_flush(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter) -> Result<bool, CompleteError>
+	{
+		self.send_queued_close_notify_alert(raw_file_descriptor, byte_counter)
+	}
+
+	#[inline(always)]
+	fn relay_close(&mut self)
+	{
+		self.queue_close_notify_alert()
+	}
+}
+
+impl TlsKernelOffload
+{
+	const SOL_TLS: c_int = 282;
+
+	const TCP_ULP: c_int = 31;
+
+	const TLS_TX: c_int = 1;
+
+	const TLS_RX: c_int = 2;
+
+	const TLS_SET_RECORD_TYPE: c_int = 1;
+
+	const TLS_GET_RECORD_TYPE: c_int = 2;
+
+	const TLS_1_3_VERSION: u16 = 0x0304;
+
+	const TLS_CIPHER_AES_GCM_128: u16 = 51;
+
+	const TLS_CIPHER_AES_GCM_256: u16 = 52;
+
+	const TLS_CIPHER_CHACHA20_POLY1305: u16 = 54;
+
+	const AlertContentType: u8 = 21;
+
+	const HandshakeContentType: u8 = 22;
+
+	const ApplicationDataContentType: u8 = 23;
+
+	const RecordHeaderLength: usize = 5;
+
+	const HandshakeMessageHeaderLength: usize = 4;
+
+	const NewSessionTicketHandshakeType: u8 = 4;
+
+	const WarningAlertLevel: u8 = 1;
+
+	const CloseNotifyAlertDescription: u8 = 0;
+
+	/// Far larger than any new session ticket.
+	const MaximumHandshakeMessageLength: usize = 16 * 1024;
+
+	/// `IOV_MAX` on Linux.
+	const MaximumNumberOfBuffers: usize = 1024;
+
+	/// Tries to offload the encryption of records sent and the decryption of records received to the kernel.
+	///
+	/// Returns `Ok(None)` if the kernel does not support this (eg the `tls` module is not loaded, the socket is not a TCP socket or the kernel does not support TLS 1.3 or `cipher_suite`), in which case the session continues to be used.
+	///
+	/// Returns an error, which ends the connection, in the unlikely event that receive is offloaded but transmit then can not be; received records would then be decrypted by the kernel, so the session can no longer be used, but sent records would not be encrypted by it.
+	/// There is no order which avoids this, as offloading either direction can not be undone; receive is offloaded first as it is the more likely to be unsupported.
+	///
+	/// `transmit_record_sequence_number` is the number of records already sent using `transmit_traffic_secret`; no records must have been received using `receive_traffic_secret`.
+	#[inline(always)]
+	pub(crate) fn install(raw_file_descriptor: RawFd, cipher_suite: &SupportedCipherSuite, transmit_traffic_secret: &[u8], receive_traffic_secret: &[u8], transmit_record_sequence_number: u64) -> Result<Option<Self>, CompleteError>
+	{
+		if Self::set_socket_option(raw_file_descriptor, IPPROTO_TCP, Self::TCP_ULP, b"tls").is_err()
+		{
+			return Ok(None)
+		}
+
+		// Receive is set first, as kernels supported TLS 1.3 for transmit before receive.
+		// Until it is set, the socket behaves as before; once it is set, it can not be unset.
+		if Self::set_socket_option(raw_file_descriptor, Self::SOL_TLS, Self::TLS_RX, &Self::crypto_information(cipher_suite, receive_traffic_secret, 0)).is_err()
+		{
+			return Ok(None)
+		}
+
+		Self::set_socket_option(raw_file_descriptor, Self::SOL_TLS, Self::TLS_TX, &Self::crypto_information(cipher_suite, transmit_traffic_secret, transmit_record_sequence_number)).map_err(CompleteError::Undifferentiated)?;
+
+		Ok(Some(Self::default()))
+	}
+
+	/// Returns `Ok(None)` if no application data has been received yet and `Ok(Some(0))` at end-of-file.
+	///
+	/// As for rustls, receiving a close notify alert is an error.
+	#[inline(always)]
+	pub(crate) fn receive(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, read_into_buffers: &mut [IoSliceMut]) -> Result<Option<usize>, CompleteError>
+	{
+		if unlikely!(self.close_notify_alert_received)
+		{
+			return Err(CompleteError::from(TlsInputOutputError::BufferReadCloseNotifyAlertReceived))
+		}
+
+		// Otherwise a record other than application data would be received as zero bytes, forever.
+		if unlikely!(read_into_buffers.iter().all(|read_into_buffer| read_into_buffer.is_empty()))
+		{
+			return Ok(Some(0))
+		}
+
+		let number_of_buffers = min(read_into_buffers.len(), Self::MaximumNumberOfBuffers);
+		loop
+		{
+			let (bytes_received, record_type) = match Self::receive_message(raw_file_descriptor, &mut read_into_buffers[.. number_of_buffers], 0)?
+			{
+				None => return Ok(None),
+
+				Some(received) => received,
+			};
+			byte_counter.bytes_read(bytes_received);
+
+			if record_type == Self::ApplicationDataContentType
+			{
+				return Ok(Some(bytes_received))
+			}
+
+			let mut remaining_bytes = bytes_received;
+			for read_into_buffer in read_into_buffers[.. number_of_buffers].iter()
+			{
+				if remaining_bytes == 0
+				{
+					break
+				}
+
+				let length = min(remaining_bytes, read_into_buffer.len());
+				self.received_control_record(record_type, &read_into_buffer[.. length])?;
+				remaining_bytes -= length;
+			}
+
+			if unlikely!(self.close_notify_alert_received)
+			{
+				return Err(CompleteError::from(TlsInputOutputError::BufferReadCloseNotifyAlertReceived))
+			}
+		}
+	}
+
+	/// Peeks at received application data without consuming it; any other records received before it are consumed.
+	///
+	/// Returns `Ok(None)` if no application data has been received yet and `Ok(Some(0))` at end-of-file.
+	#[inline(always)]
+	pub(crate) fn peek(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, peek_into_buffer: &mut [u8]) -> Result<Option<usize>, CompleteError>
+	{
+		use ::linux_epoll::libc::MSG_PEEK;
+
+		if unlikely!(self.close_notify_alert_received)
+		{
+			return Err(CompleteError::from(TlsInputOutputError::BufferReadCloseNotifyAlertReceived))
+		}
+
+		if unlikely!(peek_into_buffer.is_empty())
+		{
+			return Ok(Some(0))
+		}
+
+		loop
+		{
+			let (bytes_peeked, record_type) = match Self::receive_message(raw_file_descriptor, &mut [IoSliceMut::new(peek_into_buffer)], MSG_PEEK)?
+			{
+				None => return Ok(None),
+
+				Some(peeked) => peeked,
+			};
+
+			if record_type == Self::ApplicationDataContentType
+			{
+				return Ok(Some(bytes_peeked))
+			}
+
+			// Consume the record peeked at.
+			let bytes_received = match Self::receive_message(raw_file_descriptor, &mut [IoSliceMut::new(&mut peek_into_buffer[.. bytes_peeked])], 0)?
+			{
+				Some((bytes_received, received_record_type)) if received_record_type == record_type => bytes_received,
+
+				_ => return Err(CompleteError::SocketRead(io::Error::from(ErrorKind::InvalidData))),
+			};
+			byte_counter.bytes_read(bytes_received);
+			self.received_control_record(record_type, &peek_into_buffer[.. bytes_received])?;
+
+			if unlikely!(self.close_notify_alert_received)
+			{
+				return Err(CompleteError::from(TlsInputOutputError::BufferReadCloseNotifyAlertReceived))
+			}
+		}
+	}
+
+	/// Checks that no part of a post-handshake message or alert has been received before the socket is used without this, as if unencrypted.
+	#[inline(always)]
+	pub(crate) fn hand_over(self) -> Result<(), CompleteError>
+	{
+		if unlikely!(self.close_notify_alert_received)
+		{
+			return Err(CompleteError::from(TlsInputOutputError::BufferReadCloseNotifyAlertReceived))
+		}
+
+		if unlikely!(!self.received_control_record_bytes.is_empty())
+		{
+			return Err(Self::error(TLSError::PeerMisbehavedError("Part of a post-handshake message or alert received before handing over".to_string())))
+		}
+
+		Ok(())
+	}
+
+	/// Returns `Ok(None)` if no bytes can be written yet.
+	#[inline(always)]
+	pub(crate) fn send(raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, write_from_buffers: &[IoSlice]) -> Result<Option<usize>, CompleteError>
+	{
+		let mut message_header: msghdr = unsafe { zeroed() };
+		// `IoSlice` is guaranteed to be ABI compatible with `iovec`.
+		message_header.msg_iov = write_from_buffers.as_ptr() as *mut iovec;
+		message_header.msg_iovlen = min(write_from_buffers.len(), Self::MaximumNumberOfBuffers);
+
+		Self::send_message(raw_file_descriptor, byte_counter, &message_header)
+	}
+
+	/// Follow with `send_queued_close_notify_alert()`.
+	#[inline(always)]
+	pub(crate) fn queue_close_notify_alert(&mut self)
+	{
+		self.close_notify_alert_queued = true
+	}
+
+	/// Returns `Ok(false)` if a queued close notify alert could not be sent yet.
+	#[inline(always)]
+	pub(crate) fn send_queued_close_notify_alert(&mut self, raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter) -> Result<bool, CompleteError>
+	{
+		if !self.close_notify_alert_queued
+		{
+			return Ok(true)
+		}
+
+		let alert = [Self::WarningAlertLevel, Self::CloseNotifyAlertDescription];
+		let mut buffer = iovec
+		{
+			iov_base: alert.as_ptr() as *mut c_void,
+			iov_len: alert.len(),
+		};
+
+		// Of `usize` to ensure `cmsghdr` alignment.
+		let mut control_buffer: [usize; 4] = [0; 4];
+
+		let mut message_header: msghdr = unsafe { zeroed() };
+		message_header.msg_iov = &mut buffer;
+		message_header.msg_iovlen = 1;
+		message_header.msg_control = control_buffer.as_mut_ptr() as *mut c_void;
+		message_header.msg_controllen = unsafe { CMSG_SPACE(size_of::<u8>() as u32) } as usize;
+
+		unsafe
+		{
+			let control_message_header = CMSG_FIRSTHDR(&message_header);
+			(*control_message_header).cmsg_level = Self::SOL_TLS;
+			(*control_message_header).cmsg_type = Self::TLS_SET_RECORD_TYPE;
+			(*control_message_header).cmsg_len = CMSG_LEN(size_of::<u8>() as u32) as usize;
+			*CMSG_DATA(control_message_header) = Self::AlertContentType;
+		}
+
+		match Self::send_message(raw_file_descriptor, byte_counter, &message_header)?
+		{
+			None => Ok(false),
+
+			Some(_) =>
+			{
+				self.close_notify_alert_queued = false;
+				Ok(true)
+			}
+		}
+	}
+
+	/// Counts the TLS records in `records`, the last of which can be incomplete.
+	#[inline(always)]
+	pub(crate) fn number_of_records(records: &[u8]) -> u64
+	{
+		let mut number_of_records = 0;
+		let mut remaining_records = records;
+		while !remaining_records.is_empty()
+		{
+			number_of_records += 1;
+
+			if unlikely!(remaining_records.len() < Self::RecordHeaderLength)
+			{
+				break
+			}
+			let record_length = Self::RecordHeaderLength + u16::from_be_bytes([remaining_records[3], remaining_records[4]]) as usize;
+			remaining_records = &remaining_records[min(record_length, remaining_records.len()) .. ];
+		}
+		number_of_records
+	}
+
+	/// Creates a `struct tls12_crypto_info_aes_gcm_128`, `struct tls12_crypto_info_aes_gcm_256` or `struct tls12_crypto_info_chacha20_poly1305` (see `<linux/tls.h>`), with the key and initialization vector derived from `traffic_secret` (RFC 8446, Section 7.3).
+	#[inline(always)]
+	fn crypto_information(cipher_suite: &SupportedCipherSuite, traffic_secret: &[u8], record_sequence_number: u64) -> Vec<u8>
+	{
+		use self::BulkAlgorithm::*;
+
+		let cipher_type = match cipher_suite.bulk
+		{
+			AES_128_GCM => Self::TLS_CIPHER_AES_GCM_128,
+
+			AES_256_GCM => Self::TLS_CIPHER_AES_GCM_256,
+
+			CHACHA20_POLY1305 => Self::TLS_CIPHER_CHACHA20_POLY1305,
+		};
+
+		let hash = cipher_suite.get_hash();
+
+		let mut key = vec![0u8; cipher_suite.enc_key_len];
+		Self::hkdf_expand_label(hash, traffic_secret, b"key", &mut key[..]);
+
+		let mut initialization_vector = [0u8; 12];
+		Self::hkdf_expand_label(hash, traffic_secret, b"iv", &mut initialization_vector[..]);
+
+		// For AES-GCM, the kernel calls the first 4 bytes of the initialization vector the salt.
+		let (salt, initialization_vector) = if cipher_type == Self::TLS_CIPHER_CHACHA20_POLY1305
+		{
+			(&initialization_vector[.. 0], &initialization_vector[..])
+		}
+		else
+		{
+			initialization_vector.split_at(4)
+		};
+
+		let mut crypto_information = Vec::with_capacity(56);
+		crypto_information.extend_from_slice(&Self::TLS_1_3_VERSION.to_ne_bytes());
+		crypto_information.extend_from_slice(&cipher_type.to_ne_bytes());
+		crypto_information.extend_from_slice(initialization_vector);
+		crypto_information.extend_from_slice(&key[..]);
+		crypto_information.extend_from_slice(salt);
+		crypto_information.extend_from_slice(&record_sequence_number.to_be_bytes());
+		crypto_information
+	}
+
+	/// `HKDF-Expand-Label()` with an empty context (RFC 8446, Section 7.1).
+	#[inline(always)]
+	fn hkdf_expand_label(hash: &'static ::ring::digest::Algorithm, secret: &[u8], label: &[u8], output: &mut [u8])
+	{
+		use ::ring::hkdf::expand;
+		use ::ring::hmac::SigningKey;
+
+		const LabelPrefix: &'static [u8] = b"tls13 ";
+
+		let mut hkdf_label = Vec::with_capacity(2 + 1 + LabelPrefix.len() + label.len() + 1);
+		hkdf_label.extend_from_slice(&(output.len() as u16).to_be_bytes());
+		hkdf_label.push((LabelPrefix.len() + label.len()) as u8);
+		hkdf_label.extend_from_slice(LabelPrefix);
+		hkdf_label.extend_from_slice(label);
+		hkdf_label.push(0);
+
+		expand(&SigningKey::new(hash, secret), &hkdf_label[..], output)
+	}
+
+	/// Returns `Ok(None)` if nothing has been received yet; otherwise, the number of bytes received and their record type.
+	///
+	/// The kernel never mixes records of different types in one receive.
+	#[inline(always)]
+	fn receive_message(raw_file_descriptor: RawFd, read_into_buffers: &mut [IoSliceMut], flags: c_int) -> Result<Option<(usize, u8)>, CompleteError>
+	{
+		loop
+		{
+			// Large enough for one `cmsghdr` with one byte of data; of `usize` to ensure `cmsghdr` alignment.
+			let mut control_buffer: [usize; 4] = [0; 4];
+
+			let mut message_header: msghdr = unsafe { zeroed() };
+			// `IoSliceMut` is guaranteed to be ABI compatible with `iovec`.
+			message_header.msg_iov = read_into_buffers.as_mut_ptr() as *mut iovec;
+			message_header.msg_iovlen = read_into_buffers.len();
+			message_header.msg_control = control_buffer.as_mut_ptr() as *mut c_void;
+			message_header.msg_controllen = size_of::<[usize; 4]>();
+
+			let result = unsafe { recvmsg(raw_file_descriptor, &mut message_header, flags) };
+
+			if likely!(result >= 0)
+			{
+				let record_type = Self::received_record_type(&message_header).unwrap_or(Self::ApplicationDataContentType);
+				return Ok(Some((result as usize, record_type)))
+			}
+
+			let io_error = io::Error::last_os_error();
+			match io_error.kind()
+			{
+				ErrorKind::Interrupted => continue,
+
+				ErrorKind::WouldBlock => return Ok(None),
+
+				_ => return Err(CompleteError::SocketVectoredRead(io_error)),
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn received_record_type(message_header: &msghdr) -> Option<u8>
+	{
+		unsafe
+		{
+			let control_message_header = CMSG_FIRSTHDR(message_header);
+			if control_message_header.is_null()
+			{
+				return None
+			}
+
+			if (*control_message_header).cmsg_level == Self::SOL_TLS && (*control_message_header).cmsg_type == Self::TLS_GET_RECORD_TYPE
+			{
+				Some(*CMSG_DATA(control_message_header))
+			}
+			else
+			{
+				None
+			}
+		}
+	}
+
+	/// `bytes` can be part of a record or a message; the remainder is passed in subsequent calls.
+	#[inline(always)]
+	fn received_control_record(&mut self, record_type: u8, bytes: &[u8]) -> Result<(), CompleteError>
+	{
+		if self.received_control_record_bytes.is_empty()
+		{
+			self.received_control_record_type = record_type
+		}
+		else if unlikely!(self.received_control_record_type != record_type)
+		{
+			return Err(Self::error(TLSError::PeerMisbehavedError("Record received in the middle of a post-handshake message or alert".to_string())))
+		}
+
+		self.received_control_record_bytes.extend_from_slice(bytes);
+
+		if record_type == Self::AlertContentType
+		{
+			self.received_alert()
+		}
+		else if record_type == Self::HandshakeContentType
+		{
+			self.received_handshake_messages()
+		}
+		else
+		{
+			Err(Self::error(TLSError::PeerMisbehavedError(format!("Record of type {} received after handshake", record_type))))
+		}
+	}
+
+	#[inline(always)]
+	fn received_alert(&mut self) -> Result<(), CompleteError>
+	{
+		use ::rustls::internal::msgs::codec::Codec;
+		use ::rustls::internal::msgs::enums::AlertDescription;
+
+		if self.received_control_record_bytes.len() < 2
+		{
+			return Ok(())
+		}
+
+		let description = self.received_control_record_bytes[1];
+		self.received_control_record_bytes.clear();
+
+		if description == Self::CloseNotifyAlertDescription
+		{
+			self.close_notify_alert_received = true;
+			Ok(())
+		}
+		else
+		{
+			Err(Self::error(TLSError::AlertReceived(AlertDescription::read_bytes(&[description]).unwrap())))
+		}
+	}
+
+	#[inline(always)]
+	fn received_handshake_messages(&mut self) -> Result<(), CompleteError>
+	{
+		use ::rustls::internal::msgs::codec::Codec;
+		use ::rustls::internal::msgs::enums::HandshakeType;
+
+		loop
+		{
+			let message_length =
+			{
+				let bytes = &self.received_control_record_bytes[..];
+
+				if bytes.len() < Self::HandshakeMessageHeaderLength
+				{
+					return Ok(())
+				}
+
+				let handshake_type = bytes[0];
+				if unlikely!(handshake_type != Self::NewSessionTicketHandshakeType)
+				{
+					return Err(Self::error(TLSError::InappropriateHandshakeMessage { expect_types: vec![HandshakeType::NewSessionTicket], got_type: HandshakeType::read_bytes(&[handshake_type]).unwrap() }))
+				}
+
+				let message_length = Self::HandshakeMessageHeaderLength + ((bytes[1] as usize) << 16 | (bytes[2] as usize) << 8 | (bytes[3] as usize));
+				if unlikely!(message_length > Self::MaximumHandshakeMessageLength)
+				{
+					return Err(Self::error(TLSError::PeerMisbehavedError("New session ticket message too long".to_string())))
+				}
+
+				if bytes.len() < message_length
+				{
+					return Ok(())
+				}
+
+				message_length
+			};
+
+			// New session tickets are discarded.
+			self.received_control_record_bytes.drain(.. message_length);
+		}
+	}
+
+	#[inline(always)]
+	fn send_message(raw_file_descriptor: RawFd, byte_counter: &mut ByteCounter, message_header: &msghdr) -> Result<Option<usize>, CompleteError>
+	{
+		loop
+		{
+			let result = unsafe { sendmsg(raw_file_descriptor, message_header, MSG_NOSIGNAL) };
+
+			if likely!(result >= 0)
+			{
+				let bytes_written = result as usize;
+				byte_counter.bytes_written(bytes_written);
+				return Ok(Some(bytes_written))
+			}
+
+			let io_error = io::Error::last_os_error();
+			match io_error.kind()
+			{
+				ErrorKind::Interrupted => continue,
+
+				ErrorKind::WouldBlock => return Ok(None),
+
+				_ => return Err(CompleteError::SocketVectoredWrite(io_error)),
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn set_socket_option(raw_file_descriptor: RawFd, level: c_int, option_name: c_int, option_value: &[u8]) -> io::Result<()>
+	{
+		let result = unsafe { setsockopt(raw_file_descriptor, level, option_name, option_value.as_ptr() as *const c_void, option_value.len() as socklen_t) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(io::Error::last_os_error())
+		}
+	}
+
+	#[inline(always)]
+	fn error(tls_error: TLSError) -> CompleteError
+	{
+		CompleteError::from(TlsInputOutputError::ProcessNewPackets(tls_error))
+	}
+}
+
+#[cfg(test)]
+mod tls_kernel_offload_tests
+{
+	use super::*;
+	use ::rustls::ALL_CIPHERSUITES;
+	use ::rustls::CipherSuite;
+	use ::rustls::internal::msgs::enums::AlertDescription;
+	use ::rustls::internal::msgs::enums::HandshakeType;
+
+	/// `server_application_traffic_secret_0` from RFC 8448, Section 3.
+	const ServerApplicationTrafficSecret: [u8; 32] = [0xA1, 0x1A, 0xF9, 0xF0, 0x55, 0x31, 0xF8, 0x56, 0xAD, 0x47, 0x11, 0x6B, 0x45, 0xA9, 0x50, 0x32, 0x82, 0x04, 0xB4, 0xF4, 0x4B, 0xFB, 0x6B, 0x3A, 0x4B, 0x4F, 0x1F, 0x3F, 0xCB, 0x63, 0x16, 0x43];
+
+	const ServerApplicationKey: [u8; 16] = [0x9F, 0x02, 0x28, 0x3B, 0x6C, 0x9C, 0x07, 0xEF, 0xC2, 0x6B, 0xB9, 0xF2, 0xAC, 0x92, 0xE3, 0x56];
+
+	const ServerApplicationInitializationVector: [u8; 12] = [0xCF, 0x78, 0x2B, 0x88, 0xDD, 0x83, 0x54, 0x9A, 0xAD, 0xF1, 0xE9, 0x84];
+
+	fn cipher_suite(suite: CipherSuite) -> &'static SupportedCipherSuite
+	{
+		ALL_CIPHERSUITES.iter().find(|cipher_suite| cipher_suite.suite == suite).unwrap()
+	}
+
+	fn tls_error(result: Result<(), CompleteError>) -> TLSError
+	{
+		match result
+		{
+			Err(CompleteError::Tls(TlsInputOutputError::ProcessNewPackets(tls_error))) => tls_error,
+
+			other => panic!("Expected a TLS error, not `{:?}`", other),
+		}
+	}
+
+	fn new_session_ticket(ticket_length: usize) -> Vec<u8>
+	{
+		let mut message = vec![TlsKernelOffload::NewSessionTicketHandshakeType, 0, (ticket_length >> 8) as u8, ticket_length as u8];
+		message.resize(TlsKernelOffload::HandshakeMessageHeaderLength + ticket_length, 0xAA);
+		message
+	}
+
+	#[test]
+	fn hkdf_expand_label_derives_traffic_key_and_initialization_vector()
+	{
+		let mut key = [0u8; 16];
+		TlsKernelOffload::hkdf_expand_label(&::ring::digest::SHA256, &ServerApplicationTrafficSecret, b"key", &mut key);
+		assert_eq!(key, ServerApplicationKey);
+
+		let mut initialization_vector = [0u8; 12];
+		TlsKernelOffload::hkdf_expand_label(&::ring::digest::SHA256, &ServerApplicationTrafficSecret, b"iv", &mut initialization_vector);
+		assert_eq!(initialization_vector, ServerApplicationInitializationVector);
+	}
+
+	#[test]
+	fn crypto_information_for_aes_gcm_splits_salt_from_initialization_vector()
+	{
+		let crypto_information = TlsKernelOffload::crypto_information(cipher_suite(CipherSuite::TLS13_AES_128_GCM_SHA256), &ServerApplicationTrafficSecret, 0x0102030405060708);
+
+		assert_eq!(crypto_information.len(), 40);
+		assert_eq!(&crypto_information[0 .. 2], &0x0304u16.to_ne_bytes());
+		assert_eq!(&crypto_information[2 .. 4], &51u16.to_ne_bytes());
+		assert_eq!(&crypto_information[4 .. 12], &ServerApplicationInitializationVector[4 .. ]);
+		assert_eq!(&crypto_information[12 .. 28], &ServerApplicationKey[..]);
+		assert_eq!(&crypto_information[28 .. 32], &ServerApplicationInitializationVector[.. 4]);
+		assert_eq!(&crypto_information[32 .. 40], &[1, 2, 3, 4, 5, 6, 7, 8]);
+	}
+
+	#[test]
+	fn crypto_information_for_aes_256_gcm_and_chacha20_poly1305()
+	{
+		let aes_256_gcm = TlsKernelOffload::crypto_information(cipher_suite(CipherSuite::TLS13_AES_256_GCM_SHA384), &[0x55; 48], 0);
+		assert_eq!(aes_256_gcm.len(), 56);
+		assert_eq!(&aes_256_gcm[2 .. 4], &52u16.to_ne_bytes());
+
+		let chacha20_poly1305 = TlsKernelOffload::crypto_information(cipher_suite(CipherSuite::TLS13_CHACHA20_POLY1305_SHA256), &ServerApplicationTrafficSecret, 1);
+		let mut key = [0u8; 32];
+		TlsKernelOffload::hkdf_expand_label(&::ring::digest::SHA256, &ServerApplicationTrafficSecret, b"key", &mut key);
+		assert_eq!(chacha20_poly1305.len(), 56);
+		assert_eq!(&chacha20_poly1305[2 .. 4], &54u16.to_ne_bytes());
+		assert_eq!(&chacha20_poly1305[4 .. 16], &ServerApplicationInitializationVector[..]);
+		assert_eq!(&chacha20_poly1305[16 .. 48], &key[..]);
+		assert_eq!(&chacha20_poly1305[48 .. 56], &[0, 0, 0, 0, 0, 0, 0, 1]);
+	}
+
+	#[test]
+	fn number_of_records_counts_records()
+	{
+		assert_eq!(TlsKernelOffload::number_of_records(&[]), 0);
+		assert_eq!(TlsKernelOffload::number_of_records(&[23, 3, 3, 0, 2, 0xAA, 0xBB]), 1);
+		assert_eq!(TlsKernelOffload::number_of_records(&[23, 3, 3, 0, 1, 0xAA, 23, 3, 3, 0, 0, 23, 3, 3, 0, 2, 0xAA, 0xBB]), 3);
+	}
+
+	#[test]
+	fn number_of_records_counts_truncated_record()
+	{
+		assert_eq!(TlsKernelOffload::number_of_records(&[23, 3, 3, 0, 2, 0xAA, 0xBB, 23, 3, 3, 0, 2, 0xAA]), 2);
+		assert_eq!(TlsKernelOffload::number_of_records(&[23, 3]), 1);
+	}
+
+	#[test]
+	fn new_session_tickets_are_discarded()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		let mut messages = new_session_ticket(10);
+		messages.extend_from_slice(&new_session_ticket(0));
+		kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &messages).unwrap();
+
+		assert!(kernel_offload.received_control_record_bytes.is_empty());
+		assert!(!kernel_offload.close_notify_alert_received);
+	}
+
+	#[test]
+	fn new_session_ticket_split_across_records_is_discarded()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+		let message = new_session_ticket(300);
+
+		kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &message[.. 2]).unwrap();
+		kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &message[2 .. 100]).unwrap();
+		assert_eq!(kernel_offload.received_control_record_bytes.len(), 100);
+
+		kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &message[100 .. ]).unwrap();
+		assert!(kernel_offload.received_control_record_bytes.is_empty());
+	}
+
+	#[test]
+	fn key_update_is_an_error()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		let tls_error = tls_error(kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &[24, 0, 0, 1, 0]));
+
+		assert_eq!(tls_error, TLSError::InappropriateHandshakeMessage { expect_types: vec![HandshakeType::NewSessionTicket], got_type: HandshakeType::KeyUpdate });
+	}
+
+	#[test]
+	fn unexpected_handshake_message_after_new_session_ticket_is_an_error()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		let mut messages = new_session_ticket(4);
+		messages.extend_from_slice(&[24, 0, 0, 0]);
+
+		tls_error(kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &messages));
+	}
+
+	#[test]
+	fn too_long_handshake_message_is_an_error()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		let tls_error = tls_error(kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &[TlsKernelOffload::NewSessionTicketHandshakeType, 0x01, 0x00, 0x00]));
+
+		match tls_error
+		{
+			TLSError::PeerMisbehavedError(_) => (),
+
+			_ => panic!("Unexpected error `{:?}`", tls_error),
+		}
+	}
+
+	#[test]
+	fn close_notify_alert_is_received()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		kernel_offload.received_control_record(TlsKernelOffload::AlertContentType, &[TlsKernelOffload::WarningAlertLevel]).unwrap();
+		assert!(!kernel_offload.close_notify_alert_received);
+
+		kernel_offload.received_control_record(TlsKernelOffload::AlertContentType, &[TlsKernelOffload::CloseNotifyAlertDescription]).unwrap();
+		assert!(kernel_offload.close_notify_alert_received);
+		assert!(kernel_offload.received_control_record_bytes.is_empty());
+	}
+
+	#[test]
+	fn other_alert_is_an_error()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		let tls_error = tls_error(kernel_offload.received_control_record(TlsKernelOffload::AlertContentType, &[2, 40]));
+
+		assert_eq!(tls_error, TLSError::AlertReceived(AlertDescription::HandshakeFailure));
+	}
+
+	#[test]
+	fn alert_in_middle_of_handshake_message_is_an_error()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &new_session_ticket(10)[.. 6]).unwrap();
+
+		tls_error(kernel_offload.received_control_record(TlsKernelOffload::AlertContentType, &[TlsKernelOffload::WarningAlertLevel, TlsKernelOffload::CloseNotifyAlertDescription]));
+		assert!(!kernel_offload.close_notify_alert_received);
+	}
+
+	#[test]
+	fn change_cipher_spec_after_handshake_is_an_error()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		tls_error(kernel_offload.received_control_record(20, &[1]));
+	}
+
+	#[test]
+	fn hand_over_after_whole_new_session_ticket()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &new_session_ticket(32)[..]).unwrap();
+
+		assert!(kernel_offload.hand_over().is_ok());
+	}
+
+	#[test]
+	fn hand_over_in_middle_of_new_session_ticket_is_an_error()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		kernel_offload.received_control_record(TlsKernelOffload::HandshakeContentType, &new_session_ticket(32)[.. 10]).unwrap();
+
+		tls_error(kernel_offload.hand_over());
+	}
+
+	#[test]
+	fn hand_over_after_close_notify_alert_is_an_error()
+	{
+		let mut kernel_offload = TlsKernelOffload::default();
+
+		kernel_offload.received_control_record(TlsKernelOffload::AlertContentType, &[TlsKernelOffload::WarningAlertLevel, TlsKernelOffload::CloseNotifyAlertDescription]).unwrap();
+
+		match kernel_offload.hand_over()
+		{
+			Err(CompleteError::Tls(TlsInputOutputError::BufferReadCloseNotifyAlertReceived)) => (),
+
+			other => panic!("Expected a close notify alert, not {:?}", other),
+		}
+	}
+}
@@ -0,0 +1,39 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+thread_local!
+{
+	static LoggedTrafficSecrets: RefCell<TlsTrafficSecrets> = RefCell::new(TlsTrafficSecrets::default());
+}
+
+/// A rustls key log which captures TLS 1.3 application traffic secrets so that record encryption and decryption can be offloaded to the Linux kernel (kTLS).
+///
+/// rustls only logs secrets whilst processing received packets; a configuration's key log is shared by all of its sessions, so secrets are captured per thread around each call to `Session.process_new_packets()` (see `capture()`), which never yields.
+#[derive(Debug, Default, Copy, Clone)]
+pub(crate) struct TlsKernelOffloadKeyLog;
+
+impl KeyLog for TlsKernelOffloadKeyLog
+{
+	#[inline(always)]
+	fn log(&self, label: &str, _client_random: &[u8], secret: &[u8])
+	{
+		LoggedTrafficSecrets.with(|logged_traffic_secrets| logged_traffic_secrets.borrow_mut().log(label, secret))
+	}
+}
+
+impl TlsKernelOffloadKeyLog
+{
+	/// Calls `process_new_packets`, adding any traffic secrets it logs to `traffic_secrets`.
+	#[inline(always)]
+	pub(crate) fn capture<R>(traffic_secrets: &mut TlsTrafficSecrets, process_new_packets: impl FnOnce() -> R) -> R
+	{
+		LoggedTrafficSecrets.with(|logged_traffic_secrets| *logged_traffic_secrets.borrow_mut() = TlsTrafficSecrets::default());
+
+		let result = process_new_packets();
+
+		LoggedTrafficSecrets.with(|logged_traffic_secrets| traffic_secrets.merge(logged_traffic_secrets.replace(TlsTrafficSecrets::default())));
+
+		result
+	}
+}
@@ -14,7 +14,9 @@ stream_read_write!(TlsServerStream);
 impl<'yielder, SD: SocketData> Stream for TlsServerStream<'yielder, SD>
 {
 	/// This is a lie (ie the lifetime is ***NOT*** `'static`); the actual lifetime is ***LESS THAN*** `'yielder` and is the same as the lifetime of the underlying TLS `ServerSession`, ie the lifetime of an instance of this struct.
-	type PostHandshakeInformation = (CommonTlsPostHandshakeInformation<'static>, ServerNameIndication<'static>);
+	///
+	/// The client certificate identity is only present if the client presented a certificate, which will have been verified (see `TlsClientAuthentication`).
+	type PostHandshakeInformation = (CommonTlsPostHandshakeInformation<'static>, ServerNameIndication<'static>, Option<TlsClientCertificateIdentity>);
 
 	#[inline(always)]
 	fn post_handshake_information(&self) -> Self::PostHandshakeInformation
@@ -22,7 +24,9 @@ impl<'yielder, SD: SocketData> Stream for TlsServerStream<'yielder, SD>
 		let server_name_indication_handshake_information = self.tls_generic_stream.server_name_indication_handshake_information();
 		let server_name_indication_handshake_information: ServerNameIndication<'static> = unsafe { transmute(server_name_indication_handshake_information) };
 
-		(self.tls_generic_stream.common_tls_post_handshake_information(), server_name_indication_handshake_information)
+		let client_certificate_identity = self.tls_generic_stream.peer_certificates().and_then(TlsClientCertificateIdentity::from_certificate_chain);
+
+		(self.tls_generic_stream.common_tls_post_handshake_information(), server_name_indication_handshake_information, client_certificate_identity)
 	}
 
 	#[inline(always)]
@@ -37,6 +41,18 @@ impl<'yielder, SD: SocketData> Stream for TlsServerStream<'yielder, SD>
 		self.tls_generic_stream.write_data(write_from_buffer)
 	}
 
+	#[inline(always)]
+	fn read_vectored_data(&mut self, read_into_buffers: &mut [IoSliceMut]) -> Result<usize, CompleteError>
+	{
+		self.tls_generic_stream.read_vectored_data(read_into_buffers)
+	}
+
+	#[inline(always)]
+	fn write_vectored_data(&mut self, write_from_buffers: &[IoSlice]) -> Result<usize, CompleteError>
+	{
+		self.tls_generic_stream.write_vectored_data(write_from_buffers)
+	}
+
 	#[inline(always)]
 	fn flush_written_data(&mut self) -> Result<(), CompleteError>
 	{
@@ -50,10 +66,19 @@ impl<'yielder, SD: SocketData> Stream for TlsServerStream<'yielder, SD>
 	}
 }
 
+impl<'yielder, SD: SocketData> StreamRelayable<'yielder> for TlsServerStream<'yielder, SD>
+{
+	#[inline(always)]
+	fn stream_relay_endpoint<'a>(&'a mut self) -> StreamRelayEndpoint<'a, 'yielder> where 'yielder: 'a
+	{
+		self.tls_generic_stream.stream_relay_endpoint()
+	}
+}
+
 impl<'yielder, SD: SocketData> TlsServerStream<'yielder, SD>
 {
 	#[inline(always)]
-	pub(crate) fn new(generic_stream: GenericStream<'yielder, SD>, tls_configuration: &Arc<ServerConfig>, session_buffer_limit: usize) -> Result<Self, CompleteError>
+	pub(crate) fn new(generic_stream: GenericStream<'yielder, SD>, tls_configuration: &Arc<ServerConfig>, session_buffer_limit: usize, kernel_tls_offload: bool) -> Result<Self, CompleteError>
 	{
 		let tls_session = ServerSession::new(tls_configuration);
 
@@ -61,8 +86,33 @@ impl<'yielder, SD: SocketData> TlsServerStream<'yielder, SD>
 		(
 			Self
 			{
-				tls_generic_stream: TlsGenericStream::configure_and_handshake(generic_stream, tls_session, session_buffer_limit)?,
+				tls_generic_stream: TlsGenericStream::configure_and_handshake(generic_stream, tls_session, session_buffer_limit, kernel_tls_offload)?,
 			}
 		)
 	}
+
+	/// The protocol agreed using TLS Application-Layer Protocol Negotiation (ALPN), if any.
+	#[inline(always)]
+	pub fn application_layer_protocol_negotiation_protocol(&self) -> Option<&[u8]>
+	{
+		self.tls_generic_stream.application_layer_protocol_negotiation_protocol()
+	}
+
+	/// Is the encryption and decryption of records done by the kernel (kTLS)?
+	#[inline(always)]
+	pub fn is_kernel_tls_offloaded(&self) -> bool
+	{
+		self.tls_generic_stream.is_kernel_tls_offloaded()
+	}
+
+	/// Sends up to `length` bytes of `file`, starting at `offset`.
+	///
+	/// If the encryption of records is done by the kernel, this uses `sendfile(2)`, so that the bytes are not copied into userspace.
+	///
+	/// Waits (yields) until all `length` bytes have been sent or the end of `file` has been reached; returns the number of bytes sent.
+	#[inline(always)]
+	pub fn send_file(&mut self, file: &impl AsRawFd, offset: u64, length: usize) -> Result<usize, CompleteError>
+	{
+		self.tls_generic_stream.send_file(file, offset, length)
+	}
 }
@@ -0,0 +1,68 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A subject alternative name in a X.509 certificate.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TlsSubjectAlternativeName
+{
+	/// An email address (`rfc822Name`).
+	EmailAddress(String),
+
+	/// A DNS name (`dNSName`), eg `www.example.com` or `*.example.com`.
+	DnsName(String),
+
+	/// An Uniform Resource Identifier (`uniformResourceIdentifier`), eg a SPIFFE identity.
+	UniformResourceIdentifier(String),
+
+	/// An Internet Protocol version 4 or version 6 address (`iPAddress`).
+	InternetProtocolAddress(IpAddr),
+
+	/// Any other kind of name, as its context-specific tag number and DER-encoded content.
+	Other(u8, Vec<u8>),
+}
+
+impl TlsSubjectAlternativeName
+{
+	/// Parses the content of a `subjectAltName` extension (a `GeneralNames` sequence).
+	pub(crate) fn parse_general_names(extension_value: &[u8]) -> Option<Vec<Self>>
+	{
+		use self::TlsSubjectAlternativeName::*;
+
+		let mut general_names = DerReader::new(DerReader::new(extension_value).read(DerReader::Sequence)?);
+		let mut subject_alternative_names = Vec::new();
+		while !general_names.is_empty()
+		{
+			let (tag, content, _) = general_names.read_any()?;
+			let tag_number = tag & 0x1F;
+
+			let subject_alternative_name = match tag
+			{
+				_ if tag == DerReader::context_specific_primitive(1) => EmailAddress(from_utf8(content).ok()?.to_string()),
+
+				_ if tag == DerReader::context_specific_primitive(2) => DnsName(from_utf8(content).ok()?.to_string()),
+
+				_ if tag == DerReader::context_specific_primitive(6) => UniformResourceIdentifier(from_utf8(content).ok()?.to_string()),
+
+				_ if tag == DerReader::context_specific_primitive(7) => match content.len()
+				{
+					4 => InternetProtocolAddress(IpAddr::V4(Ipv4Addr::new(content[0], content[1], content[2], content[3]))),
+
+					16 =>
+					{
+						let mut octets = [0u8; 16];
+						octets.copy_from_slice(content);
+						InternetProtocolAddress(IpAddr::V6(Ipv6Addr::from(octets)))
+					}
+
+					_ => return None,
+				},
+
+				_ => Other(tag_number, content.to_vec()),
+			};
+			subject_alternative_names.push(subject_alternative_name);
+		}
+
+		Some(subject_alternative_names)
+	}
+}
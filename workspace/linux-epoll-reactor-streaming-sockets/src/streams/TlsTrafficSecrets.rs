@@ -0,0 +1,66 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// The TLS 1.3 application traffic secrets (`client_application_traffic_secret_0` and `server_application_traffic_secret_0`) of a session, as logged by rustls.
+///
+/// Deliberately does not implement `Debug`, so that the secrets can not be accidentally logged.
+#[derive(Default)]
+pub(crate) struct TlsTrafficSecrets
+{
+	client: Option<Vec<u8>>,
+	server: Option<Vec<u8>>,
+}
+
+impl TlsTrafficSecrets
+{
+	const ClientTrafficSecretLabel: &'static str = "CLIENT_TRAFFIC_SECRET_0";
+
+	const ServerTrafficSecretLabel: &'static str = "SERVER_TRAFFIC_SECRET_0";
+
+	/// Returns the secrets used to encrypt records sent and to decrypt records received, in that order, if both have been logged.
+	#[inline(always)]
+	pub(crate) fn transmit_and_receive(&self, is_client: bool) -> Option<(&[u8], &[u8])>
+	{
+		match (&self.client, &self.server)
+		{
+			(&Some(ref client), &Some(ref server)) => if is_client
+			{
+				Some((&client[..], &server[..]))
+			}
+			else
+			{
+				Some((&server[..], &client[..]))
+			},
+
+			_ => None,
+		}
+	}
+
+	#[inline(always)]
+	fn log(&mut self, label: &str, secret: &[u8])
+	{
+		if label == Self::ClientTrafficSecretLabel
+		{
+			self.client = Some(secret.to_vec())
+		}
+		else if label == Self::ServerTrafficSecretLabel
+		{
+			self.server = Some(secret.to_vec())
+		}
+	}
+
+	#[inline(always)]
+	fn merge(&mut self, logged: Self)
+	{
+		if logged.client.is_some()
+		{
+			self.client = logged.client
+		}
+
+		if logged.server.is_some()
+		{
+			self.server = logged.server
+		}
+	}
+}
@@ -0,0 +1,27 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// A stream which can be upgraded to TLS in place, so that a `StreamUser` implementing a `STARTTLS`-style protocol can be generic over it.
+///
+/// Implemented for `UnencryptedStream`.
+///
+/// The TLS configuration, session buffer limit and whether to try to offload the encryption and decryption of records to the kernel (kTLS) are taken from a stream factory, as if a TLS stream had been created by it.
+pub trait TlsUpgradable: Stream
+{
+	/// The type of TLS server stream upgraded to.
+	type TlsServerStream: Stream;
+
+	/// The type of TLS client stream upgraded to.
+	type TlsClientStream: Stream;
+
+	/// Upgrades to a TLS server stream in place by doing a TLS handshake, eg after receiving a `STARTTLS` command in SMTP, IMAP or XMPP, or a `SSLRequest` in PostgreSQL.
+	///
+	/// To prevent plaintext command injection (RFC 8314, CVE-2011-0411), a `StreamUser` must discard (not act upon) any data it read and buffered after the command that started the upgrade; this stream itself does not buffer.
+	fn start_tls_server(self, tls_server_stream_factory: &TlsServerStreamFactory) -> Result<Self::TlsServerStream, CompleteError>;
+
+	/// Upgrades to a TLS client stream in place by doing a TLS handshake, eg after sending a `STARTTLS` command in SMTP, IMAP or XMPP (and receiving a positive response), or a `SSLRequest` in PostgreSQL.
+	///
+	/// To prevent response injection, a `StreamUser` must discard (not act upon) any data it read and buffered after the response to the command that started the upgrade; this stream itself does not buffer.
+	fn start_tls_client(self, tls_client_stream_factory: &TlsClientStreamFactory, ascii_host_name: Rc<DNSName>) -> Result<Self::TlsClientStream, CompleteError>;
+}
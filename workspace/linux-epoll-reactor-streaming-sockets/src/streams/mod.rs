@@ -10,14 +10,23 @@ include!("read_loop_or_await_or_error.rs");
 include!("write_loop_or_await_or_error.rs");
 
 
+include!("ApplicationLayerProtocolNegotiationStreamUser.rs");
 include!("ByteCounter.rs");
 include!("InputOutputYielder.rs");
+include!("DerReader.rs");
 include!("GenericStream.rs");
 include!("SessionExt.rs");
 include!("Stream.rs");
 include!("stream_read_write.rs");
 include!("StreamUser.rs");
+include!("TlsClientCertificateIdentity.rs");
 include!("TlsClientStream.rs");
+include!("TlsEarlyDataOutcome.rs");
 include!("TlsGenericStream.rs");
+include!("TlsKernelOffload.rs");
+include!("TlsKernelOffloadKeyLog.rs");
 include!("TlsServerStream.rs");
+include!("TlsSubjectAlternativeName.rs");
+include!("TlsTrafficSecrets.rs");
+include!("TlsUpgradable.rs");
 include!("UnencryptedStream.rs");
@@ -21,6 +21,15 @@ macro_rules! stream_read_write
 				CompleteError::convert_to_io_error(self.read_data(buf))
 			}
 
+			/// Will ***never*** return `ErrorKind::Interupted` or `ErrorKind::WouldBlock`.
+			///
+			/// Smuggles and converts `CompleteError` variants in the same way as `read()`.
+			#[inline(always)]
+			fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize>
+			{
+				CompleteError::convert_to_io_error(self.read_vectored_data(bufs))
+			}
+
 			#[inline(always)]
 			unsafe fn initializer(&self) -> Initializer
 			{
@@ -43,6 +52,15 @@ macro_rules! stream_read_write
 				CompleteError::convert_to_io_error(self.write_data(buf))
 			}
 
+			/// Will ***never*** return `ErrorKind::Interupted` or `ErrorKind::WouldBlock`.
+			///
+			/// Smuggles and converts `CompleteError` variants in the same way as `write()`.
+			#[inline(always)]
+			fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize>
+			{
+				CompleteError::convert_to_io_error(self.write_vectored_data(bufs))
+			}
+
 			/// Will ***never*** return `ErrorKind::Interupted` or `ErrorKind::WouldBlock`.
 			///
 			/// Will never return `Ok(0)` unless `buf.is_empty()`.
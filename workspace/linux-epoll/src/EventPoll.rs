@@ -45,12 +45,20 @@ impl ReactorsRegistrar for EventPoll
 impl EventPollRegister for EventPoll
 {
 	#[inline(always)]
-	fn register<A: Arena<R>, R: Reactor, F: FnOnce(&mut R, R::FileDescriptor) -> Result<(), EventPollRegistrationError>>(&self, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, file_descriptor: R::FileDescriptor, add_flags: EPollAddFlags, initializer: F) -> Result<(), EventPollRegistrationError>
+	fn register<A: Arena<R>, R: Reactor, F: FnOnce(&mut R, R::FileDescriptor) -> Result<(), EventPollRegistrationError>>(&self, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, file_descriptor: R::FileDescriptor, add_flags: EPollAddFlags, companion: Option<(RawFd, EPollAddFlags)>, initializer: F) -> Result<(), EventPollRegistrationError>
 	{
 		let (mut non_null, arena_index) = arena.allocate()?;
 		let event_poll_token = EventPollToken::new(reactor_compressed_type_identifier, arena_index);
 
-		match self.epoll_file_descriptor.add(file_descriptor.as_raw_fd(), add_flags, event_poll_token.0)
+		// The companion is added first; if adding `file_descriptor` then fails, the caller closes the companion, which removes it from the event poll.
+		let result = match companion
+		{
+			None => Ok(()),
+
+			Some((companion_file_descriptor, companion_add_flags)) => self.epoll_file_descriptor.add(companion_file_descriptor, companion_add_flags, event_poll_token.0),
+		}.and_then(|()| self.epoll_file_descriptor.add(file_descriptor.as_raw_fd(), add_flags, event_poll_token.0));
+
+		match result
 		{
 			Err(error) =>
 			{
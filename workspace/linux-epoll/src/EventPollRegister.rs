@@ -6,5 +6,8 @@
 pub trait EventPollRegister
 {
 	/// Register callback for an event poll.
-	fn register<A: Arena<R>, R: Reactor, F: FnOnce(&mut R, R::FileDescriptor) -> Result<(), EventPollRegistrationError>>(&self, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, file_descriptor: R::FileDescriptor, add_flags: EPollAddFlags, initializer: F) -> Result<(), EventPollRegistrationError>;
+	///
+	/// If `companion` is supplied, its file descriptor (for example, a timer) is also added to the event poll with its add flags, and causes the same reactor to react when it is ready.
+	/// The companion file descriptor remains owned by the caller, and should be closed after `file_descriptor` (closing it removes it from the event poll); if registration fails, it should be closed immediately.
+	fn register<A: Arena<R>, R: Reactor, F: FnOnce(&mut R, R::FileDescriptor) -> Result<(), EventPollRegistrationError>>(&self, arena: &A, reactor_compressed_type_identifier: CompressedTypeIdentifier, file_descriptor: R::FileDescriptor, add_flags: EPollAddFlags, companion: Option<(RawFd, EPollAddFlags)>, initializer: F) -> Result<(), EventPollRegistrationError>;
 }
@@ -0,0 +1,122 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// Transmission Control Protocol (TCP) options common to listener and outbound sockets.
+///
+/// Each option is `None` to leave the kernel default unchanged.
+///
+/// When applied to a listener socket, Linux copies these options to each accepted socket.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct TransmissionControlProtocolOptions
+{
+	/// Disable Nagle's algorithm (`TCP_NODELAY`).
+	pub no_delay: Option<bool>,
+
+	/// Only send full segments until uncorked (`TCP_CORK`).
+	pub cork: Option<bool>,
+
+	/// Report the socket as writable only when less than this number of bytes is unsent (`TCP_NOTSENT_LOWAT`).
+	///
+	/// Reduces memory used by, and latency of, data that is buffered in the kernel, eg for HTTP/2 prioritization.
+	pub not_sent_low_water_mark_in_bytes: Option<u32>,
+
+	/// Maximum time in milliseconds that transmitted data may remain unacknowledged before the connection is forcibly closed (`TCP_USER_TIMEOUT`).
+	pub user_timeout_milliseconds: Option<u32>,
+
+	/// Congestion control algorithm, eg `bbr` or `cubic` (`TCP_CONGESTION`).
+	///
+	/// Unprivileged processes can only use algorithms listed in `/proc/sys/net/ipv4/tcp_allowed_congestion_control`.
+	pub congestion_control_algorithm: Option<String>,
+
+	/// Type of service (`IP_TOS`) or traffic class (`IPV6_TCLASS`) byte, including the DSCP and ECN bits.
+	pub type_of_service: Option<u8>,
+}
+
+impl Default for TransmissionControlProtocolOptions
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			no_delay: None,
+			cork: None,
+			not_sent_low_water_mark_in_bytes: None,
+			user_timeout_milliseconds: None,
+			congestion_control_algorithm: None,
+			type_of_service: None,
+		}
+	}
+}
+
+impl TransmissionControlProtocolOptions
+{
+	/// Not yet defined by the `libc` crate.
+	const TCP_NOTSENT_LOWAT: c_int = 25;
+
+	/// Apply these options to a socket.
+	#[inline(always)]
+	pub fn apply(&self, raw_file_descriptor: RawFd, is_internet_protocol_version_6: bool) -> io::Result<()>
+	{
+		if let Some(no_delay) = self.no_delay
+		{
+			Self::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_NODELAY, no_delay as c_int)?;
+		}
+
+		if let Some(cork) = self.cork
+		{
+			Self::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_CORK, cork as c_int)?;
+		}
+
+		if let Some(not_sent_low_water_mark_in_bytes) = self.not_sent_low_water_mark_in_bytes
+		{
+			Self::set_socket_option(raw_file_descriptor, IPPROTO_TCP, Self::TCP_NOTSENT_LOWAT, not_sent_low_water_mark_in_bytes as c_int)?;
+		}
+
+		if let Some(user_timeout_milliseconds) = self.user_timeout_milliseconds
+		{
+			Self::set_socket_option(raw_file_descriptor, IPPROTO_TCP, TCP_USER_TIMEOUT, user_timeout_milliseconds as c_int)?;
+		}
+
+		if let Some(ref congestion_control_algorithm) = self.congestion_control_algorithm
+		{
+			let result = unsafe { setsockopt(raw_file_descriptor, IPPROTO_TCP, TCP_CONGESTION, congestion_control_algorithm.as_ptr() as *const c_void, congestion_control_algorithm.len() as socklen_t) };
+			if unlikely!(result != 0)
+			{
+				return Err(io::Error::last_os_error())
+			}
+		}
+
+		if let Some(type_of_service) = self.type_of_service
+		{
+			if is_internet_protocol_version_6
+			{
+				Self::set_socket_option(raw_file_descriptor, IPPROTO_IPV6, IPV6_TCLASS, type_of_service as c_int)?;
+			}
+			else
+			{
+				Self::set_socket_option(raw_file_descriptor, IPPROTO_IP, IP_TOS, type_of_service as c_int)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Set an integer socket option.
+	#[inline(always)]
+	pub fn set_socket_option(raw_file_descriptor: RawFd, level: c_int, option_name: c_int, value: c_int) -> io::Result<()>
+	{
+		let result = unsafe { setsockopt(raw_file_descriptor, level, option_name, &value as *const c_int as *const c_void, size_of::<c_int>() as socklen_t) };
+		if likely!(result == 0)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(io::Error::last_os_error())
+		}
+	}
+}
@@ -0,0 +1,64 @@
+// This file is part of linux-epoll. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT. No part of linux-epoll, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2019 The developers of linux-epoll. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/linux-epoll/master/COPYRIGHT.
+
+
+/// An Unix domain socket address as used by `libc`, shared by listeners and message sockets.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UnixDomainSocketRawAddress<'a>
+{
+	/// A socket file in a file system.
+	Path(&'a Path),
+
+	/// A Linux abstract namespace address without the leading ASCII NUL.
+	Abstract(&'a [u8]),
+
+	/// Just the address family.
+	///
+	/// When binding, the kernel chooses a unique abstract namespace address (autobind).
+	Unnamed,
+}
+
+impl<'a> UnixDomainSocketRawAddress<'a>
+{
+	/// Maximum length of a socket file path or abstract name.
+	pub const MaximumLength: usize = 107;
+
+	/// Converts to a `sockaddr_un` and its length.
+	#[inline(always)]
+	pub fn to_sockaddr_un(self) -> io::Result<(libc_sockaddr_un, socklen_t)>
+	{
+		use self::UnixDomainSocketRawAddress::*;
+
+		#[inline(always)]
+		fn copy_into(socket_address: &mut libc_sockaddr_un, offset: usize, bytes: &[u8]) -> io::Result<usize>
+		{
+			if unlikely!(bytes.len() > UnixDomainSocketRawAddress::MaximumLength)
+			{
+				return Err(io::Error::new(io::ErrorKind::InvalidInput, "Unix domain socket address is too long"))
+			}
+
+			for (index, byte) in bytes.iter().enumerate()
+			{
+				socket_address.sun_path[offset + index] = *byte as c_char;
+			}
+
+			Ok(offset + bytes.len())
+		}
+
+		let mut socket_address: libc_sockaddr_un = unsafe { zeroed() };
+		socket_address.sun_family = AF_UNIX as sa_family_t;
+
+		let path_length = match self
+		{
+			// Includes the trailing ASCII NUL, which is already present as `socket_address` is zeroed.
+			Path(path) => copy_into(&mut socket_address, 0, path.as_os_str().as_bytes())? + 1,
+
+			// `sun_path[0]` remains ASCII NUL to indicate the abstract namespace.
+			Abstract(abstract_name) => copy_into(&mut socket_address, 1, abstract_name)?,
+
+			Unnamed => 0,
+		};
+
+		Ok((socket_address, (size_of::<sa_family_t>() + path_length) as socklen_t))
+	}
+}
@@ -26,11 +26,28 @@ use ::file_descriptors::*;
 use ::file_descriptors::epoll::*;
 use ::file_descriptors::epoll::syscall::*;
 use ::file_descriptors::socket::*;
+use ::libc::AF_UNIX;
+use ::libc::c_char;
+use ::libc::c_int;
+use ::libc::c_void;
+use ::libc::IP_TOS;
+use ::libc::IPPROTO_IP;
+use ::libc::IPPROTO_IPV6;
+use ::libc::IPPROTO_TCP;
+use ::libc::IPV6_TCLASS;
+use ::libc::sa_family_t;
+use ::libc::setsockopt;
 use ::libc::SIGHUP;
 use ::libc::SIGINT;
 use ::libc::SIGQUIT;
 use ::libc::sigset_t;
 use ::libc::SIGTERM;
+use ::libc::sockaddr_un as libc_sockaddr_un;
+use ::libc::socklen_t;
+use ::libc::TCP_CONGESTION;
+use ::libc::TCP_CORK;
+use ::libc::TCP_NODELAY;
+use ::libc::TCP_USER_TIMEOUT;
 use ::message_dispatch::*;
 pub use ::message_dispatch::erased_boxed_functions::CompressedTypeIdentifier;
 use ::std::cell::Cell;
@@ -44,13 +61,18 @@ use ::std::fmt;
 use ::std::fmt::Debug;
 use ::std::fmt::Display;
 use ::std::fmt::Formatter;
+use ::std::io;
 use ::std::mem::ManuallyDrop;
+use ::std::mem::size_of;
 use ::std::mem::transmute;
 use ::std::mem::uninitialized;
 use ::std::mem::zeroed;
 use ::std::ops::Deref;
+use ::std::os::unix::ffi::OsStrExt;
 use ::std::os::unix::io::AsRawFd;
+use ::std::os::unix::io::RawFd;
 use ::std::panic::*;
+use ::std::path::Path;
 use ::std::ptr::drop_in_place;
 use ::std::ptr::NonNull;
 use ::std::sync::Arc;
@@ -75,3 +97,5 @@ include!("JoinHandles.rs");
 include!("Process.rs");
 include!("ProcessConfiguration.rs");
 include!("Registration.rs");
+include!("TransmissionControlProtocolOptions.rs");
+include!("UnixDomainSocketRawAddress.rs");